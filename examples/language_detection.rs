@@ -0,0 +1,33 @@
+use mediapipe_rs::tasks::text::LanguageDetectorBuilder;
+
+fn parse_args() -> Result<String, Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 2 {
+        return Err(format!("Usage {} model_path", args[0]).into());
+    }
+    Ok(args[1].clone())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let model_path = parse_args()?;
+
+    let language_detector = LanguageDetectorBuilder::new()
+        .model_asset_path(model_path) // set model path
+        .max_results(2) // set max result
+        .finalize()?; // create a language detector
+
+    let mut session = language_detector.new_session()?;
+    let texts = [
+        "To be, or not to be, that is the question",
+        "Il y a beaucoup de bouches qui parlent et fort peu de têtes qui pensent.",
+        "分久必合合久必分",
+    ];
+
+    // detect and show formatted result message
+    for text in texts {
+        let result = session.detect(&text)?;
+        println!("`{}` -- {}", text, result);
+    }
+
+    Ok(())
+}
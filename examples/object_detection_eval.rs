@@ -0,0 +1,48 @@
+fn parse_args() -> Result<(String, String, String), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 4 {
+        return Err(format!(
+            "Usage {} model_path coco_annotations_json_path images_dir",
+            args[0]
+        )
+        .into());
+    }
+    Ok((args[1].clone(), args[2].clone(), args[3].clone()))
+}
+
+use mediapipe_rs::postprocess::eval::{CocoDataset, CocoEvaluator};
+use mediapipe_rs::tasks::vision::ObjectDetectorBuilder;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (model_path, annotations_path, images_dir) = parse_args()?;
+
+    let dataset = CocoDataset::from_path(annotations_path)?;
+    let detector = ObjectDetectorBuilder::new()
+        .model_asset_path(model_path) // set model path
+        .max_results(100) // the COCO metrics use max 100 detections per image
+        .score_threshold(0.) // keep the low score detections for the precision-recall curve
+        .finalize()?; // create a object detector
+    let mut session = detector.new_session()?;
+
+    let mut evaluator = CocoEvaluator::new(&dataset);
+    for (i, image) in dataset.images.iter().enumerate() {
+        let path = std::path::Path::new(&images_dir).join(&image.file_name);
+        let input_img = match image::open(&path) {
+            Ok(img) => img,
+            Err(e) => {
+                eprintln!("Skip image `{}`: {}", path.display(), e);
+                continue;
+            }
+        };
+        let detection_result = session.detect(&input_img)?;
+        evaluator.add(image.id, &detection_result)?;
+        if (i + 1) % 100 == 0 {
+            println!("Processed {}/{} images", i + 1, dataset.images.len());
+        }
+    }
+
+    // show the COCO metrics
+    println!("{}", evaluator.evaluate());
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+#!/bin/bash
+
+set -ex
+
+source "$HOME/.cargo/env"
+
+CURRENT="$(realpath "$(dirname -- "$0")")"
+
+pushd "${CURRENT}/.."
+
+# replay the model outputs in `assets/testdata/recordings` natively, without WasmEdge
+cargo test --release --target x86_64-unknown-linux-gnu --features tract -- --nocapture
+
+popd
@@ -118,6 +118,7 @@ face_detection_init() {
     # now models have not be released
     model_urls=()
     curl -SL "https://storage.googleapis.com/mediapipe-assets/face_detection_short_range.tflite?generation=1677044301978921" -o "face_detection_short_range.tflite"
+    curl -SL "https://storage.googleapis.com/mediapipe-assets/face_detection_full_range_sparse.tflite" -o "face_detection_full_range_sparse.tflite"
 
     for url in "${model_urls[@]}"; do
       curl -sLO "${url}"
@@ -158,6 +159,21 @@ text_classification_init() {
   popd
 }
 
+language_detection_init() {
+  language_detection_dir="${model_path}/language_detection"
+  mkdir -p "${language_detection_dir}"
+  pushd "${language_detection_dir}"
+
+  model_urls=("https://storage.googleapis.com/mediapipe-models/language_detector/language_detector/float32/latest/language_detector.tflite"
+  )
+
+  for url in "${model_urls[@]}"; do
+    curl -sLO "${url}"
+  done
+
+  popd
+}
+
 object_detection_init
 image_classification_init
 gesture_recognition_init
@@ -167,3 +183,4 @@ image_embedding_init
 face_detection_init
 audio_classification_init
 text_classification_init
+language_detection_init
@@ -0,0 +1,136 @@
+mod npy;
+mod replay;
+mod wasi_nn;
+pub use replay::{RecordingBackend, ReplayBackend, ReplayKey};
+pub use wasi_nn::WasiNNBackend;
+
+#[cfg(feature = "tract")]
+mod tract;
+#[cfg(feature = "tract")]
+pub use self::tract::TractBackend;
+
+use crate::{Device, Error, GraphEncoding, SharedSlice, TensorType};
+use std::sync::Arc;
+
+/// An inference engine which can build graphs from model binaries.
+///
+/// The tasks never use the engine directly, they only build graphs from it and run the graphs using
+/// [`ExecutionContext`]. So the same task code can run on different engines.
+pub trait InferenceBackend: Send + Sync {
+    /// Build a graph from the model binary.
+    ///
+    /// The graph does not need to keep the model binary, the tasks keep it while the graph lives.
+    fn build_graph(
+        &self,
+        encoding: GraphEncoding,
+        target: Device,
+        model: SharedSlice<u8>,
+    ) -> Result<Box<dyn InferenceGraph>, Error>;
+}
+
+/// A model graph built by an [`InferenceBackend`].
+pub trait InferenceGraph {
+    /// Create a new execution context, which holds the input and output tensors.
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error>;
+}
+
+/// The execution context to do inference using a [`InferenceGraph`].
+///
+/// All tensor data are raw bytes in native endian.
+pub trait ExecutionContext {
+    /// Set the input tensor data.
+    fn set_input(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: &[u8],
+    ) -> Result<(), Error>;
+
+    /// Do inference.
+    fn compute(&mut self) -> Result<(), Error>;
+
+    /// Copy the output tensor data to ```output```, return the output bytes size.
+    fn get_output(&mut self, index: usize, output: &mut [u8]) -> Result<usize, Error>;
+}
+
+/// Get the default inference backend.
+///
+/// The default backend is [`WasiNNBackend`].
+/// If the feature ```tract``` is enabled and the target is not ```wasm32```, the default is ```TractBackend```.
+///
+/// [`ReplayBackend`] and [`RecordingBackend`] are never the default, they must be set explicitly by the task builders.
+#[inline(always)]
+pub fn default_backend() -> Arc<dyn InferenceBackend> {
+    #[cfg(all(feature = "tract", not(target_arch = "wasm32")))]
+    return Arc::new(TractBackend);
+    #[cfg(not(all(feature = "tract", not(target_arch = "wasm32"))))]
+    return Arc::new(WasiNNBackend);
+}
+
+/// Graph used by tasks.
+///
+/// It keeps the model binary, so the model resources parsed from the binary are valid as long as the graph.
+pub(crate) struct Graph {
+    graph: Box<dyn InferenceGraph>,
+    _model: SharedSlice<u8>,
+}
+
+impl Graph {
+    #[inline(always)]
+    pub(crate) fn new(
+        backend: &dyn InferenceBackend,
+        encoding: GraphEncoding,
+        target: Device,
+        model: SharedSlice<u8>,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            graph: backend.build_graph(encoding, target, model.clone())?,
+            _model: model,
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn init_execution_context(&self) -> Result<GraphExecutionContext<'_>, Error> {
+        Ok(GraphExecutionContext(self.graph.init_execution_context()?))
+    }
+}
+
+/// Execution context used by tasks, which can accept typed buffers.
+pub(crate) struct GraphExecutionContext<'graph>(Box<dyn ExecutionContext + 'graph>);
+
+impl<'graph> GraphExecutionContext<'graph> {
+    #[inline(always)]
+    pub(crate) fn set_input<T: Copy>(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: &[T],
+    ) -> Result<(), Error> {
+        let data = unsafe {
+            std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
+        };
+        self.0.set_input(index, tensor_type, dimensions, data)
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute(&mut self) -> Result<(), Error> {
+        self.0.compute()
+    }
+
+    #[inline(always)]
+    pub(crate) fn get_output<T: Copy>(
+        &mut self,
+        index: usize,
+        output: &mut [T],
+    ) -> Result<usize, Error> {
+        let output = unsafe {
+            std::slice::from_raw_parts_mut(
+                output.as_mut_ptr() as *mut u8,
+                std::mem::size_of_val(output),
+            )
+        };
+        self.0.get_output(index, output)
+    }
+}
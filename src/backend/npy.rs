@@ -0,0 +1,175 @@
+use crate::{Error, TensorType};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Get the numpy ```descr``` string for the tensor type.
+#[inline(always)]
+pub(crate) fn tensor_type_descr(tensor_type: TensorType) -> Option<&'static str> {
+    match tensor_type {
+        TensorType::F16 => Some("<f2"),
+        TensorType::F32 => Some("<f4"),
+        TensorType::U8 => Some("|u1"),
+        TensorType::I32 => Some("<i4"),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+/// Get the element bytes size of the numpy ```descr```, such as ```<f4```.
+#[inline(always)]
+pub(crate) fn descr_bytes(descr: &str) -> usize {
+    descr.get(2..).and_then(|s| s.parse().ok()).unwrap_or(0)
+}
+
+/// Write tensor data to ```.npy``` (version 1.0) format.
+pub(crate) fn write_npy(descr: &str, shape: &[usize], data: &[u8]) -> Vec<u8> {
+    let shape_str = match shape.len() {
+        1 => format!("({},)", shape[0]),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+        descr, shape_str
+    );
+    // the total header length must be aligned to 64 bytes and end with '\n'
+    let total = MAGIC.len() + 4 + header.len() + 1;
+    let padding = total.div_ceil(64) * 64 - total;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+
+    let mut res = Vec::with_capacity(MAGIC.len() + 4 + header.len() + data.len());
+    res.extend_from_slice(MAGIC);
+    res.extend_from_slice(&[1, 0]);
+    res.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    res.extend_from_slice(header.as_bytes());
+    res.extend_from_slice(data);
+    res
+}
+
+/// Parse the ```.npy``` file, return the ```descr```, shape and the raw data.
+pub(crate) fn read_npy(buf: &[u8]) -> Result<(&str, Vec<usize>, &[u8]), Error> {
+    if buf.len() < MAGIC.len() + 4 || &buf[..MAGIC.len()] != MAGIC {
+        return Err(Error::ArgumentError("Invalid `.npy` file magic".into()));
+    }
+    let major = buf[MAGIC.len()];
+    let (header_start, header_len) = match major {
+        1 => (
+            MAGIC.len() + 4,
+            u16::from_le_bytes([buf[MAGIC.len() + 2], buf[MAGIC.len() + 3]]) as usize,
+        ),
+        2 | 3 => {
+            if buf.len() < MAGIC.len() + 6 {
+                return Err(Error::ArgumentError("Invalid `.npy` file header".into()));
+            }
+            let mut l = [0u8; 4];
+            l.copy_from_slice(&buf[MAGIC.len() + 2..MAGIC.len() + 6]);
+            (MAGIC.len() + 6, u32::from_le_bytes(l) as usize)
+        }
+        _ => {
+            return Err(Error::ArgumentError(format!(
+                "Unsupported `.npy` file version `{}`",
+                major
+            )));
+        }
+    };
+    if buf.len() < header_start + header_len {
+        return Err(Error::ArgumentError("Invalid `.npy` file header".into()));
+    }
+    let header = std::str::from_utf8(&buf[header_start..header_start + header_len])
+        .map_err(|e| Error::ArgumentError(format!("Invalid `.npy` file header: `{}`", e)))?;
+
+    let descr = header_value(header, "descr")
+        .and_then(|d| d.strip_prefix('\'').and_then(|d| d.strip_suffix('\'')))
+        .ok_or_else(|| Error::ArgumentError("Cannot find `descr` in `.npy` file".into()))?;
+    if descr.starts_with('>') {
+        return Err(Error::ArgumentError(
+            "Big-endian `.npy` file is unsupported".into(),
+        ));
+    }
+    if header_value(header, "fortran_order") != Some("False") {
+        return Err(Error::ArgumentError(
+            "Fortran order `.npy` file is unsupported".into(),
+        ));
+    }
+    let shape_str = header_value(header, "shape")
+        .and_then(|s| s.strip_prefix('(').and_then(|s| s.strip_suffix(')')))
+        .ok_or_else(|| Error::ArgumentError("Cannot find `shape` in `.npy` file".into()))?;
+    let mut shape = Vec::new();
+    for s in shape_str
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+    {
+        shape.push(
+            s.parse::<usize>()
+                .map_err(|_| Error::ArgumentError(format!("Invalid `.npy` shape `{}`", s)))?,
+        );
+    }
+    Ok((descr, shape, &buf[header_start + header_len..]))
+}
+
+// get the raw value string of the key from the header dict
+fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let key = format!("'{}':", key);
+    let start = header.find(key.as_str())? + key.len();
+    let value = header[start..].trim_start();
+    let end = if value.starts_with('(') {
+        value.find(')')? + 1
+    } else {
+        value.find([',', '}'])?
+    };
+    Some(value[..end].trim())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_npy() {
+        let data: Vec<u8> = [1f32, 2., 3., 4., 5., 6.]
+            .iter()
+            .flat_map(|f| f.to_le_bytes())
+            .collect();
+        let buf = write_npy("<f4", &[2, 3], &data);
+        assert_eq!((buf.len() - data.len()) % 64, 0);
+        let (descr, shape, d) = read_npy(&buf).unwrap();
+        assert_eq!(descr, "<f4");
+        assert_eq!(shape, vec![2, 3]);
+        assert_eq!(d, data.as_slice());
+
+        let buf = write_npy("|u1", &[4], &[1, 2, 3, 4]);
+        let (descr, shape, d) = read_npy(&buf).unwrap();
+        assert_eq!(descr, "|u1");
+        assert_eq!(shape, vec![4]);
+        assert_eq!(d, &[1, 2, 3, 4]);
+
+        let buf = write_npy("<i4", &[], &[1, 0, 0, 0]);
+        assert_eq!(read_npy(&buf).unwrap().1, Vec::<usize>::new());
+
+        // header written by numpy: `np.save(f, np.zeros((1, 2), dtype=np.uint8))`
+        let mut buf =
+            b"\x93NUMPY\x01\x00v\x00{'descr': '|u1', 'fortran_order': False, 'shape': (1, 2), }"
+                .to_vec();
+        buf.resize(118 + 10 - 1, b' ');
+        buf.push(b'\n');
+        buf.extend_from_slice(&[0, 0]);
+        let (_, shape, d) = read_npy(&buf).unwrap();
+        assert_eq!(shape, vec![1, 2]);
+        assert_eq!(d, &[0, 0]);
+
+        assert!(read_npy(b"\x93NUMPY").is_err());
+        assert!(read_npy(&buf[..20]).is_err());
+        let mut fortran = write_npy("<f4", &[2], &[0; 8]);
+        let pos = fortran.windows(5).position(|w| w == b"False").unwrap();
+        fortran[pos..pos + 5].copy_from_slice(b"True ");
+        assert!(read_npy(&fortran).is_err());
+    }
+}
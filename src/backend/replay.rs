@@ -0,0 +1,574 @@
+use super::npy::{descr_bytes, read_npy, tensor_type_descr, write_npy};
+use super::{ExecutionContext, InferenceBackend, InferenceGraph};
+use crate::{Device, Error, GraphEncoding, SharedSlice, TensorType};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// How to find the recorded outputs for one inference.
+///
+/// The recorded outputs are saved in directory ```{dir}/{model_hash}/{key}/```, the output tensor ```i``` is saved as
+/// ```output_{i}.npy``` (or raw bytes ```output_{i}.bin```).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayKey {
+    /// Key is ```input_{hash}```, the hash of all input tensors.
+    /// The recordings can be shared by multiple tasks and threads.
+    InputHash,
+    /// Key is ```call_{n}```, the ```n```-th inference of the graph.
+    /// It only works when every graph is built once, such as a program which only has one task instance.
+    CallOrder,
+}
+
+impl Default for ReplayKey {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::InputHash
+    }
+}
+
+/// Inference backend which does not execute models, but returns the output tensors recorded by [`RecordingBackend`].
+///
+/// It makes the pre-process and post-process code testable without any inference engine.
+///
+/// ```rust
+/// use mediapipe_rs::backend::ReplayBackend;
+/// use mediapipe_rs::tasks::vision::ImageClassifierBuilder;
+/// use std::sync::Arc;
+///
+/// let classifier = ImageClassifierBuilder::new()
+///     .model_asset_path(model_path)
+///     .inference_backend(Arc::new(ReplayBackend::new("./recordings")))
+///     .finalize()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplayBackend {
+    dir: PathBuf,
+    key: ReplayKey,
+}
+
+impl ReplayBackend {
+    /// Create a new replay backend which reads recordings from the directory.
+    #[inline(always)]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            key: Default::default(),
+        }
+    }
+
+    /// Set the way to find recordings. Default is [`ReplayKey::InputHash`].
+    #[inline(always)]
+    pub fn key(mut self, key: ReplayKey) -> Self {
+        self.key = key;
+        self
+    }
+}
+
+impl InferenceBackend for ReplayBackend {
+    fn build_graph(
+        &self,
+        _encoding: GraphEncoding,
+        _target: Device,
+        model: SharedSlice<u8>,
+    ) -> Result<Box<dyn InferenceGraph>, Error> {
+        Ok(Box::new(ReplayGraph {
+            dir: self.dir.join(model_dir_name(model.as_ref())),
+            key: self.key,
+            calls: Cell::new(0),
+        }))
+    }
+}
+
+struct ReplayGraph {
+    dir: PathBuf,
+    key: ReplayKey,
+    calls: Cell<usize>,
+}
+
+impl InferenceGraph for ReplayGraph {
+    #[inline(always)]
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error> {
+        Ok(Box::new(ReplayExecutionContext {
+            graph: self,
+            inputs: InputsHasher::default(),
+            outputs: BTreeMap::new(),
+        }))
+    }
+}
+
+struct ReplayExecutionContext<'graph> {
+    graph: &'graph ReplayGraph,
+    inputs: InputsHasher,
+    outputs: BTreeMap<usize, Vec<u8>>,
+}
+
+impl<'graph> ExecutionContext for ReplayExecutionContext<'graph> {
+    #[inline(always)]
+    fn set_input(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: &[u8],
+    ) -> Result<(), Error> {
+        // check the inputs as the real backends do
+        if let Some(descr) = tensor_type_descr(tensor_type) {
+            let expect = descr_bytes(descr) * dimensions.iter().product::<usize>();
+            if expect != data.len() {
+                return Err(Error::ArgumentError(format!(
+                    "Input `{}` bytes size is `{}`, but the tensor type and dimensions `{:?}` need `{}`",
+                    index,
+                    data.len(),
+                    dimensions,
+                    expect
+                )));
+            }
+        }
+        self.inputs.set_input(index, dimensions, data);
+        Ok(())
+    }
+
+    fn compute(&mut self) -> Result<(), Error> {
+        let dir = self.graph.dir.join(key_dir_name(
+            self.graph.key,
+            &self.graph.calls,
+            &self.inputs,
+        ));
+        self.outputs.clear();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let index = match output_file_index(&path) {
+                Some(i) => i,
+                None => continue,
+            };
+            let buf = std::fs::read(&path)?;
+            let data = if path.extension().is_some_and(|e| e == "npy") {
+                read_npy(&buf)?.2.to_vec()
+            } else {
+                buf
+            };
+            self.outputs.insert(index, data);
+        }
+        if self.outputs.is_empty() {
+            return Err(Error::ArgumentError(format!(
+                "Cannot find any recorded output in `{}`",
+                dir.display()
+            )));
+        }
+        Ok(())
+    }
+
+    fn get_output(&mut self, index: usize, output: &mut [u8]) -> Result<usize, Error> {
+        let data = self.outputs.get(&index).ok_or_else(|| {
+            Error::ArgumentError(format!("Cannot find recorded output `{}`", index))
+        })?;
+        if output.len() < data.len() {
+            return Err(Error::ArgumentError(format!(
+                "Output buffer size `{}` is too small, expect at least `{}`",
+                output.len(),
+                data.len()
+            )));
+        }
+        output[..data.len()].copy_from_slice(data);
+        Ok(data.len())
+    }
+}
+
+/// Inference backend which runs models using another backend, and saves all outputs which the tasks read.
+/// The recordings can be used by [`ReplayBackend`].
+///
+/// If the output tensor types and shapes can be parsed from the model, outputs are saved as ```.npy``` files,
+/// otherwise raw ```.bin``` files.
+///
+/// ```rust
+/// use mediapipe_rs::backend::{RecordingBackend, WasiNNBackend};
+/// use mediapipe_rs::tasks::vision::ImageClassifierBuilder;
+/// use std::sync::Arc;
+///
+/// let classifier = ImageClassifierBuilder::new()
+///     .model_asset_path(model_path)
+///     .inference_backend(Arc::new(RecordingBackend::new(Arc::new(WasiNNBackend), "./recordings")))
+///     .finalize()?;
+/// ```
+#[derive(Clone)]
+pub struct RecordingBackend {
+    inner: Arc<dyn InferenceBackend>,
+    dir: PathBuf,
+    key: ReplayKey,
+}
+
+impl RecordingBackend {
+    /// Create a new recording backend, which uses ```inner``` backend to run models and saves outputs to the directory.
+    #[inline(always)]
+    pub fn new(inner: Arc<dyn InferenceBackend>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            dir: dir.into(),
+            key: Default::default(),
+        }
+    }
+
+    /// Set the way to save recordings. Default is [`ReplayKey::InputHash`].
+    #[inline(always)]
+    pub fn key(mut self, key: ReplayKey) -> Self {
+        self.key = key;
+        self
+    }
+}
+
+impl InferenceBackend for RecordingBackend {
+    fn build_graph(
+        &self,
+        encoding: GraphEncoding,
+        target: Device,
+        model: SharedSlice<u8>,
+    ) -> Result<Box<dyn InferenceGraph>, Error> {
+        let dir = self.dir.join(model_dir_name(model.as_ref()));
+        // the output tensor information is used to save `.npy` files
+        let outputs_info = match crate::model::parse_model(model.as_ref()) {
+            Ok(m) => (0..m.output_tensor_count())
+                .map(|i| Some((m.output_tensor_type(i)?, m.output_tensor_shape(i)?.to_vec())))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        let inner = self.inner.build_graph(encoding, target, model)?;
+        Ok(Box::new(RecordingGraph {
+            inner,
+            dir,
+            key: self.key,
+            calls: Cell::new(0),
+            outputs_info,
+        }))
+    }
+}
+
+struct RecordingGraph {
+    inner: Box<dyn InferenceGraph>,
+    dir: PathBuf,
+    key: ReplayKey,
+    calls: Cell<usize>,
+    outputs_info: Vec<Option<(TensorType, Vec<usize>)>>,
+}
+
+impl InferenceGraph for RecordingGraph {
+    #[inline(always)]
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error> {
+        Ok(Box::new(RecordingExecutionContext {
+            inner: self.inner.init_execution_context()?,
+            graph: self,
+            inputs: InputsHasher::default(),
+            dir: None,
+        }))
+    }
+}
+
+struct RecordingExecutionContext<'graph> {
+    inner: Box<dyn ExecutionContext + 'graph>,
+    graph: &'graph RecordingGraph,
+    inputs: InputsHasher,
+    dir: Option<PathBuf>,
+}
+
+impl<'graph> ExecutionContext for RecordingExecutionContext<'graph> {
+    #[inline(always)]
+    fn set_input(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: &[u8],
+    ) -> Result<(), Error> {
+        self.inputs.set_input(index, dimensions, data);
+        self.inner.set_input(index, tensor_type, dimensions, data)
+    }
+
+    fn compute(&mut self) -> Result<(), Error> {
+        self.inner.compute()?;
+        let dir = self.graph.dir.join(key_dir_name(
+            self.graph.key,
+            &self.graph.calls,
+            &self.inputs,
+        ));
+        std::fs::create_dir_all(&dir)?;
+        self.dir = Some(dir);
+        Ok(())
+    }
+
+    fn get_output(&mut self, index: usize, output: &mut [u8]) -> Result<usize, Error> {
+        let size = self.inner.get_output(index, output)?;
+        let dir = match self.dir {
+            Some(ref d) => d,
+            None => return Ok(size),
+        };
+
+        let data = &output[..size];
+        let npy = match self.graph.outputs_info.get(index) {
+            Some(Some((tensor_type, shape))) => match tensor_type_descr(*tensor_type) {
+                // the output size must be consistent with the shape
+                Some(descr) if descr_bytes(descr) * shape.iter().product::<usize>() == size => {
+                    Some(write_npy(descr, shape, data))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        match npy {
+            Some(buf) => std::fs::write(dir.join(format!("output_{}.npy", index)), buf)?,
+            None => std::fs::write(dir.join(format!("output_{}.bin", index)), data)?,
+        }
+        Ok(size)
+    }
+}
+
+/// FNV-1a 64-bit hash, which is stable on all platforms.
+#[derive(Clone, Copy)]
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    #[inline(always)]
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    #[inline(always)]
+    fn write_usize(&mut self, v: usize) {
+        self.write(&(v as u64).to_le_bytes());
+    }
+}
+
+#[derive(Default)]
+struct InputsHasher {
+    inputs: BTreeMap<usize, u64>,
+}
+
+impl InputsHasher {
+    #[inline(always)]
+    fn set_input(&mut self, index: usize, dimensions: &[usize], data: &[u8]) {
+        let mut h = Fnv1a::default();
+        h.write_usize(dimensions.len());
+        for d in dimensions {
+            h.write_usize(*d);
+        }
+        h.write(data);
+        self.inputs.insert(index, h.0);
+    }
+
+    #[inline(always)]
+    fn hash(&self) -> u64 {
+        let mut h = Fnv1a::default();
+        for (index, hash) in self.inputs.iter() {
+            h.write_usize(*index);
+            h.write(&hash.to_le_bytes());
+        }
+        h.0
+    }
+}
+
+#[inline(always)]
+fn model_dir_name(model: &[u8]) -> String {
+    let mut h = Fnv1a::default();
+    h.write(model);
+    format!("model_{:016x}", h.0)
+}
+
+#[inline(always)]
+fn key_dir_name(key: ReplayKey, calls: &Cell<usize>, inputs: &InputsHasher) -> String {
+    match key {
+        ReplayKey::InputHash => format!("input_{:016x}", inputs.hash()),
+        ReplayKey::CallOrder => {
+            let n = calls.get();
+            calls.set(n + 1);
+            format!("call_{}", n)
+        }
+    }
+}
+
+#[inline(always)]
+fn output_file_index(path: &Path) -> Option<usize> {
+    let ext = path.extension()?;
+    if ext != "npy" && ext != "bin" {
+        return None;
+    }
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix("output_")?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // a fake backend which outputs the sum of all input bytes as `f32` and the first input
+    struct SumBackend;
+
+    struct SumContext(Vec<u8>, f32);
+
+    impl InferenceBackend for SumBackend {
+        fn build_graph(
+            &self,
+            _: GraphEncoding,
+            _: Device,
+            _: SharedSlice<u8>,
+        ) -> Result<Box<dyn InferenceGraph>, Error> {
+            Ok(Box::new(SumBackend))
+        }
+    }
+
+    impl InferenceGraph for SumBackend {
+        fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error> {
+            Ok(Box::new(SumContext(Vec::new(), 0.)))
+        }
+    }
+
+    impl ExecutionContext for SumContext {
+        fn set_input(
+            &mut self,
+            _: usize,
+            _: TensorType,
+            _: &[usize],
+            d: &[u8],
+        ) -> Result<(), Error> {
+            self.0 = d.to_vec();
+            Ok(())
+        }
+
+        fn compute(&mut self) -> Result<(), Error> {
+            self.1 = self.0.iter().map(|b| *b as f32).sum();
+            Ok(())
+        }
+
+        fn get_output(&mut self, index: usize, output: &mut [u8]) -> Result<usize, Error> {
+            let d = match index {
+                0 => self.1.to_ne_bytes().to_vec(),
+                _ => self.0.clone(),
+            };
+            output[..d.len()].copy_from_slice(&d);
+            Ok(d.len())
+        }
+    }
+
+    fn run(
+        backend: &dyn InferenceBackend,
+        model: &[u8],
+        inputs: &[&[u8]],
+    ) -> Result<Vec<(f32, Vec<u8>)>, Error> {
+        let graph = backend.build_graph(
+            GraphEncoding::TensorflowLite,
+            Device::CPU,
+            model.to_vec().into(),
+        )?;
+        let mut ctx = graph.init_execution_context()?;
+        let mut res = Vec::new();
+        for input in inputs {
+            ctx.set_input(0, TensorType::U8, &[input.len()], input)?;
+            ctx.compute()?;
+            let mut sum = [0u8; 4];
+            assert_eq!(ctx.get_output(0, &mut sum)?, 4);
+            let mut echo = vec![0u8; 16];
+            let size = ctx.get_output(1, &mut echo)?;
+            echo.truncate(size);
+            res.push((f32::from_ne_bytes(sum), echo));
+        }
+        Ok(res)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("mediapipe-rs-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let model = b"not a real model";
+        let inputs: [&[u8]; 3] = [&[1, 2, 3], &[4, 5], &[1, 2, 3]];
+        for key in [ReplayKey::InputHash, ReplayKey::CallOrder] {
+            let dir = temp_dir(&format!("{:?}", key));
+            let expect = run(&SumBackend, model, &inputs).unwrap();
+            assert_eq!(expect[0], (6., vec![1, 2, 3]));
+
+            let recording = RecordingBackend::new(Arc::new(SumBackend), &dir).key(key);
+            assert_eq!(run(&recording, model, &inputs).unwrap(), expect);
+            let model_dir = dir.join(model_dir_name(model));
+            let count = std::fs::read_dir(&model_dir).unwrap().count();
+            match key {
+                ReplayKey::InputHash => assert_eq!(count, 2),
+                ReplayKey::CallOrder => assert_eq!(count, 3),
+            }
+            // unknown model, save raw bytes
+            for entry in std::fs::read_dir(&model_dir).unwrap() {
+                assert!(entry.unwrap().path().join("output_0.bin").exists());
+            }
+
+            let replay = ReplayBackend::new(&dir).key(key);
+            assert_eq!(run(&replay, model, &inputs).unwrap(), expect);
+            // the input bytes size must be consistent with the shape
+            let graph = replay
+                .build_graph(
+                    GraphEncoding::TensorflowLite,
+                    Device::CPU,
+                    model.to_vec().into(),
+                )
+                .unwrap();
+            let mut ctx = graph.init_execution_context().unwrap();
+            assert!(ctx.set_input(0, TensorType::U8, &[1], &[1, 2, 3]).is_err());
+            assert!(ctx.set_input(0, TensorType::F32, &[3], &[1, 2, 3]).is_err());
+            // no recordings for other models and inputs
+            assert!(run(&replay, b"another model", &inputs).is_err());
+            if key == ReplayKey::InputHash {
+                assert!(run(&replay, model, &[&[1, 2]]).is_err());
+            }
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_record_npy() {
+        use crate::model::metadata_writer::test::bare_model_with_types;
+
+        let model = bare_model_with_types(
+            &[(&[3], TensorType::U8)],
+            &[(&[1], TensorType::F32), (&[1, 3], TensorType::U8)],
+        );
+        let dir = temp_dir("npy");
+        let recording = RecordingBackend::new(Arc::new(SumBackend), &dir);
+        let expect = run(&recording, &model, &[&[7, 8, 9]]).unwrap();
+
+        let model_dir = dir.join(model_dir_name(&model));
+        let key_dir = std::fs::read_dir(&model_dir)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let buf = std::fs::read(key_dir.join("output_0.npy")).unwrap();
+        let (descr, shape, data) = read_npy(&buf).unwrap();
+        assert_eq!(
+            (descr, shape, data),
+            ("<f4", vec![1], &24f32.to_le_bytes()[..])
+        );
+        let buf = std::fs::read(key_dir.join("output_1.npy")).unwrap();
+        let (descr, shape, data) = read_npy(&buf).unwrap();
+        assert_eq!((descr, shape, data), ("|u1", vec![1, 3], &[7u8, 8, 9][..]));
+
+        assert_eq!(
+            run(&ReplayBackend::new(&dir), &model, &[&[7, 8, 9]]).unwrap(),
+            expect
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,190 @@
+use super::{ExecutionContext, InferenceBackend, InferenceGraph};
+use crate::{Device, Error, GraphEncoding, SharedSlice, TensorType};
+use tract_onnx::prelude::{
+    DatumType, Framework, InferenceModelExt, IntoTValue, TValue, TVec, Tensor, TypedModel,
+    TypedRunnableModel,
+};
+
+/// Native inference backend using [tract](https://github.com/sonos/tract), a pure-Rust inference engine.
+///
+/// It supports ```ONNX``` and ```TensorFlowLite``` models, and only supports ```CPU``` device.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TractBackend;
+
+impl InferenceBackend for TractBackend {
+    fn build_graph(
+        &self,
+        encoding: GraphEncoding,
+        target: Device,
+        model: SharedSlice<u8>,
+    ) -> Result<Box<dyn InferenceGraph>, Error> {
+        if !matches!(target, Device::CPU) {
+            return Err(Error::ArgumentError(
+                "Tract backend only supports `CPU` device".into(),
+            ));
+        }
+
+        let mut reader = model.as_ref();
+        let model = match encoding {
+            GraphEncoding::Onnx => tract_onnx::onnx()
+                .model_for_read(&mut reader)?
+                .into_optimized()?,
+            GraphEncoding::TensorflowLite => tract_tflite::tflite()
+                .model_for_read(&mut reader)?
+                .into_optimized()?,
+            _ => {
+                return Err(Error::ArgumentError(
+                    "Tract backend only supports `ONNX` and `TensorFlowLite` models".into(),
+                ));
+            }
+        };
+
+        let mut input_types = Vec::with_capacity(model.inputs.len());
+        for i in 0..model.inputs.len() {
+            input_types.push(model.input_fact(i)?.datum_type);
+        }
+        Ok(Box::new(TractGraph {
+            plan: model.into_runnable()?,
+            input_types,
+        }))
+    }
+}
+
+struct TractGraph {
+    plan: TypedRunnableModel<TypedModel>,
+    input_types: Vec<DatumType>,
+}
+
+impl InferenceGraph for TractGraph {
+    #[inline(always)]
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error> {
+        Ok(Box::new(TractExecutionContext {
+            graph: self,
+            inputs: vec![None; self.input_types.len()],
+            outputs: TVec::new(),
+        }))
+    }
+}
+
+struct TractExecutionContext<'graph> {
+    graph: &'graph TractGraph,
+    inputs: Vec<Option<TValue>>,
+    outputs: TVec<TValue>,
+}
+
+impl<'graph> ExecutionContext for TractExecutionContext<'graph> {
+    fn set_input(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: &[u8],
+    ) -> Result<(), Error> {
+        if index >= self.inputs.len() {
+            return Err(Error::ArgumentError(format!(
+                "Input tensor index `{}` out of range, the model has `{}` inputs",
+                index,
+                self.inputs.len()
+            )));
+        }
+
+        let datum_type = match tensor_type {
+            TensorType::F16 => DatumType::F16,
+            TensorType::F32 => DatumType::F32,
+            TensorType::U8 => DatumType::U8,
+            TensorType::I32 => DatumType::I32,
+            #[allow(unreachable_patterns)]
+            _ => {
+                return Err(Error::ArgumentError(format!(
+                    "Unsupported tensor type `{:?}` for tract backend",
+                    tensor_type
+                )));
+            }
+        };
+        let mut tensor = unsafe { Tensor::from_raw_dt(datum_type, dimensions, data)? };
+        let expect_type = self.graph.input_types[index];
+        if expect_type != datum_type {
+            if expect_type.unquantized() == datum_type {
+                // quantized models use the same data, only need to attach the quantization parameters.
+                unsafe { tensor.set_datum_type(expect_type) };
+            } else {
+                tensor = tensor.cast_to_dt(expect_type)?.into_owned();
+            }
+        }
+        self.inputs[index] = Some(tensor.into_tvalue());
+        Ok(())
+    }
+
+    fn compute(&mut self) -> Result<(), Error> {
+        let mut inputs = TVec::with_capacity(self.inputs.len());
+        for (i, input) in self.inputs.iter().enumerate() {
+            match input {
+                Some(t) => inputs.push(t.clone()),
+                None => {
+                    return Err(Error::ArgumentError(format!(
+                        "Input tensor `{}` has not been set",
+                        i
+                    )));
+                }
+            }
+        }
+        self.outputs = self.graph.plan.run(inputs)?;
+        Ok(())
+    }
+
+    fn get_output(&mut self, index: usize, output: &mut [u8]) -> Result<usize, Error> {
+        let tensor = match self.outputs.get(index) {
+            Some(t) => t,
+            None => {
+                return Err(Error::ArgumentError(format!(
+                    "Output tensor index `{}` out of range, the model has `{}` outputs",
+                    index,
+                    self.outputs.len()
+                )));
+            }
+        };
+        let bytes = unsafe { tensor.as_bytes() };
+        if output.len() < bytes.len() {
+            return Err(Error::ArgumentError(format!(
+                "Output buffer size `{}` is too small, expect at least `{}`",
+                output.len(),
+                bytes.len()
+            )));
+        }
+        output[..bytes.len()].copy_from_slice(bytes);
+        Ok(bytes.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tract_onnx_graph() {
+        let model = std::fs::read("assets/testdata/test_add.onnx").unwrap();
+        let graph = TractBackend
+            .build_graph(GraphEncoding::Onnx, Device::CPU, model.into())
+            .unwrap();
+        let mut ctx = graph.init_execution_context().unwrap();
+
+        let mut output = [0u8; 8];
+        assert!(ctx.compute().is_err());
+        assert!(ctx.set_input(1, TensorType::F32, &[2], &[0; 8]).is_err());
+
+        let input = [1.5f32, -2f32];
+        let input_bytes: Vec<u8> = input.iter().flat_map(|f| f.to_ne_bytes()).collect();
+        ctx.set_input(0, TensorType::F32, &[2], &input_bytes)
+            .unwrap();
+        ctx.compute().unwrap();
+        assert!(ctx.get_output(0, &mut output[..4]).is_err());
+        assert!(ctx.get_output(1, &mut output).is_err());
+        assert_eq!(ctx.get_output(0, &mut output).unwrap(), 8);
+        assert_eq!(f32::from_ne_bytes(output[..4].try_into().unwrap()), 3f32);
+        assert_eq!(f32::from_ne_bytes(output[4..].try_into().unwrap()), -4f32);
+
+        assert!(TractBackend
+            .build_graph(GraphEncoding::Onnx, Device::GPU, vec![].into())
+            .is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use super::{ExecutionContext, InferenceBackend, InferenceGraph};
+use crate::{Device, Error, GraphEncoding, SharedSlice, TensorType};
+
+/// Inference backend using [WASI-NN](https://github.com/WebAssembly/wasi-nn), which needs the runtime such as
+/// [WasmEdge](https://github.com/WasmEdge/WasmEdge) with WASI-NN plugins.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WasiNNBackend;
+
+impl InferenceBackend for WasiNNBackend {
+    #[inline(always)]
+    fn build_graph(
+        &self,
+        encoding: GraphEncoding,
+        target: Device,
+        model: SharedSlice<u8>,
+    ) -> Result<Box<dyn InferenceGraph>, Error> {
+        let graph =
+            wasi_nn_safe::GraphBuilder::new(encoding, target).build_from_shared_slices([model])?;
+        Ok(Box::new(WasiNNGraph(graph)))
+    }
+}
+
+struct WasiNNGraph(wasi_nn_safe::Graph);
+
+impl InferenceGraph for WasiNNGraph {
+    #[inline(always)]
+    fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error> {
+        Ok(Box::new(WasiNNExecutionContext(
+            self.0.init_execution_context()?,
+        )))
+    }
+}
+
+struct WasiNNExecutionContext<'graph>(wasi_nn_safe::GraphExecutionContext<'graph>);
+
+impl<'graph> ExecutionContext for WasiNNExecutionContext<'graph> {
+    #[inline(always)]
+    fn set_input(
+        &mut self,
+        index: usize,
+        tensor_type: TensorType,
+        dimensions: &[usize],
+        data: &[u8],
+    ) -> Result<(), Error> {
+        self.0.set_input(index, tensor_type, dimensions, data)?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn compute(&mut self) -> Result<(), Error> {
+        self.0.compute()?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn get_output(&mut self, index: usize, output: &mut [u8]) -> Result<usize, Error> {
+        Ok(self.0.get_output(index, output)?)
+    }
+}
@@ -0,0 +1,337 @@
+//! Print the information which mediapipe-rs parses from a model file (`.tflite`) or a model bundle (`.task`).
+//!
+//! Usage: `mediapipe-rs-inspect [--json] <model_path>`
+
+use mediapipe_rs::model::{ModelInfo, ProcessUnit};
+use mediapipe_rs::postprocess::QuantizationParameters;
+use serde_json::{json, Map, Value};
+use std::fmt::{Display, Formatter, Write};
+
+const USAGE: &'static str = "Usage: mediapipe-rs-inspect [--json] <model_path>";
+
+// labels more than this number will be folded in text mode.
+const TEXT_MODE_MAX_ARRAY_LEN: usize = 20;
+
+/// write the value as human-readable text
+fn write_text(f: &mut Formatter<'_>, value: &Value, indent: usize) -> std::fmt::Result {
+    match value {
+        Value::Array(arr) => {
+            if arr.iter().all(|v| !v.is_array() && !v.is_object()) {
+                f.write_char('[')?;
+                for (i, v) in arr.iter().take(TEXT_MODE_MAX_ARRAY_LEN).enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", v)?;
+                }
+                if arr.len() > TEXT_MODE_MAX_ARRAY_LEN {
+                    write!(f, ", ... ({} total)", arr.len())?;
+                }
+                return writeln!(f, "]");
+            }
+            f.write_char('\n')?;
+            for (i, v) in arr.iter().enumerate() {
+                write!(f, "{:width$}#{}:", "", i, width = indent)?;
+                write_text(f, v, indent + 2)?;
+            }
+            Ok(())
+        }
+        Value::Object(obj) => {
+            f.write_char('\n')?;
+            for (k, v) in obj.iter() {
+                if v.is_null() {
+                    continue;
+                }
+                write!(f, "{:width$}{}:", "", k, width = indent)?;
+                write_text(f, v, indent + 2)?;
+            }
+            Ok(())
+        }
+        Value::String(s) => writeln!(f, " {}", s),
+        _ => writeln!(f, " {}", value),
+    }
+}
+
+struct TextMode<'a>(&'a Value);
+
+impl Display for TextMode<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Value::Object(obj) = self.0 {
+            for (k, v) in obj.iter() {
+                write!(f, "{}:", k)?;
+                write_text(f, v, 2)?;
+            }
+            Ok(())
+        } else {
+            write_text(f, self.0, 0)
+        }
+    }
+}
+
+fn quantization_json(q: Option<QuantizationParameters>) -> Value {
+    match q {
+        Some(q) => json!({ "scale": q.scale, "zero_point": q.zero_point }),
+        None => Value::Null,
+    }
+}
+
+fn process_units_json(units: &[ProcessUnit]) -> Value {
+    if units.is_empty() {
+        return Value::Null;
+    }
+    units
+        .iter()
+        .map(|u| match u {
+            ProcessUnit::Normalization { mean, std } => {
+                json!({ "kind": "normalization", "mean": mean, "std": std })
+            }
+            ProcessUnit::ScoreCalibration {
+                score_transformation,
+                default_score,
+            } => json!({
+                "kind": "score_calibration",
+                "score_transformation": score_transformation,
+                "default_score": default_score,
+            }),
+            ProcessUnit::ScoreThresholding {
+                global_score_threshold,
+            } => json!({
+                "kind": "score_thresholding",
+                "global_score_threshold": global_score_threshold,
+            }),
+            ProcessUnit::BertTokenizer => json!({ "kind": "bert_tokenizer" }),
+            ProcessUnit::SentencePieceTokenizer => json!({ "kind": "sentence_piece_tokenizer" }),
+            ProcessUnit::RegexTokenizer {
+                delim_regex_pattern,
+            } => json!({
+                "kind": "regex_tokenizer",
+                "delim_regex_pattern": delim_regex_pattern,
+            }),
+        })
+        .collect()
+}
+
+#[allow(unused_variables)]
+fn to_tensor_info_json(model_info: &ModelInfo, index: usize) -> Value {
+    let info = match model_info.to_tensor_info(index) {
+        Some(i) => i,
+        None => return Value::Null,
+    };
+
+    #[cfg(feature = "vision")]
+    if let Ok(img) = info.try_to_image() {
+        return json!({
+            "kind": "image",
+            "data_layout": format!("{:?}", img.image_data_layout),
+            "color_space": format!("{:?}", img.color_space),
+            "tensor_type": format!("{:?}", img.tensor_type),
+            "tensor_shape": {
+                "batch": img.tensor_shape.batch,
+                "width": img.tensor_shape.width,
+                "height": img.tensor_shape.height,
+                "channels": img.tensor_shape.channels,
+            },
+            "normalization_mean": img.normalization_options.0,
+            "normalization_std": img.normalization_options.1,
+            "stats_min": img.stats_min,
+            "stats_max": img.stats_max,
+        });
+    }
+
+    #[cfg(feature = "audio")]
+    if let Ok(audio) = info.try_to_audio() {
+        return json!({
+            "kind": "audio",
+            "num_channels": audio.num_channels,
+            "num_samples": audio.num_samples,
+            "sample_rate": audio.sample_rate,
+            "num_overlapping_samples": audio.num_overlapping_samples,
+            "tensor_type": format!("{:?}", audio.tensor_type),
+        });
+    }
+
+    #[cfg(feature = "text")]
+    if let Ok(text) = info.try_to_text() {
+        use mediapipe_rs::preprocess::text::TextToTensorInfo;
+        return match text {
+            TextToTensorInfo::BertModel {
+                token_index_map,
+                max_seq_len,
+                classifier_token_id,
+                separator_token_id,
+            } => json!({
+                "kind": "bert_tokenizer",
+                "max_seq_len": max_seq_len,
+                "vocab_size": token_index_map.len(),
+                "classifier_token_id": classifier_token_id,
+                "separator_token_id": separator_token_id,
+            }),
+            TextToTensorInfo::RegexModel {
+                delim_regex,
+                token_index_map,
+                max_seq_len,
+                unknown_id,
+                pad_id,
+            } => json!({
+                "kind": "regex_tokenizer",
+                "delim_regex_pattern": delim_regex.as_str(),
+                "max_seq_len": max_seq_len,
+                "vocab_size": token_index_map.len(),
+                "unknown_id": unknown_id,
+                "pad_id": pad_id,
+            }),
+            TextToTensorInfo::StringModel => json!({ "kind": "string" }),
+            TextToTensorInfo::UseModel => json!({ "kind": "universal_sentence_encoder" }),
+        };
+    }
+
+    Value::Null
+}
+
+fn input_json(model_info: &ModelInfo, index: usize) -> Value {
+    json!({
+        "index": index,
+        "type": model_info.input_tensor_type(index).map(|t| format!("{:?}", t)),
+        "shape": model_info.input_tensor_shape(index),
+        "quantization": quantization_json(model_info.input_tensor_quantization_parameters(index)),
+        "process_units": process_units_json(model_info.input_tensor_process_units(index)),
+        "preprocess": to_tensor_info_json(model_info, index),
+    })
+}
+
+fn output_json(model_info: &ModelInfo, index: usize) -> Value {
+    let locales = model_info.output_tensor_label_locales(index);
+    let display_names = if locales.is_empty() {
+        Value::Null
+    } else {
+        locales
+            .into_iter()
+            .map(|locale| {
+                json!({
+                    "locale": locale,
+                    "names": model_info.output_tensor_display_names(index, locale),
+                })
+            })
+            .collect()
+    };
+
+    #[cfg(feature = "vision")]
+    let bounding_box_properties = json!(model_info.output_tensor_bounding_box_properties(index));
+    #[cfg(not(feature = "vision"))]
+    let bounding_box_properties = Value::Null;
+
+    json!({
+        "index": index,
+        "name": model_info.output_tensor_name(index),
+        "type": model_info.output_tensor_type(index).map(|t| format!("{:?}", t)),
+        "shape": model_info.output_tensor_shape(index),
+        "quantization": quantization_json(model_info.output_tensor_quantization_parameters(index)),
+        "process_units": process_units_json(model_info.output_tensor_process_units(index)),
+        "labels": model_info.output_tensor_labels(index),
+        "display_names": display_names,
+        "bounding_box_properties": bounding_box_properties,
+    })
+}
+
+fn model_json(name: &str, model_info: &ModelInfo) -> Value {
+    let inputs: Vec<Value> = (0..model_info.input_tensor_count())
+        .map(|i| input_json(model_info, i))
+        .collect();
+    let outputs: Vec<Value> = (0..model_info.output_tensor_count())
+        .map(|i| output_json(model_info, i))
+        .collect();
+    let associated_files: Vec<Value> = model_info
+        .associated_files()
+        .into_iter()
+        .map(|f| match model_info.associated_file(f) {
+            Ok(c) => json!({ "name": f, "size": c.map(|c| c.len()) }),
+            Err(e) => json!({ "name": f, "error": e.to_string() }),
+        })
+        .collect();
+
+    json!({
+        "name": name,
+        "size": model_info.model_asset_buffer().len(),
+        "inputs": inputs,
+        "outputs": outputs,
+        "input_process_units": process_units_json(model_info.subgraph_input_process_units()),
+        "output_process_units": process_units_json(model_info.subgraph_output_process_units()),
+        "output_activation": format!("{:?}", model_info.output_activation()),
+        "associated_files": associated_files,
+    })
+}
+
+/// Inspect the model or model bundle, push the models information to `models`
+fn inspect(
+    name: String,
+    buf: Vec<u8>,
+    models: &mut Vec<Value>,
+    bundle_files: &mut Vec<Value>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let model_info = ModelInfo::from_buffer(buf)?;
+    if !model_info.is_model_asset_bundle() {
+        models.push(model_json(name.as_str(), &model_info));
+        return Ok(());
+    }
+
+    for file_name in model_info.bundle_file_names() {
+        let content = model_info.bundle_file(file_name)?.unwrap();
+        let full_name = format!("{}/{}", name, file_name);
+        bundle_files.push(json!({ "name": full_name, "size": content.len() }));
+        if !file_name.ends_with(".tflite") && !file_name.ends_with(".task") {
+            continue;
+        }
+        if let Err(e) = inspect(full_name.clone(), content.to_vec(), models, bundle_files) {
+            models.push(json!({ "name": full_name, "error": e.to_string() }));
+        }
+    }
+    Ok(())
+}
+
+fn parse_args() -> Result<(bool, String), Box<dyn std::error::Error>> {
+    let mut json = false;
+    let mut path = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            _ => {
+                if path.is_some() {
+                    return Err(USAGE.into());
+                }
+                path = Some(arg);
+            }
+        }
+    }
+    match path {
+        Some(p) => Ok((json, p)),
+        None => Err(USAGE.into()),
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (json, path) = parse_args()?;
+    let buf = std::fs::read(&path)?;
+
+    let mut models = Vec::new();
+    let mut bundle_files = Vec::new();
+    inspect(path.clone(), buf, &mut models, &mut bundle_files)?;
+
+    let mut result = Map::new();
+    result.insert("path".into(), path.into());
+    if !bundle_files.is_empty() {
+        result.insert("bundle_files".into(), bundle_files.into());
+    }
+    result.insert("models".into(), models.into());
+    let result = Value::Object(result);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        print!("{}", TextMode(&result));
+    }
+    Ok(())
+}
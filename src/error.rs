@@ -24,10 +24,22 @@ pub enum Error {
     #[error("FlatBuffer Error: {0}")]
     FlatBufferError(#[from] flatbuffers::InvalidFlatbuffer),
 
+    #[cfg(feature = "vision")]
+    #[error("Image Error: {0}")]
+    ImageError(#[from] image::ImageError),
+
     #[cfg(feature = "ffmpeg")]
     #[error("FFMpeg Error: {0}")]
     FFMpegError(#[from] ffmpeg_next::Error),
 
+    #[cfg(feature = "tract")]
+    #[error("Tract Error: {0}")]
+    TractError(#[from] tract_onnx::prelude::TractError),
+
+    #[cfg(feature = "eval")]
+    #[error("Json Error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
     #[cfg(feature = "audio")]
     #[error("Symphonia Error: {0}")]
     SymphoniaError(#[from] symphonia_core::errors::Error),
@@ -59,6 +59,7 @@
 //! * audio:
 //!   * audio classification: [`AudioClassifierBuilder`] -> [`AudioClassifier`] -> [`AudioClassifierSession`]
 //! * text:
+//!   * language detection: [`LanguageDetectorBuilder`] -> [`LanguageDetector`] -> [`LanguageDetectorSession`]
 //!   * text classification: [`TextClassifierBuilder`] -> [`TextClassifier`] -> [`TextClassifierSession`]
 //!
 //!
@@ -299,9 +300,12 @@
 #[cfg(not(any(feature = "vision", feature = "audio", feature = "text")))]
 compile_error!("Must select at least one task type: `vision`, `audio`, `text`");
 
+/// MediaPipe-rs inference backend api, which abstracts the inference engine used by tasks.
+pub mod backend;
 mod error;
+/// MediaPipe-rs model api, which can be used to inspect the model information before building a task.
 #[macro_use]
-mod model;
+pub mod model;
 
 /// MediaPipe-rs postprocess api, which define the tasks results and implement tensors results to task results.
 /// The module also has utils to make use of results, such as drawing utils.
@@ -311,11 +315,11 @@ pub mod preprocess;
 /// MediaPipe-rs tasks api, contain audio, vision and text tasks.
 pub mod tasks;
 
+use backend::{Graph, GraphExecutionContext};
 pub use error::Error;
 pub use wasi_nn_safe::GraphExecutionTarget as Device;
-use wasi_nn_safe::{
-    Graph, GraphBuilder, GraphEncoding, GraphExecutionContext, SharedSlice, TensorType,
-};
+pub use wasi_nn_safe::TensorType;
+use wasi_nn_safe::{GraphEncoding, SharedSlice};
 
 #[cfg(doc)]
 use tasks::{audio::*, text::*, vision::*};
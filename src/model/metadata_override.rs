@@ -0,0 +1,394 @@
+use super::*;
+#[cfg(feature = "vision")]
+use crate::preprocess::vision::ImageLikeTensorShape;
+use std::path::PathBuf;
+
+/// Labels supplied by users.
+#[derive(Debug, Clone)]
+pub(crate) enum LabelsOverride {
+    Labels(Vec<String>),
+    File(PathBuf),
+}
+
+/// Model metadata supplied by users, which is used when the model has no metadata or has wrong metadata.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ModelMetadataOverride {
+    /// (mean, std), len can be 1 or the number of channels.
+    #[cfg(feature = "vision")]
+    pub image_normalization: Option<(Vec<f32>, Vec<f32>)>,
+
+    #[cfg(feature = "vision")]
+    pub image_layout: Option<ImageDataLayout>,
+
+    /// (sample rate, number of channels)
+    #[cfg(feature = "audio")]
+    pub audio_properties: Option<(usize, usize)>,
+
+    pub labels: Option<LabelsOverride>,
+}
+
+impl ModelMetadataOverride {
+    #[inline(always)]
+    fn has_input_override(&self) -> bool {
+        #[cfg(feature = "vision")]
+        if self.image_normalization.is_some() || self.image_layout.is_some() {
+            return true;
+        }
+        #[cfg(feature = "audio")]
+        if self.audio_properties.is_some() {
+            return true;
+        }
+        false
+    }
+
+    /// The input override without the labels, for the subtasks whose outputs are not the task outputs.
+    #[cfg(feature = "vision")]
+    pub(crate) fn input_override(&self) -> Self {
+        Self {
+            labels: None,
+            ..self.clone()
+        }
+    }
+
+    /// Wrap the model resource with the metadata, return the origin model resource if nothing to override.
+    pub(crate) fn apply<'buf>(
+        &self,
+        model_resource: Box<dyn ModelResourceTrait + 'buf>,
+    ) -> Result<Box<dyn ModelResourceTrait + 'buf>, Error> {
+        if !self.has_input_override() && self.labels.is_none() {
+            return Ok(model_resource);
+        }
+
+        let labels = match self.labels {
+            Some(LabelsOverride::Labels(ref l)) => Some(l.join("\n").into_bytes()),
+            Some(LabelsOverride::File(ref p)) => Some(std::fs::read(p)?),
+            None => None,
+        };
+        #[cfg(any(feature = "vision", feature = "audio"))]
+        let to_tensor_info = if self.has_input_override() {
+            Some(self.to_tensor_info(model_resource.as_ref())?)
+        } else {
+            None
+        };
+        #[cfg(not(any(feature = "vision", feature = "audio")))]
+        let to_tensor_info = None;
+        Ok(Box::new(MetadataOverrideModelResource {
+            model_resource,
+            to_tensor_info,
+            labels,
+        }))
+    }
+
+    #[cfg(any(feature = "vision", feature = "audio"))]
+    fn to_tensor_info(
+        &self,
+        model_resource: &dyn ModelResourceTrait,
+    ) -> Result<ToTensorInfo<'static>, Error> {
+        let shape = model_resource_check_and_get_impl!(model_resource, input_tensor_shape, 0);
+        let tensor_type = model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
+
+        #[cfg(feature = "audio")]
+        if let Some((sample_rate, num_channels)) = self.audio_properties {
+            #[cfg(feature = "vision")]
+            if self.image_normalization.is_some() || self.image_layout.is_some() {
+                return Err(Error::ArgumentError(
+                    "Cannot use both image options and audio options".into(),
+                ));
+            }
+            if num_channels == 0 {
+                return Err(Error::ArgumentError(
+                    "Audio num channel cannot be zero".into(),
+                ));
+            }
+            let input_buffer_size = shape.iter().product::<usize>();
+            if input_buffer_size % num_channels != 0 {
+                return Err(Error::ArgumentError(format!(
+                    "Input tensor size `{}` should be a multiplier of the number of channels `{}`",
+                    input_buffer_size, num_channels
+                )));
+            }
+            return Ok(ToTensorInfo::new_audio(AudioToTensorInfo {
+                num_channels,
+                num_samples: shape.last().cloned().unwrap_or(1) / num_channels,
+                sample_rate,
+                num_overlapping_samples: 0,
+                tensor_type,
+            }));
+        }
+
+        #[cfg(feature = "vision")]
+        return Ok(ToTensorInfo::new_image(self.image_to_tensor_info(
+            model_resource,
+            shape,
+            tensor_type,
+        )?));
+        #[cfg(not(feature = "vision"))]
+        Ok(ToTensorInfo::new_none())
+    }
+
+    #[cfg(feature = "vision")]
+    fn image_to_tensor_info(
+        &self,
+        model_resource: &dyn ModelResourceTrait,
+        shape: &[usize],
+        tensor_type: TensorType,
+    ) -> Result<ImageToTensorInfo, Error> {
+        let current = model_resource
+            .to_tensor_info(0)
+            .and_then(|t| t.try_to_image().ok());
+        let image_data_layout = self
+            .image_layout
+            .or(current.map(|c| c.image_data_layout))
+            .or(ImageDataLayout::detect(shape))
+            .unwrap_or(ImageDataLayout::NHWC);
+        let tensor_shape = ImageLikeTensorShape::parse(image_data_layout, shape)?;
+        let normalization_options = match (&self.image_normalization, current) {
+            (Some(n), _) => n.clone(),
+            (None, Some(c)) => c.normalization_options.clone(),
+            // float inputs are in range [0, 1] by default
+            (None, None) if tensor_type == TensorType::F32 => (vec![0.], vec![255.]),
+            (None, None) => (vec![], vec![]),
+        };
+        if tensor_type == TensorType::F32 {
+            for v in [&normalization_options.0, &normalization_options.1] {
+                if v.len() != 1 && v.len() != tensor_shape.channels {
+                    return Err(Error::ArgumentError(format!(
+                        "Image normalization options length must be `1` or `{}`, but got `{}`",
+                        tensor_shape.channels,
+                        v.len()
+                    )));
+                }
+            }
+        }
+        Ok(ImageToTensorInfo {
+            image_data_layout,
+            color_space: current
+                .map(|c| c.color_space)
+                .unwrap_or(ImageColorSpaceType::RGB),
+            tensor_type,
+            tensor_shape,
+            stats_min: current.map(|c| c.stats_min.clone()).unwrap_or_default(),
+            stats_max: current.map(|c| c.stats_max.clone()).unwrap_or_default(),
+            normalization_options,
+        })
+    }
+}
+
+/// The model resource with input information and labels supplied by users.
+struct MetadataOverrideModelResource<'buf> {
+    model_resource: Box<dyn ModelResourceTrait + 'buf>,
+    // input 0
+    to_tensor_info: Option<ToTensorInfo<'static>>,
+    labels: Option<Vec<u8>>,
+}
+
+impl<'buf> ModelResourceTrait for MetadataOverrideModelResource<'buf> {
+    fn model_backend(&self) -> GraphEncoding {
+        self.model_resource.model_backend()
+    }
+
+    fn input_tensor_count(&self) -> usize {
+        self.model_resource.input_tensor_count()
+    }
+
+    fn output_tensor_count(&self) -> usize {
+        self.model_resource.output_tensor_count()
+    }
+
+    fn input_tensor_type(&self, index: usize) -> Option<TensorType> {
+        self.model_resource.input_tensor_type(index)
+    }
+
+    fn output_tensor_type(&self, index: usize) -> Option<TensorType> {
+        self.model_resource.output_tensor_type(index)
+    }
+
+    fn input_tensor_shape(&self, index: usize) -> Option<&[usize]> {
+        self.model_resource.input_tensor_shape(index)
+    }
+
+    fn output_tensor_shape(&self, index: usize) -> Option<&[usize]> {
+        self.model_resource.output_tensor_shape(index)
+    }
+
+    fn output_tensor_name_to_index(&self, name: &'static str) -> Option<usize> {
+        self.model_resource.output_tensor_name_to_index(name)
+    }
+
+    fn output_tensor_name(&self, index: usize) -> Option<&str> {
+        self.model_resource.output_tensor_name(index)
+    }
+
+    fn input_tensor_quantization_parameters(&self, index: usize) -> Option<QuantizationParameters> {
+        self.model_resource
+            .input_tensor_quantization_parameters(index)
+    }
+
+    fn output_tensor_quantization_parameters(
+        &self,
+        index: usize,
+    ) -> Option<QuantizationParameters> {
+        self.model_resource
+            .output_tensor_quantization_parameters(index)
+    }
+
+    fn output_tensor_labels_locale(
+        &self,
+        index: usize,
+        locale: &str,
+    ) -> Result<(&[u8], Option<&[u8]>), Error> {
+        // the labels supplied by users are used for any outputs which need labels
+        match self.labels {
+            Some(ref l) => Ok((l.as_slice(), None)),
+            None => self
+                .model_resource
+                .output_tensor_labels_locale(index, locale),
+        }
+    }
+
+    fn output_tensor_labels_locales(&self, index: usize) -> Vec<&str> {
+        match self.labels {
+            Some(_) => Vec::new(),
+            None => self.model_resource.output_tensor_labels_locales(index),
+        }
+    }
+
+    #[cfg(feature = "vision")]
+    fn output_bounding_box_properties(&self, index: usize, slice: &mut [usize]) -> bool {
+        self.model_resource
+            .output_bounding_box_properties(index, slice)
+    }
+
+    #[cfg(feature = "vision")]
+    fn ssd_detection_metadata(&self) -> Option<&SsdDetectionMetadata> {
+        self.model_resource.ssd_detection_metadata()
+    }
+
+    fn to_tensor_info(&self, input_index: usize) -> Option<&ToTensorInfo> {
+        match self.to_tensor_info {
+            Some(ref t) if input_index == 0 => Some(t),
+            _ => self.model_resource.to_tensor_info(input_index),
+        }
+    }
+
+    fn output_activation(&self) -> Activation {
+        self.model_resource.output_activation()
+    }
+
+    fn associated_files(&self) -> Option<&ZipFiles> {
+        self.model_resource.associated_files()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_metadata_override() {
+        let buf = metadata_writer::test::bare_model_with_types(
+            &[(&[1, 2, 2, 3], TensorType::F32)],
+            &[(&[1, 3], TensorType::F32)],
+        );
+        let model = parse_model(&buf).unwrap();
+        assert!(model.output_tensor_labels_locale(0, "en").is_err());
+
+        let model = ModelMetadataOverride {
+            labels: Some(LabelsOverride::Labels(vec![
+                "a".into(),
+                "b".into(),
+                "c".into(),
+            ])),
+            ..Default::default()
+        }
+        .apply(model)
+        .unwrap();
+        assert_eq!(
+            model.output_tensor_labels_locale(0, "en").unwrap(),
+            ("a\nb\nc".as_bytes(), None)
+        );
+        assert_eq!(model.output_tensor_shape(0), Some([1, 3].as_slice()));
+
+        #[cfg(feature = "vision")]
+        {
+            assert!(model
+                .to_tensor_info(0)
+                .and_then(|t| t.try_to_image().ok())
+                .is_none());
+            let model = ModelMetadataOverride {
+                image_normalization: Some((vec![127.5], vec![127.5])),
+                ..Default::default()
+            }
+            .apply(model)
+            .unwrap();
+            let info = model.to_tensor_info(0).unwrap().try_to_image().unwrap();
+            assert_eq!(info.image_data_layout, ImageDataLayout::NHWC);
+            assert_eq!((info.width(), info.height()), (2, 2));
+            assert_eq!(info.normalization_options, (vec![127.5], vec![127.5]));
+            // labels of the inner model resource are kept
+            assert_eq!(
+                model.output_tensor_labels_locale(0, "en").unwrap().0,
+                "a\nb\nc".as_bytes()
+            );
+
+            // override the layout only, keep the normalization options
+            let model = ModelMetadataOverride {
+                image_layout: Some(ImageDataLayout::NCHW),
+                ..Default::default()
+            }
+            .apply(model)
+            .unwrap();
+            let info = model.to_tensor_info(0).unwrap().try_to_image().unwrap();
+            assert_eq!(info.image_data_layout, ImageDataLayout::NCHW);
+            assert_eq!(info.tensor_shape.channels, 2);
+            assert!(ModelMetadataOverride {
+                image_normalization: Some((vec![1., 2.], vec![1.])),
+                ..Default::default()
+            }
+            .apply(parse_model(&buf).unwrap())
+            .is_err());
+        }
+
+        #[cfg(feature = "audio")]
+        {
+            let buf = metadata_writer::test::bare_model_with_types(
+                &[(&[1, 15600], TensorType::F32)],
+                &[(&[1, 521], TensorType::F32)],
+            );
+            let model = ModelMetadataOverride {
+                audio_properties: Some((16000, 1)),
+                ..Default::default()
+            }
+            .apply(parse_model(&buf).unwrap())
+            .unwrap();
+            let info = model.to_tensor_info(0).unwrap().try_to_audio().unwrap();
+            assert_eq!(info.num_samples, 15600);
+            assert_eq!(info.sample_rate, 16000);
+            assert!(ModelMetadataOverride {
+                audio_properties: Some((16000, 0)),
+                ..Default::default()
+            }
+            .apply(parse_model(&buf).unwrap())
+            .is_err());
+        }
+
+        assert!(ModelMetadataOverride {
+            labels: Some(LabelsOverride::File("/not/exist/labels.txt".into())),
+            ..Default::default()
+        }
+        .apply(parse_model(&buf).unwrap())
+        .is_err());
+        // the input override drops the labels
+        #[cfg(feature = "vision")]
+        {
+            let model = ModelMetadataOverride {
+                labels: Some(LabelsOverride::File("/not/exist/labels.txt".into())),
+                ..Default::default()
+            }
+            .input_override()
+            .apply(parse_model(&buf).unwrap())
+            .unwrap();
+            assert!(model.output_tensor_labels_locale(0, "en").is_err());
+        }
+    }
+}
@@ -3,6 +3,8 @@
 use std::collections::HashMap;
 
 pub(crate) use memory_text_file::MemoryTextFile;
+pub(crate) use metadata_override::{LabelsOverride, ModelMetadataOverride};
+pub(crate) use model_info::MetadataProcessUnits;
 pub(crate) use zip::ZipFiles;
 
 use crate::postprocess::{Activation, QuantizationParameters};
@@ -17,7 +19,7 @@ use crate::{Error, GraphEncoding, TensorType};
 
 /// Abstraction for model resources.
 /// Users can use this trait to get information for models, such as data layout, model backend, etc.
-/// Now it supports ```TensorFlowLite``` backend.
+/// Now it supports ```TensorFlowLite``` and ```Onnx``` backends.
 pub(crate) trait ModelResourceTrait {
     fn model_backend(&self) -> GraphEncoding;
 
@@ -35,6 +37,10 @@ pub(crate) trait ModelResourceTrait {
 
     fn output_tensor_name_to_index(&self, name: &'static str) -> Option<usize>;
 
+    fn output_tensor_name(&self, index: usize) -> Option<&str>;
+
+    fn input_tensor_quantization_parameters(&self, index: usize) -> Option<QuantizationParameters>;
+
     fn output_tensor_quantization_parameters(&self, index: usize)
         -> Option<QuantizationParameters>;
 
@@ -44,12 +50,28 @@ pub(crate) trait ModelResourceTrait {
         locale: &str,
     ) -> Result<(&[u8], Option<&[u8]>), Error>;
 
+    fn output_tensor_labels_locales(&self, index: usize) -> Vec<&str>;
+
     #[cfg(feature = "vision")]
     fn output_bounding_box_properties(&self, index: usize, slice: &mut [usize]) -> bool;
 
+    #[cfg(feature = "vision")]
+    fn ssd_detection_metadata(&self) -> Option<&SsdDetectionMetadata>;
+
     fn to_tensor_info(&self, input_index: usize) -> Option<&ToTensorInfo>;
 
     fn output_activation(&self) -> Activation;
+
+    fn associated_files(&self) -> Option<&ZipFiles>;
+}
+
+/// Anchors and decoding options of SSD detection models, such as MediaPipe `DETECTOR_METADATA`.
+#[cfg(feature = "vision")]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SsdDetectionMetadata {
+    /// Fixed anchors, empty if the model does not have them.
+    pub anchors: Vec<crate::postprocess::Anchor>,
+    pub decoding_options: Option<crate::postprocess::SsdDecodingOptions>,
 }
 
 #[inline]
@@ -67,6 +89,10 @@ pub(crate) fn parse_model<'buf>(
             let tf_model_resource = tflite::TfLiteModelResource::new(buf)?;
             Ok(Box::new(tf_model_resource))
         }
+        _ if onnx::OnnxModelResource::is_onnx(buf) => {
+            let onnx_model_resource = onnx::OnnxModelResource::new(buf)?;
+            Ok(Box::new(onnx_model_resource))
+        }
         _ => Err(Error::ModelParseError(format!(
             "Cannot parse this head magic `{:?}`",
             &buf[..8]
@@ -131,14 +157,19 @@ macro_rules! search_file_in_zip {
     ( $zip_files:expr, $buf:expr, $candidate_list:expr, $task_name:expr ) => {{
         let mut search_result = None;
         for name in $candidate_list {
+            // stored files share the buffer, deflated files must be copied.
             if let Some(r) = $zip_files.get_file_offset(*name) {
-                search_result = Some(r);
+                let len = r.end - r.start;
+                search_result = Some($buf.subslice(r.start, len).unwrap());
+                break;
+            }
+            if let Some(c) = $zip_files.get_file(*name)? {
+                search_result = Some(crate::SharedSlice::from(c.to_vec()));
                 break;
             }
         }
-        if let Some(r) = search_result {
-            let len = r.end - r.start;
-            $buf.subslice(r.start, len).unwrap()
+        if let Some(s) = search_result {
+            s
         } else {
             return Err(crate::Error::ModelInconsistentError(format!(
                 "Cannot find model asset file for `{}` task, candidate list is `{:?}`",
@@ -164,5 +195,14 @@ macro_rules! check_tensor_type {
 }
 
 mod memory_text_file;
+mod metadata_override;
+mod model_info;
+mod onnx;
 mod tflite;
 mod zip;
+
+pub use model_info::{ModelInfo, ProcessUnit};
+/// Write metadata properties into ONNX models.
+pub use onnx::OnnxMetadataWriter;
+/// Write metadata and associated files into TFLite models.
+pub use tflite::metadata_writer;
@@ -0,0 +1,477 @@
+use super::*;
+use crate::model::MemoryTextFile;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Process unit in the model metadata, such as normalization, score calibration and tokenizers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessUnit {
+    Normalization {
+        mean: Vec<f32>,
+        std: Vec<f32>,
+    },
+    ScoreCalibration {
+        score_transformation: String,
+        default_score: f32,
+    },
+    ScoreThresholding {
+        global_score_threshold: f32,
+    },
+    BertTokenizer,
+    SentencePieceTokenizer,
+    RegexTokenizer {
+        delim_regex_pattern: Option<String>,
+    },
+}
+
+/// Process units of tensors and subgraph in the model metadata.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MetadataProcessUnits {
+    pub inputs: Vec<Vec<ProcessUnit>>,
+    pub outputs: Vec<Vec<ProcessUnit>>,
+    pub subgraph_inputs: Vec<ProcessUnit>,
+    pub subgraph_outputs: Vec<ProcessUnit>,
+}
+
+/// Information of a model, such as input/output tensor shapes, tensor types, quantization parameters, labels,
+/// pre-processing information and associated files.
+///
+/// It can be used to check a model before building a task.
+/// For the model asset bundles (such as `.task` files), the tensor information is empty,
+/// and the models in the bundle can be inspected by [`ModelInfo::bundle_model_infos`].
+///
+/// ```rust
+/// use mediapipe_rs::model::ModelInfo;
+///
+/// let model_info = ModelInfo::from_path(model_path)?;
+/// for i in 0..model_info.input_tensor_count() {
+///     println!("{:?}", model_info.input_tensor_shape(i));
+/// }
+/// println!("{}", model_info);
+/// ```
+pub struct ModelInfo {
+    // model resource and bundle files reference the buffer, so they must be dropped before the buffer.
+    model_resource: Option<Box<dyn ModelResourceTrait + 'static>>,
+    bundle_files: Option<ZipFiles<'static>>,
+    process_units: MetadataProcessUnits,
+    buf: Vec<u8>,
+}
+
+impl ModelInfo {
+    // local file header signature of zip files
+    const ZIP_MAGIC: &'static [u8] = &[0x50, 0x4b, 0x03, 0x04];
+
+    /// Parse the model information from model asset buffer, which can be a model or a model asset bundle.
+    pub fn from_buffer(buf: Vec<u8>) -> Result<Self, Error> {
+        // change the lifetime to 'static, because the buf will move to self and will not be released.
+        if buf.starts_with(Self::ZIP_MAGIC) {
+            let bundle_files = ZipFiles::new(buf.as_slice())?;
+            let bundle_files =
+                unsafe { std::mem::transmute::<ZipFiles<'_>, ZipFiles<'static>>(bundle_files) };
+            return Ok(Self {
+                model_resource: None,
+                bundle_files: Some(bundle_files),
+                process_units: Default::default(),
+                buf,
+            });
+        }
+
+        let model_resource_ref = parse_model(buf.as_slice())?;
+        let process_units = if buf.get(4..8) == Some(tflite::TfLiteModelResource::HEAD_MAGIC) {
+            tflite::TfLiteModelResource::parse_process_units(buf.as_slice())?
+        } else {
+            Default::default()
+        };
+        let model_resource = unsafe {
+            std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
+        };
+        Ok(Self {
+            model_resource: Some(model_resource),
+            bundle_files: None,
+            process_units,
+            buf,
+        })
+    }
+
+    /// Parse the model information from model asset path.
+    #[inline(always)]
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::from_buffer(std::fs::read(path)?)
+    }
+
+    /// Return `true` if the model asset is a model asset bundle, such as the `.task` files.
+    #[inline(always)]
+    pub fn is_model_asset_bundle(&self) -> bool {
+        self.bundle_files.is_some()
+    }
+
+    /// Get the names of files in the model asset bundle (sorted by name).
+    /// Return an empty vector if the model asset is not a bundle.
+    pub fn bundle_file_names(&self) -> Vec<&str> {
+        let mut names = match self.bundle_files {
+            Some(ref z) => z.file_names().collect(),
+            None => Vec::new(),
+        };
+        names.sort();
+        names
+    }
+
+    /// Get the content of a file in the model asset bundle.
+    /// Return an error if the file is deflated and cannot be inflated.
+    #[inline(always)]
+    pub fn bundle_file(&self, name: &str) -> Result<Option<&[u8]>, Error> {
+        match self.bundle_files {
+            Some(ref z) => z.get_file(name),
+            None => Ok(None),
+        }
+    }
+
+    /// Parse the model information of a file in the model asset bundle.
+    pub fn bundle_model_info(&self, name: &str) -> Result<ModelInfo, Error> {
+        match self.bundle_file(name)? {
+            Some(buf) => Self::from_buffer(buf.to_vec()),
+            None => Err(Error::ArgumentError(format!(
+                "Cannot find file `{}` in the model asset bundle",
+                name
+            ))),
+        }
+    }
+
+    /// Parse the model information of all models (`.tflite` and nested `.task` files) in the model asset bundle.
+    pub fn bundle_model_infos(&self) -> Result<Vec<(&str, ModelInfo)>, Error> {
+        let mut res = Vec::new();
+        for name in self.bundle_file_names() {
+            if name.ends_with(".tflite") || name.ends_with(".task") {
+                res.push((name, self.bundle_model_info(name)?));
+            }
+        }
+        Ok(res)
+    }
+
+    /// Get the metadata process units of the input tensor.
+    #[inline(always)]
+    pub fn input_tensor_process_units(&self, index: usize) -> &[ProcessUnit] {
+        self.process_units
+            .inputs
+            .get(index)
+            .map_or(&[], |v| v.as_slice())
+    }
+
+    /// Get the metadata process units of the output tensor.
+    #[inline(always)]
+    pub fn output_tensor_process_units(&self, index: usize) -> &[ProcessUnit] {
+        self.process_units
+            .outputs
+            .get(index)
+            .map_or(&[], |v| v.as_slice())
+    }
+
+    /// Get the metadata process units which are applied to all input tensors, such as tokenizers.
+    #[inline(always)]
+    pub fn subgraph_input_process_units(&self) -> &[ProcessUnit] {
+        self.process_units.subgraph_inputs.as_slice()
+    }
+
+    /// Get the metadata process units which are applied to all output tensors.
+    #[inline(always)]
+    pub fn subgraph_output_process_units(&self) -> &[ProcessUnit] {
+        self.process_units.subgraph_outputs.as_slice()
+    }
+
+    /// Get the model asset buffer.
+    #[inline(always)]
+    pub fn model_asset_buffer(&self) -> &[u8] {
+        self.buf.as_slice()
+    }
+
+    /// Get the number of model input tensors.
+    #[inline(always)]
+    pub fn input_tensor_count(&self) -> usize {
+        self.model_resource
+            .as_ref()
+            .map_or(0, |m| m.input_tensor_count())
+    }
+
+    /// Get the number of model output tensors.
+    #[inline(always)]
+    pub fn output_tensor_count(&self) -> usize {
+        self.model_resource
+            .as_ref()
+            .map_or(0, |m| m.output_tensor_count())
+    }
+
+    /// Get the type of input tensor at `index`.
+    /// String input tensors are fed as bytes, so the type is [`TensorType::U8`].
+    #[inline(always)]
+    pub fn input_tensor_type(&self, index: usize) -> Option<TensorType> {
+        self.model_resource.as_ref()?.input_tensor_type(index)
+    }
+
+    /// Get the type of output tensor at `index`.
+    #[inline(always)]
+    pub fn output_tensor_type(&self, index: usize) -> Option<TensorType> {
+        self.model_resource.as_ref()?.output_tensor_type(index)
+    }
+
+    /// Get the shape of input tensor at `index`.
+    #[inline(always)]
+    pub fn input_tensor_shape(&self, index: usize) -> Option<&[usize]> {
+        self.model_resource.as_ref()?.input_tensor_shape(index)
+    }
+
+    /// Get the shape of output tensor at `index`.
+    #[inline(always)]
+    pub fn output_tensor_shape(&self, index: usize) -> Option<&[usize]> {
+        self.model_resource.as_ref()?.output_tensor_shape(index)
+    }
+
+    /// Get the name of output tensor at `index`, which is defined in model metadata.
+    #[inline(always)]
+    pub fn output_tensor_name(&self, index: usize) -> Option<&str> {
+        self.model_resource.as_ref()?.output_tensor_name(index)
+    }
+
+    /// Get the quantization parameters of input tensor at `index`.
+    #[inline(always)]
+    pub fn input_tensor_quantization_parameters(
+        &self,
+        index: usize,
+    ) -> Option<QuantizationParameters> {
+        self.model_resource
+            .as_ref()?
+            .input_tensor_quantization_parameters(index)
+    }
+
+    /// Get the quantization parameters of output tensor at `index`.
+    #[inline(always)]
+    pub fn output_tensor_quantization_parameters(
+        &self,
+        index: usize,
+    ) -> Option<QuantizationParameters> {
+        self.model_resource
+            .as_ref()?
+            .output_tensor_quantization_parameters(index)
+    }
+
+    /// Get the labels of output tensor at `index`.
+    /// Return [`None`] if the model has no label file for this tensor.
+    pub fn output_tensor_labels(&self, index: usize) -> Option<Vec<Cow<str>>> {
+        match self
+            .model_resource
+            .as_ref()?
+            .output_tensor_labels_locale(index, "")
+        {
+            Ok((labels, _)) => Some(Self::read_lines(labels)),
+            Err(_) => None,
+        }
+    }
+
+    /// Get the display names of output tensor at `index` for the given locale, such as `en`.
+    /// Return [`None`] if the model has no label file for this locale.
+    pub fn output_tensor_display_names(&self, index: usize, locale: &str) -> Option<Vec<Cow<str>>> {
+        match self
+            .model_resource
+            .as_ref()?
+            .output_tensor_labels_locale(index, locale)
+        {
+            Ok((_, Some(labels_locale))) => Some(Self::read_lines(labels_locale)),
+            _ => None,
+        }
+    }
+
+    /// Get the locales of display names for output tensor at `index`.
+    #[inline(always)]
+    pub fn output_tensor_label_locales(&self, index: usize) -> Vec<&str> {
+        match self.model_resource {
+            Some(ref m) => m.output_tensor_labels_locales(index),
+            None => Vec::new(),
+        }
+    }
+
+    /// Get the bounding box properties (the index of `left`, `top`, `right`, `bottom`) of output tensor at `index`.
+    #[cfg(feature = "vision")]
+    #[inline(always)]
+    pub fn output_tensor_bounding_box_properties(&self, index: usize) -> Option<[usize; 4]> {
+        let mut res = [0; 4];
+        if self
+            .model_resource
+            .as_ref()?
+            .output_bounding_box_properties(index, &mut res)
+        {
+            Some(res)
+        } else {
+            None
+        }
+    }
+
+    /// Get the activation function of the model outputs, which is defined in model metadata.
+    #[inline(always)]
+    pub fn output_activation(&self) -> Activation {
+        match self.model_resource {
+            Some(ref m) => m.output_activation(),
+            None => Default::default(),
+        }
+    }
+
+    /// Get the necessary information to convert media to input tensor at `index`,
+    /// such as image normalization options, audio properties, text tokenizer information, etc.
+    #[inline(always)]
+    pub fn to_tensor_info(&self, index: usize) -> Option<&ToTensorInfo> {
+        self.model_resource.as_ref()?.to_tensor_info(index)
+    }
+
+    /// Get the names of files which are packed in the model asset (sorted by name).
+    pub fn associated_files(&self) -> Vec<&str> {
+        let mut names = match self
+            .model_resource
+            .as_ref()
+            .and_then(|m| m.associated_files())
+        {
+            Some(z) => z.file_names().collect(),
+            None => Vec::new(),
+        };
+        names.sort();
+        names
+    }
+
+    /// Get the content of a file which is packed in the model asset.
+    /// Return an error if the file is deflated and cannot be inflated.
+    #[inline(always)]
+    pub fn associated_file(&self, name: &str) -> Result<Option<&[u8]>, Error> {
+        match self
+            .model_resource
+            .as_ref()
+            .and_then(|r| r.associated_files())
+        {
+            Some(z) => z.get_file(name),
+            None => Ok(None),
+        }
+    }
+
+    #[inline(always)]
+    fn read_lines(buf: &[u8]) -> Vec<Cow<str>> {
+        let mut res = Vec::new();
+        let mut f = MemoryTextFile::new(buf);
+        while let Some(line) = f.next_line() {
+            res.push(line);
+        }
+        res
+    }
+}
+
+impl Display for ModelInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_model_asset_bundle() {
+            writeln!(f, "ModelAssetBundle:")?;
+            for name in self.bundle_file_names() {
+                let size = self.bundle_file(name).ok().flatten().map_or(0, |c| c.len());
+                writeln!(f, "  {} ({} bytes)", name, size)?;
+                if name.ends_with(".tflite") || name.ends_with(".task") {
+                    match self.bundle_model_info(name) {
+                        Ok(info) => {
+                            for line in info.to_string().lines() {
+                                writeln!(f, "    {}", line)?;
+                            }
+                        }
+                        Err(e) => writeln!(f, "    Error: {}", e)?,
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        writeln!(f, "ModelInfo:")?;
+        writeln!(f, "  Inputs:")?;
+        for i in 0..self.input_tensor_count() {
+            writeln!(f, "    Input #{}:", i)?;
+            writeln!(f, "      Type:  {:?}", self.input_tensor_type(i))?;
+            writeln!(f, "      Shape: {:?}", self.input_tensor_shape(i))?;
+            if let Some(q) = self.input_tensor_quantization_parameters(i) {
+                writeln!(
+                    f,
+                    "      Quantization: scale={}, zero_point={}",
+                    q.scale, q.zero_point
+                )?;
+            }
+            for unit in self.input_tensor_process_units(i) {
+                writeln!(f, "      Process unit: {:?}", unit)?;
+            }
+            if let Some(info) = self.to_tensor_info(i) {
+                #[cfg(feature = "vision")]
+                if let Ok(img) = info.try_to_image() {
+                    writeln!(f, "      Image: {:?}", img)?;
+                }
+                #[cfg(feature = "audio")]
+                if let Ok(audio) = info.try_to_audio() {
+                    writeln!(f, "      Audio: {:?}", audio)?;
+                }
+                #[cfg(feature = "text")]
+                if let Ok(text) = info.try_to_text() {
+                    match text {
+                        TextToTensorInfo::BertModel { max_seq_len, .. } => {
+                            writeln!(f, "      Text:  Bert model, max_seq_len={}", max_seq_len)?
+                        }
+                        TextToTensorInfo::RegexModel { max_seq_len, .. } => {
+                            writeln!(f, "      Text:  Regex model, max_seq_len={}", max_seq_len)?
+                        }
+                        TextToTensorInfo::StringModel => writeln!(f, "      Text:  String model")?,
+                        TextToTensorInfo::UseModel => writeln!(f, "      Text:  USE model")?,
+                    }
+                }
+            }
+        }
+
+        writeln!(f, "  Outputs:")?;
+        for i in 0..self.output_tensor_count() {
+            writeln!(f, "    Output #{}:", i)?;
+            if let Some(name) = self.output_tensor_name(i) {
+                writeln!(f, "      Name:   {}", name)?;
+            }
+            writeln!(f, "      Type:   {:?}", self.output_tensor_type(i))?;
+            writeln!(f, "      Shape:  {:?}", self.output_tensor_shape(i))?;
+            if let Some(q) = self.output_tensor_quantization_parameters(i) {
+                writeln!(
+                    f,
+                    "      Quantization: scale={}, zero_point={}",
+                    q.scale, q.zero_point
+                )?;
+            }
+            for unit in self.output_tensor_process_units(i) {
+                writeln!(f, "      Process unit: {:?}", unit)?;
+            }
+            if let Some(labels) = self.output_tensor_labels(i) {
+                writeln!(f, "      Labels: {} labels", labels.len())?;
+            }
+        }
+
+        let units = self.subgraph_input_process_units();
+        if !units.is_empty() {
+            writeln!(f, "  Input process units:")?;
+            for unit in units {
+                writeln!(f, "    {:?}", unit)?;
+            }
+        }
+        let units = self.subgraph_output_process_units();
+        if !units.is_empty() {
+            writeln!(f, "  Output process units:")?;
+            for unit in units {
+                writeln!(f, "    {:?}", unit)?;
+            }
+        }
+
+        let files = self.associated_files();
+        if !files.is_empty() {
+            writeln!(f, "  Associated files:")?;
+            for name in files {
+                let size = self
+                    .associated_file(name)
+                    .ok()
+                    .flatten()
+                    .map_or(0, |c| c.len());
+                writeln!(f, "    {} ({} bytes)", name, size)?;
+            }
+        }
+        Ok(())
+    }
+}
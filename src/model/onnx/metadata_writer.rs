@@ -0,0 +1,216 @@
+use super::protobuf::{write_bytes_field, ProtoReader};
+use super::*;
+use std::path::Path;
+
+/// Write metadata into an ONNX model as `metadata_props`, so that models exported from other frameworks
+/// (such as PyTorch) can be used in tasks with essential information.
+/// Existing properties with the same keys will be replaced.
+///
+/// The properties can also be written by other tools, such as `onnx.helper.set_model_props` in python:
+///
+/// | key | value |
+/// | --- | --- |
+/// | `labels.{output index}` | Labels, one label per line. `labels` is the same as `labels.0`. |
+/// | `labels.{output index}.{locale}` | Display names for the `locale`, in the same order as labels. |
+/// | `names` | Class names dict written by ultralytics, such as `{0: 'person', 1: 'bicycle'}`. It is used if `labels.0` is missing. |
+/// | `image_mean`, `image_std` | Normalization options `(input - mean) / std` for image inputs, separated by `,`. Float inputs without normalization options are in range `[0, 1]`. |
+/// | `image_layout` | `NCHW`, `NHWC` or `CHWN`. It is detected from the input shape if missing. |
+/// | `color_space` | `RGB` or `GRAYSCALE`. |
+/// | `audio_sample_rate`, `audio_channels` | Audio properties for input `0`. |
+/// | `activation` | `NONE`, `SIGMOID` or `SOFTMAX`. |
+/// | `bounding_box.{output index}` | The index of `left`, `top`, `right`, `bottom` in the last dimension, separated by `,`. |
+///
+/// ```rust
+/// use mediapipe_rs::model::OnnxMetadataWriter;
+///
+/// let model = OnnxMetadataWriter::from_path("resnet18.onnx")?
+///     .labels(0, ["cat", "dog"])
+///     .image_normalization(vec![123.675, 116.28, 103.53], vec![58.395, 57.12, 57.375])
+///     .finalize()?;
+/// std::fs::write("resnet18_with_metadata.onnx", model)?;
+/// ```
+pub struct OnnxMetadataWriter {
+    model_buf: Vec<u8>,
+    props: Vec<(String, String)>,
+    // output indices referenced by properties
+    output_indices: Vec<usize>,
+}
+
+impl OnnxMetadataWriter {
+    /// Create a metadata writer for the ONNX model buffer.
+    #[inline(always)]
+    pub fn new(model_buf: Vec<u8>) -> Self {
+        Self {
+            model_buf,
+            props: Vec::new(),
+            output_indices: Vec::new(),
+        }
+    }
+
+    /// Create a metadata writer for the ONNX model file.
+    #[inline(always)]
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Ok(Self::new(std::fs::read(path)?))
+    }
+
+    /// Set the labels for output tensor at `index`.
+    #[inline(always)]
+    pub fn labels(
+        mut self,
+        index: usize,
+        labels: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Self {
+        self.output_indices.push(index);
+        self.prop(
+            format!("{}.{}", LABELS_KEY, index),
+            Self::join_lines(labels),
+        )
+    }
+
+    /// Set the display names for output tensor at `index` for the `locale`, such as `en`, `zh`.
+    /// The display names must be in the same order as labels.
+    #[inline(always)]
+    pub fn display_names(
+        mut self,
+        index: usize,
+        locale: impl AsRef<str>,
+        display_names: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Self {
+        self.output_indices.push(index);
+        self.prop(
+            format!("{}.{}.{}", LABELS_KEY, index, locale.as_ref()),
+            Self::join_lines(display_names),
+        )
+    }
+
+    /// Set the image normalization options: `(input - mean) / std`. The length can be 1 or the number of channels.
+    #[cfg(feature = "vision")]
+    #[inline(always)]
+    pub fn image_normalization(self, mean: Vec<f32>, std: Vec<f32>) -> Self {
+        self.prop(IMAGE_MEAN_KEY.into(), Self::join_values(&mean))
+            .prop(IMAGE_STD_KEY.into(), Self::join_values(&std))
+    }
+
+    /// Set the image data layout of image inputs.
+    #[cfg(feature = "vision")]
+    #[inline(always)]
+    pub fn image_layout(self, layout: ImageDataLayout) -> Self {
+        self.prop(IMAGE_LAYOUT_KEY.into(), format!("{:?}", layout))
+    }
+
+    /// Set the color space of image inputs.
+    #[cfg(feature = "vision")]
+    #[inline(always)]
+    pub fn color_space(self, color_space: ImageColorSpaceType) -> Self {
+        self.prop(COLOR_SPACE_KEY.into(), format!("{:?}", color_space))
+    }
+
+    /// Mark the input `0` as an audio tensor with sample rate and number of channels.
+    #[cfg(feature = "audio")]
+    #[inline(always)]
+    pub fn audio_properties(self, sample_rate: u32, channels: u32) -> Self {
+        self.prop(AUDIO_SAMPLE_RATE_KEY.into(), sample_rate.to_string())
+            .prop(AUDIO_CHANNELS_KEY.into(), channels.to_string())
+    }
+
+    /// Set the activation function which is applied to the model output tensors.
+    #[inline(always)]
+    pub fn activation(self, activation: Activation) -> Self {
+        let activation = match activation {
+            Activation::None => "NONE",
+            Activation::SIGMOID => "SIGMOID",
+            Activation::SOFTMAX => "SOFTMAX",
+        };
+        self.prop(ACTIVATION_KEY.into(), activation.into())
+    }
+
+    /// Set the bounding box properties for output tensor at `index`:
+    /// the index of `left`, `top`, `right`, `bottom` in the last dimension.
+    #[cfg(feature = "vision")]
+    #[inline(always)]
+    pub fn bounding_box_properties(mut self, index: usize, bounding_box: [usize; 4]) -> Self {
+        self.output_indices.push(index);
+        let value = bounding_box.map(|b| b.to_string()).join(",");
+        self.prop(format!("{}.{}", BOUNDING_BOX_KEY, index), value)
+    }
+
+    /// Set a custom metadata property.
+    #[inline(always)]
+    pub fn metadata_prop(self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.prop(key.into(), value.into())
+    }
+
+    /// Write the metadata properties and return the new model buffer.
+    pub fn finalize(self) -> Result<Vec<u8>, Error> {
+        let output_count = OnnxModelResource::new(self.model_buf.as_slice())?.output_tensor_count();
+        for index in self.output_indices.iter() {
+            if *index >= output_count {
+                return Err(Error::ArgumentError(format!(
+                    "Output tensor index `{}` out of range, model has `{}` outputs",
+                    index, output_count
+                )));
+            }
+        }
+
+        let mut res = Vec::with_capacity(self.model_buf.len());
+        for field in ProtoReader::new(self.model_buf.as_slice()) {
+            let (number, value, raw) = field?;
+            if number == OnnxModelResource::MODEL_METADATA_PROPS_FIELD {
+                let (key, _) = OnnxModelResource::parse_string_string_entry(value)?;
+                // `labels` is an alias of `labels.0`
+                let key = if key == LABELS_KEY { "labels.0" } else { key };
+                if self.props.iter().any(|(k, _)| k == key) {
+                    continue;
+                }
+            }
+            res.extend_from_slice(raw);
+        }
+        for (key, value) in self.props.iter() {
+            let mut entry = Vec::with_capacity(key.len() + value.len() + 8);
+            write_bytes_field(
+                &mut entry,
+                OnnxModelResource::ENTRY_KEY_FIELD,
+                key.as_bytes(),
+            );
+            write_bytes_field(
+                &mut entry,
+                OnnxModelResource::ENTRY_VALUE_FIELD,
+                value.as_bytes(),
+            );
+            write_bytes_field(
+                &mut res,
+                OnnxModelResource::MODEL_METADATA_PROPS_FIELD,
+                &entry,
+            );
+        }
+
+        // check the result
+        OnnxModelResource::new(res.as_slice())?;
+        Ok(res)
+    }
+
+    #[inline(always)]
+    fn prop(mut self, key: String, value: String) -> Self {
+        self.props.retain(|(k, _)| *k != key);
+        self.props.push((key, value));
+        self
+    }
+
+    #[inline(always)]
+    fn join_lines(lines: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+        lines
+            .into_iter()
+            .map(|l| l.as_ref().to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[inline(always)]
+    fn join_values(values: &[f32]) -> String {
+        values
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
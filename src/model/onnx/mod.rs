@@ -0,0 +1,921 @@
+mod metadata_writer;
+mod protobuf;
+
+pub use metadata_writer::OnnxMetadataWriter;
+
+use super::*;
+#[cfg(feature = "vision")]
+use crate::preprocess::vision::ImageLikeTensorShape;
+use protobuf::{ProtoReader, WireValue};
+use std::borrow::Cow;
+
+// keys of the ONNX model `metadata_props`, see `OnnxMetadataWriter` for details.
+const LABELS_KEY: &'static str = "labels";
+const NAMES_KEY: &'static str = "names";
+const IMAGE_MEAN_KEY: &'static str = "image_mean";
+const IMAGE_STD_KEY: &'static str = "image_std";
+const IMAGE_LAYOUT_KEY: &'static str = "image_layout";
+const COLOR_SPACE_KEY: &'static str = "color_space";
+const AUDIO_SAMPLE_RATE_KEY: &'static str = "audio_sample_rate";
+const AUDIO_CHANNELS_KEY: &'static str = "audio_channels";
+const ACTIVATION_KEY: &'static str = "activation";
+const BOUNDING_BOX_KEY: &'static str = "bounding_box";
+
+pub(crate) struct OnnxModelResource<'buf> {
+    input_shape: Vec<Vec<usize>>,
+    output_shape: Vec<Vec<usize>>,
+    input_types: Vec<TensorType>,
+    output_types: Vec<TensorType>,
+    output_names: Vec<&'buf str>,
+    output_name_map: HashMap<&'buf str, usize>,
+    // (output index, locale, labels with one label per line)
+    output_labels: Vec<(usize, Option<&'buf str>, Cow<'buf, [u8]>)>,
+    #[cfg(feature = "vision")]
+    output_bounding_box_properties: HashMap<usize, [usize; 4]>,
+    to_tensor_info: Vec<ToTensorInfo<'buf>>,
+    output_activation: Activation,
+}
+
+impl<'buf> OnnxModelResource<'buf> {
+    // ModelProto
+    const MODEL_IR_VERSION_FIELD: u32 = 1;
+    const MODEL_GRAPH_FIELD: u32 = 7;
+    const MODEL_OPSET_IMPORT_FIELD: u32 = 8;
+    const MODEL_METADATA_PROPS_FIELD: u32 = 14;
+    // GraphProto
+    const GRAPH_INITIALIZER_FIELD: u32 = 5;
+    const GRAPH_INPUT_FIELD: u32 = 11;
+    const GRAPH_OUTPUT_FIELD: u32 = 12;
+    // TensorProto
+    const TENSOR_NAME_FIELD: u32 = 8;
+    // ValueInfoProto
+    const VALUE_INFO_NAME_FIELD: u32 = 1;
+    const VALUE_INFO_TYPE_FIELD: u32 = 2;
+    // TypeProto
+    const TYPE_TENSOR_TYPE_FIELD: u32 = 1;
+    // TypeProto.Tensor
+    const TENSOR_ELEM_TYPE_FIELD: u32 = 1;
+    const TENSOR_SHAPE_FIELD: u32 = 2;
+    // TensorShapeProto
+    const SHAPE_DIM_FIELD: u32 = 1;
+    // TensorShapeProto.Dimension
+    const DIM_VALUE_FIELD: u32 = 1;
+    // StringStringEntryProto
+    const ENTRY_KEY_FIELD: u32 = 1;
+    const ENTRY_VALUE_FIELD: u32 = 2;
+
+    /// ONNX models are protobuf messages which start with the `ir_version` field (field 1, varint),
+    /// and have the `graph` (field 7) or `opset_import` (field 8) message.
+    pub(super) fn is_onnx(buf: &[u8]) -> bool {
+        let mut fields = ProtoReader::new(buf);
+        match fields.next() {
+            Some(Ok((Self::MODEL_IR_VERSION_FIELD, WireValue::Varint(_), _))) => {}
+            _ => return false,
+        }
+        for field in fields {
+            match field {
+                Ok((
+                    Self::MODEL_GRAPH_FIELD | Self::MODEL_OPSET_IMPORT_FIELD,
+                    WireValue::LengthDelimited(_),
+                    _,
+                )) => return true,
+                Ok(_) => {}
+                Err(_) => return false,
+            }
+        }
+        false
+    }
+
+    pub(super) fn new(buf: &'buf [u8]) -> Result<Self, Error> {
+        let mut graph = None;
+        let mut metadata_props = HashMap::new();
+        for field in ProtoReader::new(buf) {
+            let (number, value, _) = field?;
+            match number {
+                Self::MODEL_GRAPH_FIELD => graph = value.as_bytes(),
+                Self::MODEL_METADATA_PROPS_FIELD => {
+                    let (k, v) = Self::parse_string_string_entry(value)?;
+                    metadata_props.insert(k, v);
+                }
+                _ => {}
+            }
+        }
+        let graph = match graph {
+            Some(g) => g,
+            None => return Err(Error::ModelParseError("ONNX model has no graph".into())),
+        };
+
+        let mut _self = Self {
+            input_shape: Vec::new(),
+            output_shape: Vec::new(),
+            input_types: Vec::new(),
+            output_types: Vec::new(),
+            output_names: Vec::new(),
+            output_name_map: Default::default(),
+            output_labels: Vec::new(),
+            #[cfg(feature = "vision")]
+            output_bounding_box_properties: Default::default(),
+            to_tensor_info: Vec::new(),
+            output_activation: Default::default(),
+        };
+        _self.parse_graph(graph)?;
+        _self.parse_metadata_props(&metadata_props)?;
+        Ok(_self)
+    }
+
+    #[inline]
+    fn parse_string_string_entry(value: WireValue<'buf>) -> Result<(&'buf str, &'buf str), Error> {
+        let buf = match value.as_bytes() {
+            Some(b) => b,
+            None => {
+                return Err(Error::ModelParseError(
+                    "Invalid ONNX metadata property".into(),
+                ))
+            }
+        };
+        let (mut key, mut value) = ("", "");
+        for field in ProtoReader::new(buf) {
+            let (number, v, _) = field?;
+            match number {
+                Self::ENTRY_KEY_FIELD => key = v.as_str()?,
+                Self::ENTRY_VALUE_FIELD => value = v.as_str()?,
+                _ => {}
+            }
+        }
+        Ok((key, value))
+    }
+
+    fn parse_graph(&mut self, graph: &'buf [u8]) -> Result<(), Error> {
+        let mut initializers = Vec::new();
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        for field in ProtoReader::new(graph) {
+            let (number, value, _) = field?;
+            let bytes = match value.as_bytes() {
+                Some(b) => b,
+                None => continue,
+            };
+            match number {
+                Self::GRAPH_INITIALIZER_FIELD => {
+                    for f in ProtoReader::new(bytes) {
+                        let (n, v, _) = f?;
+                        if n == Self::TENSOR_NAME_FIELD {
+                            initializers.push(v.as_str()?);
+                        }
+                    }
+                }
+                Self::GRAPH_INPUT_FIELD => inputs.push(bytes),
+                Self::GRAPH_OUTPUT_FIELD => outputs.push(bytes),
+                _ => {}
+            }
+        }
+
+        for input in inputs {
+            let (name, type_proto) = Self::parse_value_info(input)?;
+            // old exporters list the weights in graph inputs, they are not real inputs
+            if initializers.contains(&name) {
+                continue;
+            }
+            let (tensor_type, shape) =
+                Self::parse_tensor_type(type_proto, "input", self.input_shape.len())?;
+            self.input_types.push(tensor_type);
+            self.input_shape.push(shape);
+        }
+
+        for output in outputs {
+            let (name, type_proto) = Self::parse_value_info(output)?;
+            let index = self.output_shape.len();
+            let (tensor_type, shape) = Self::parse_tensor_type(type_proto, "output", index)?;
+            self.output_types.push(tensor_type);
+            self.output_shape.push(shape);
+            self.output_names.push(name);
+            self.output_name_map.insert(name, index);
+        }
+
+        if self.input_shape.is_empty() || self.output_shape.is_empty() {
+            return Err(Error::ModelParseError(
+                "Model must has inputs and outputs information.".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn parse_value_info(buf: &'buf [u8]) -> Result<(&'buf str, Option<&'buf [u8]>), Error> {
+        let mut name = "";
+        let mut type_proto = None;
+        for field in ProtoReader::new(buf) {
+            let (number, value, _) = field?;
+            match number {
+                Self::VALUE_INFO_NAME_FIELD => name = value.as_str()?,
+                Self::VALUE_INFO_TYPE_FIELD => type_proto = value.as_bytes(),
+                _ => {}
+            }
+        }
+        Ok((name, type_proto))
+    }
+
+    fn parse_tensor_type(
+        type_proto: Option<&'buf [u8]>,
+        kind: &str,
+        index: usize,
+    ) -> Result<(TensorType, Vec<usize>), Error> {
+        let mut tensor = None;
+        for field in ProtoReader::new(type_proto.unwrap_or_default()) {
+            let (number, value, _) = field?;
+            if number == Self::TYPE_TENSOR_TYPE_FIELD {
+                tensor = value.as_bytes();
+            }
+        }
+        let tensor = match tensor {
+            Some(t) => t,
+            None => {
+                return Err(Error::ModelParseError(format!(
+                    "Model {} `{}` is not a tensor",
+                    kind, index
+                )))
+            }
+        };
+
+        let mut elem_type = 0;
+        let mut shape_proto = None;
+        for field in ProtoReader::new(tensor) {
+            let (number, value, _) = field?;
+            match number {
+                Self::TENSOR_ELEM_TYPE_FIELD => elem_type = value.as_varint().unwrap_or(0),
+                Self::TENSOR_SHAPE_FIELD => shape_proto = value.as_bytes(),
+                _ => {}
+            }
+        }
+        let tensor_type = Self::onnx_type_parse(elem_type)?;
+        let shape_proto = match shape_proto {
+            Some(s) => s,
+            None => {
+                return Err(Error::ModelParseError(format!(
+                    "Missing tensor shape for {} `{}`",
+                    kind, index
+                )))
+            }
+        };
+
+        let mut shape = Vec::new();
+        for field in ProtoReader::new(shape_proto) {
+            let (number, value, _) = field?;
+            if number != Self::SHAPE_DIM_FIELD {
+                continue;
+            }
+            // symbolic dimensions such as batch size are treated as `1`
+            let mut val = 1;
+            for f in ProtoReader::new(value.as_bytes().unwrap_or_default()) {
+                let (n, v, _) = f?;
+                if n == Self::DIM_VALUE_FIELD {
+                    let d = v.as_varint().unwrap_or(0) as i64;
+                    if d == 0 {
+                        return Err(Error::ModelParseError(format!(
+                            "Invalid model {} `{}` shape `{}`, size is `0`",
+                            kind,
+                            index,
+                            shape.len()
+                        )));
+                    }
+                    // negative value means dynamic dimension
+                    val = if d > 0 { d as usize } else { 1 };
+                }
+            }
+            shape.push(val);
+        }
+        Ok((tensor_type, shape))
+    }
+
+    #[inline(always)]
+    fn onnx_type_parse(elem_type: u64) -> Result<TensorType, Error> {
+        match elem_type {
+            // FLOAT
+            1 => Ok(TensorType::F32),
+            // UINT8
+            2 => Ok(TensorType::U8),
+            // INT32
+            6 => Ok(TensorType::I32),
+            // FLOAT16
+            10 => Ok(TensorType::F16),
+            _ => Err(Error::ModelParseError(format!(
+                "Unsupported ONNX tensor type `{}`",
+                elem_type
+            ))),
+        }
+    }
+
+    fn parse_metadata_props(&mut self, props: &HashMap<&'buf str, &'buf str>) -> Result<(), Error> {
+        for (&key, &value) in props.iter() {
+            if let Some((index, locale)) = Self::parse_labels_key(key) {
+                self.check_output_index(key, index)?;
+                self.output_labels
+                    .retain(|(i, l, _)| *i != index || *l != locale);
+                self.output_labels
+                    .push((index, locale, Cow::Borrowed(value.as_bytes())));
+            }
+
+            #[cfg(feature = "vision")]
+            if let Some(index) = key
+                .strip_prefix(BOUNDING_BOX_KEY)
+                .and_then(|k| k.strip_prefix('.'))
+            {
+                let index = Self::parse_prop(key, index)?;
+                self.check_output_index(key, index)?;
+                let values = Self::parse_prop_list::<usize>(key, value)?;
+                if values.len() != 4 {
+                    return Err(Error::ModelParseError(format!(
+                        "ONNX metadata `{}` must have 4 values, but got `{}`",
+                        key, value
+                    )));
+                }
+                self.output_bounding_box_properties
+                    .insert(index, [values[0], values[1], values[2], values[3]]);
+            }
+        }
+
+        // class names written by ultralytics
+        if !self
+            .output_labels
+            .iter()
+            .any(|(i, l, _)| *i == 0 && l.is_none())
+        {
+            if let Some(names) = props.get(NAMES_KEY) {
+                if let Some(labels) = Self::parse_names_dict(names) {
+                    self.output_labels
+                        .push((0, None, Cow::Owned(labels.into_bytes())));
+                }
+            }
+        }
+
+        if let Some(activation) = props.get(ACTIVATION_KEY) {
+            self.output_activation = match activation.to_ascii_uppercase().as_str() {
+                "NONE" => Activation::None,
+                "SIGMOID" => Activation::SIGMOID,
+                "SOFTMAX" => Activation::SOFTMAX,
+                _ => {
+                    return Err(Error::ModelParseError(format!(
+                        "Invalid activation type found in ONNX metadata: `{}`",
+                        activation
+                    )))
+                }
+            };
+        }
+
+        for i in 0..self.input_shape.len() {
+            #[cfg(feature = "audio")]
+            if i == 0 {
+                if let Some(sample_rate) = props.get(AUDIO_SAMPLE_RATE_KEY) {
+                    let audio_info = self.parse_audio_model_input_info(
+                        i,
+                        sample_rate,
+                        props.get(AUDIO_CHANNELS_KEY).cloned(),
+                    )?;
+                    self.to_tensor_info
+                        .push(ToTensorInfo::new_audio(audio_info));
+                    continue;
+                }
+            }
+
+            #[cfg(feature = "vision")]
+            if let Some(img_info) = self.parse_vision_model_input_info(i, props)? {
+                self.to_tensor_info.push(ToTensorInfo::new_image(img_info));
+                continue;
+            }
+
+            self.to_tensor_info.push(ToTensorInfo::new_none());
+        }
+        Ok(())
+    }
+
+    // `labels` -> (0, None), `labels.{index}` -> (index, None), `labels.{index}.{locale}` -> (index, locale)
+    #[inline]
+    fn parse_labels_key(key: &'buf str) -> Option<(usize, Option<&'buf str>)> {
+        let suffix = key.strip_prefix(LABELS_KEY)?;
+        if suffix.is_empty() {
+            return Some((0, None));
+        }
+        let mut parts = suffix.strip_prefix('.')?.splitn(2, '.');
+        let index = parts.next()?.parse().ok()?;
+        Some((index, parts.next()))
+    }
+
+    // parse the class names dict string, such as `{0: 'person', 1: 'bicycle'}`
+    fn parse_names_dict(names: &str) -> Option<String> {
+        let mut s = names.trim().strip_prefix('{')?.strip_suffix('}')?.trim();
+        let mut res = Vec::new();
+        while !s.is_empty() {
+            let (key, rest) = s.split_once(':')?;
+            let key: usize = key.trim().parse().ok()?;
+            let rest = rest.trim_start();
+            let quote = rest.chars().next()?;
+            if quote != '\'' && quote != '"' {
+                return None;
+            }
+            let rest = &rest[1..];
+            let end = rest.find(quote)?;
+            res.push((key, &rest[..end]));
+            s = rest[end + 1..].trim_start();
+            s = s.strip_prefix(',').unwrap_or(s).trim_start();
+        }
+        let len = res.iter().map(|(k, _)| k + 1).max()?;
+        let mut labels = vec![""; len];
+        for (k, name) in res {
+            labels[k] = name;
+        }
+        Some(labels.join("\n"))
+    }
+
+    #[inline(always)]
+    fn check_output_index(&self, key: &str, index: usize) -> Result<(), Error> {
+        if index >= self.output_shape.len() {
+            return Err(Error::ModelParseError(format!(
+                "ONNX metadata `{}` output index out of range, model has `{}` outputs",
+                key,
+                self.output_shape.len()
+            )));
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn parse_prop<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, Error> {
+        value.trim().parse().map_err(|_| {
+            Error::ModelParseError(format!("Invalid ONNX metadata `{}`: `{}`", key, value))
+        })
+    }
+
+    #[inline(always)]
+    fn parse_prop_list<T: std::str::FromStr>(key: &str, value: &str) -> Result<Vec<T>, Error> {
+        value.split(',').map(|v| Self::parse_prop(key, v)).collect()
+    }
+
+    #[cfg(feature = "vision")]
+    fn parse_vision_model_input_info(
+        &self,
+        i: usize,
+        props: &HashMap<&'buf str, &'buf str>,
+    ) -> Result<Option<ImageToTensorInfo>, Error> {
+        let shape = self.input_shape[i].as_slice();
+        let tensor_type = self.input_types[i];
+        if shape.len() != 4 || (tensor_type != TensorType::F32 && tensor_type != TensorType::U8) {
+            return Ok(None);
+        }
+
+        let image_data_layout = match props.get(IMAGE_LAYOUT_KEY) {
+            Some(l) => match l.to_ascii_uppercase().as_str() {
+                "NCHW" => ImageDataLayout::NCHW,
+                "NHWC" => ImageDataLayout::NHWC,
+                "CHWN" => ImageDataLayout::CHWN,
+                _ => {
+                    return Err(Error::ModelParseError(format!(
+                        "Invalid image layout found in ONNX metadata: `{}`",
+                        l
+                    )))
+                }
+            },
+            // PyTorch models use `NCHW` layout
+            None => match ImageDataLayout::detect(shape) {
+                Some(l) => l,
+                None => return Ok(None),
+            },
+        };
+        let tensor_shape = ImageLikeTensorShape::parse(image_data_layout, shape)?;
+
+        let color_space = match props.get(COLOR_SPACE_KEY) {
+            Some(c) => match c.to_ascii_uppercase().as_str() {
+                "RGB" => ImageColorSpaceType::RGB,
+                "GRAYSCALE" => ImageColorSpaceType::GRAYSCALE,
+                _ => ImageColorSpaceType::UNKNOWN,
+            },
+            None => match tensor_shape.channels {
+                3 => ImageColorSpaceType::RGB,
+                1 => ImageColorSpaceType::GRAYSCALE,
+                _ => ImageColorSpaceType::UNKNOWN,
+            },
+        };
+
+        let mean = match props.get(IMAGE_MEAN_KEY) {
+            Some(m) => Some(Self::parse_prop_list::<f32>(IMAGE_MEAN_KEY, m)?),
+            None => None,
+        };
+        let std = match props.get(IMAGE_STD_KEY) {
+            Some(s) => Some(Self::parse_prop_list::<f32>(IMAGE_STD_KEY, s)?),
+            None => None,
+        };
+        let normalization_options = match (mean, std) {
+            (Some(m), Some(s)) => (m, s),
+            (Some(m), None) => (m, vec![1.]),
+            (None, Some(s)) => (vec![0.], s),
+            // float inputs are in range [0, 1] by default
+            (None, None) if tensor_type == TensorType::F32 => (vec![0.], vec![255.]),
+            (None, None) => (vec![], vec![]),
+        };
+        for (key, v) in [
+            (IMAGE_MEAN_KEY, &normalization_options.0),
+            (IMAGE_STD_KEY, &normalization_options.1),
+        ] {
+            if !v.is_empty() && v.len() != 1 && v.len() != tensor_shape.channels {
+                return Err(Error::ModelParseError(format!(
+                    "ONNX metadata `{}` length must be `1` or `{}`, but got `{}`",
+                    key,
+                    tensor_shape.channels,
+                    v.len()
+                )));
+            }
+        }
+
+        Ok(Some(ImageToTensorInfo {
+            image_data_layout,
+            color_space,
+            tensor_type,
+            tensor_shape,
+            stats_min: vec![],
+            stats_max: vec![],
+            normalization_options,
+        }))
+    }
+
+    #[cfg(feature = "audio")]
+    fn parse_audio_model_input_info(
+        &self,
+        i: usize,
+        sample_rate: &str,
+        channels: Option<&str>,
+    ) -> Result<AudioToTensorInfo, Error> {
+        let input_shape = self.input_shape.get(i).unwrap();
+        let sample_rate = Self::parse_prop(AUDIO_SAMPLE_RATE_KEY, sample_rate)?;
+        let num_channels = match channels {
+            Some(c) => Self::parse_prop(AUDIO_CHANNELS_KEY, c)?,
+            None => 1,
+        };
+        if num_channels == 0 {
+            return Err(Error::ModelParseError(format!(
+                "Audio input tensor `{}`, num channel cannot be zero",
+                i
+            )));
+        }
+        let input_buffer_size = input_shape.iter().product::<usize>();
+        if input_buffer_size % num_channels != 0 {
+            return Err(Error::ModelParseError(format!(
+                "Input tensor size `{}` should be a multiplier of the number of channels `{}`",
+                input_buffer_size, num_channels
+            )));
+        }
+        let num_samples = input_shape.last().cloned().unwrap_or(1) / num_channels;
+        Ok(AudioToTensorInfo {
+            num_channels,
+            num_samples,
+            sample_rate,
+            num_overlapping_samples: 0,
+            tensor_type: self.input_types[i],
+        })
+    }
+}
+
+impl<'buf> ModelResourceTrait for OnnxModelResource<'buf> {
+    fn model_backend(&self) -> GraphEncoding {
+        GraphEncoding::Onnx
+    }
+
+    fn input_tensor_count(&self) -> usize {
+        self.input_shape.len()
+    }
+
+    fn output_tensor_count(&self) -> usize {
+        self.output_shape.len()
+    }
+
+    fn input_tensor_type(&self, index: usize) -> Option<TensorType> {
+        self.input_types.get(index).cloned()
+    }
+
+    fn output_tensor_type(&self, index: usize) -> Option<TensorType> {
+        self.output_types.get(index).cloned()
+    }
+
+    fn input_tensor_shape(&self, index: usize) -> Option<&[usize]> {
+        self.input_shape.get(index).map(|v| v.as_slice())
+    }
+
+    fn output_tensor_shape(&self, index: usize) -> Option<&[usize]> {
+        self.output_shape.get(index).map(|v| v.as_slice())
+    }
+
+    fn output_tensor_name_to_index(&self, name: &'static str) -> Option<usize> {
+        self.output_name_map.get(name).cloned()
+    }
+
+    fn output_tensor_name(&self, index: usize) -> Option<&str> {
+        self.output_names.get(index).cloned()
+    }
+
+    fn input_tensor_quantization_parameters(
+        &self,
+        _index: usize,
+    ) -> Option<QuantizationParameters> {
+        // quantized ONNX models use `QuantizeLinear` operators, the inputs are float.
+        None
+    }
+
+    fn output_tensor_quantization_parameters(
+        &self,
+        _index: usize,
+    ) -> Option<QuantizationParameters> {
+        // quantized ONNX models use `DequantizeLinear` operators, the outputs are float.
+        None
+    }
+
+    fn output_tensor_labels_locale(
+        &self,
+        index: usize,
+        locale_name: &str,
+    ) -> Result<(&[u8], Option<&[u8]>), Error> {
+        let mut l = None;
+        let mut locale = None;
+        for (i, name, labels) in self.output_labels.iter() {
+            if *i != index {
+                continue;
+            }
+            match name {
+                None => l = Some(labels.as_ref()),
+                Some(n) if *n == locale_name => locale = Some(labels.as_ref()),
+                _ => {}
+            }
+        }
+        match (l, locale) {
+            (Some(l), locale) => Ok((l, locale)),
+            (None, Some(locale)) => Ok((locale, Some(locale))),
+            (None, None) => Err(Error::ModelInconsistentError(
+                "Missing model label file information.".into(),
+            )),
+        }
+    }
+
+    fn output_tensor_labels_locales(&self, index: usize) -> Vec<&str> {
+        self.output_labels
+            .iter()
+            .filter(|(i, _, _)| *i == index)
+            .filter_map(|(_, locale, _)| *locale)
+            .collect()
+    }
+
+    #[cfg(feature = "vision")]
+    fn output_bounding_box_properties(&self, index: usize, slice: &mut [usize]) -> bool {
+        if let Some(b) = self.output_bounding_box_properties.get(&index) {
+            slice[..4].copy_from_slice(b);
+            return true;
+        }
+        false
+    }
+
+    #[cfg(feature = "vision")]
+    fn ssd_detection_metadata(&self) -> Option<&SsdDetectionMetadata> {
+        None
+    }
+
+    fn to_tensor_info(&self, input_index: usize) -> Option<&ToTensorInfo> {
+        self.to_tensor_info.get(input_index)
+    }
+
+    fn output_activation(&self) -> Activation {
+        self.output_activation
+    }
+
+    fn associated_files(&self) -> Option<&ZipFiles> {
+        None
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::protobuf::{write_bytes_field, write_varint_field};
+    use super::*;
+
+    // negative dimension will be written as symbolic dimension
+    fn value_info(name: &str, elem_type: u64, dims: &[i64]) -> Vec<u8> {
+        let mut shape = Vec::new();
+        for d in dims {
+            let mut dim = Vec::new();
+            if *d < 0 {
+                write_bytes_field(&mut dim, 2, b"batch");
+            } else {
+                write_varint_field(&mut dim, 1, *d as u64);
+            }
+            write_bytes_field(&mut shape, 1, &dim);
+        }
+        let mut tensor = Vec::new();
+        write_varint_field(&mut tensor, 1, elem_type);
+        write_bytes_field(&mut tensor, 2, &shape);
+        let mut type_proto = Vec::new();
+        write_bytes_field(&mut type_proto, 1, &tensor);
+        let mut res = Vec::new();
+        write_bytes_field(&mut res, 1, name.as_bytes());
+        write_bytes_field(&mut res, 2, &type_proto);
+        res
+    }
+
+    /// Build an ONNX model which only has graph inputs and outputs, (name, elem type, dims).
+    pub(crate) fn onnx_model(
+        inputs: &[(&str, u64, &[i64])],
+        outputs: &[(&str, u64, &[i64])],
+    ) -> Vec<u8> {
+        let mut graph = Vec::new();
+        // an int64 weight which is also listed in graph inputs
+        let mut initializer = Vec::new();
+        write_varint_field(&mut initializer, 1, 1);
+        write_bytes_field(&mut initializer, 8, b"weight");
+        write_bytes_field(&mut graph, 5, &initializer);
+        for (name, elem_type, dims) in inputs {
+            write_bytes_field(&mut graph, 11, &value_info(name, *elem_type, dims));
+        }
+        write_bytes_field(&mut graph, 11, &value_info("weight", 7, &[1]));
+        for (name, elem_type, dims) in outputs {
+            write_bytes_field(&mut graph, 12, &value_info(name, *elem_type, dims));
+        }
+        let mut model = Vec::new();
+        write_varint_field(&mut model, 1, 8);
+        write_bytes_field(&mut model, 7, &graph);
+        model
+    }
+
+    #[test]
+    fn test_parse_onnx_model() {
+        let buf = onnx_model(
+            &[("input", 1, &[-1, 3, 224, 224])],
+            &[("logits", 1, &[-1, 1000]), ("features", 10, &[1, 512])],
+        );
+        let model = parse_model(&buf).unwrap();
+        assert!(matches!(model.model_backend(), GraphEncoding::Onnx));
+        assert_eq!(model.input_tensor_count(), 1);
+        assert_eq!(
+            model.input_tensor_shape(0),
+            Some([1, 3, 224, 224].as_slice())
+        );
+        assert_eq!(model.input_tensor_type(0), Some(TensorType::F32));
+        assert_eq!(model.output_tensor_count(), 2);
+        assert_eq!(model.output_tensor_shape(0), Some([1, 1000].as_slice()));
+        assert_eq!(model.output_tensor_type(1), Some(TensorType::F16));
+        assert_eq!(model.output_tensor_name(1), Some("features"));
+        assert_eq!(model.output_tensor_name_to_index("logits"), Some(0));
+        assert!(model.output_tensor_quantization_parameters(0).is_none());
+        assert!(model.output_tensor_labels_locale(0, "en").is_err());
+        assert_eq!(model.output_activation(), Activation::None);
+        assert!(model.associated_files().is_none());
+
+        #[cfg(feature = "vision")]
+        {
+            let info = model.to_tensor_info(0).unwrap().try_to_image().unwrap();
+            assert_eq!(info.image_data_layout, ImageDataLayout::NCHW);
+            assert_eq!(info.color_space, ImageColorSpaceType::RGB);
+            assert_eq!((info.width(), info.height()), (224, 224));
+            assert_eq!(info.normalization_options, (vec![0.], vec![255.]));
+        }
+
+        // unsupported int64 output
+        let buf = onnx_model(&[("input", 1, &[1, 3])], &[("output", 7, &[1])]);
+        assert!(parse_model(&buf).is_err());
+        // zero dimension
+        let buf = onnx_model(&[("input", 1, &[1, 0])], &[("output", 1, &[1])]);
+        assert!(parse_model(&buf).is_err());
+        // no graph
+        assert!(parse_model(&[0x08, 0x08, 0x3a, 0x00, 0x00, 0x00, 0x00, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_is_onnx() {
+        let buf = onnx_model(&[("input", 1, &[1, 3])], &[("output", 1, &[1, 3])]);
+        assert!(OnnxModelResource::is_onnx(&buf));
+        assert!(OnnxModelResource::is_onnx(
+            &std::fs::read("assets/testdata/test_add.onnx").unwrap()
+        ));
+
+        // start with `ir_version` but not an ONNX model
+        assert!(!OnnxModelResource::is_onnx(&[
+            0x08, 0x07, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff
+        ]));
+        assert!(!OnnxModelResource::is_onnx(&[
+            0x08, 0x07, 0x12, 0x01, b'a', 0, 0, 0
+        ]));
+        assert!(parse_model(&[0x08, 0x07, 0x12, 0x01, b'a', 0, 0, 0]).is_err());
+        // `graph` with wrong wire type
+        assert!(!OnnxModelResource::is_onnx(&[0x08, 0x07, 0x38, 0x01]));
+        assert!(!OnnxModelResource::is_onnx(&[]));
+    }
+
+    #[test]
+    fn test_parse_test_add_onnx() {
+        let buf = std::fs::read("assets/testdata/test_add.onnx").unwrap();
+        let model = parse_model(&buf).unwrap();
+        assert_eq!(model.input_tensor_count(), 1);
+        assert_eq!(model.input_tensor_shape(0), Some([2].as_slice()));
+        assert_eq!(model.output_tensor_type(0), Some(TensorType::F32));
+        #[cfg(feature = "vision")]
+        assert!(model.to_tensor_info(0).unwrap().try_to_image().is_err());
+    }
+
+    #[test]
+    fn test_onnx_metadata_writer() {
+        let buf = onnx_model(
+            &[("images", 2, &[1, 320, 320, 3])],
+            &[("location", 1, &[1, 10, 4]), ("score", 1, &[1, 10])],
+        );
+        let writer = OnnxMetadataWriter::new(buf.clone())
+            .labels(1, ["cat", "dog"])
+            .display_names(1, "zh", ["猫", "狗"])
+            .activation(Activation::SIGMOID);
+        #[cfg(feature = "vision")]
+        let writer = writer
+            .image_normalization(vec![127.5], vec![127.5])
+            .color_space(ImageColorSpaceType::RGB)
+            .bounding_box_properties(0, [1, 0, 3, 2]);
+        let buf = writer.finalize().unwrap();
+
+        let model = parse_model(&buf).unwrap();
+        assert_eq!(model.output_activation(), Activation::SIGMOID);
+        assert_eq!(
+            model.output_tensor_labels_locale(1, "zh").unwrap(),
+            ("cat\ndog".as_bytes(), Some("猫\n狗".as_bytes()))
+        );
+        assert_eq!(
+            model.output_tensor_labels_locale(1, "en").unwrap(),
+            ("cat\ndog".as_bytes(), None)
+        );
+        assert_eq!(model.output_tensor_labels_locales(1), vec!["zh"]);
+        assert!(model.output_tensor_labels_locale(0, "zh").is_err());
+        #[cfg(feature = "vision")]
+        {
+            let mut bbox = [0; 4];
+            assert!(model.output_bounding_box_properties(0, &mut bbox));
+            assert_eq!(bbox, [1, 0, 3, 2]);
+            assert!(!model.output_bounding_box_properties(1, &mut bbox));
+
+            let info = model.to_tensor_info(0).unwrap().try_to_image().unwrap();
+            assert_eq!(info.image_data_layout, ImageDataLayout::NHWC);
+            assert_eq!(info.tensor_type, TensorType::U8);
+            assert_eq!(info.normalization_options, (vec![127.5], vec![127.5]));
+        }
+
+        // rewrite labels, old properties are replaced
+        let buf = OnnxMetadataWriter::new(buf.clone())
+            .labels(1, ["person"])
+            .metadata_prop("author", "mediapipe-rs")
+            .finalize()
+            .unwrap();
+        let props = ProtoReader::new(&buf)
+            .map(|f| f.unwrap())
+            .filter(|(n, _, _)| *n == OnnxModelResource::MODEL_METADATA_PROPS_FIELD)
+            .map(|(_, v, _)| OnnxModelResource::parse_string_string_entry(v).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(props.iter().filter(|(k, _)| *k == "labels.1").count(), 1);
+        assert!(props.contains(&("author", "mediapipe-rs")));
+        let model = parse_model(&buf).unwrap();
+        assert_eq!(
+            model.output_tensor_labels_locale(1, "").unwrap().0,
+            "person".as_bytes()
+        );
+
+        // output index out of range
+        assert!(OnnxMetadataWriter::new(buf.clone())
+            .labels(2, ["a"])
+            .finalize()
+            .is_err());
+        // invalid normalization options
+        #[cfg(feature = "vision")]
+        assert!(OnnxMetadataWriter::new(buf.clone())
+            .image_normalization(vec![0., 0.], vec![1.])
+            .finalize()
+            .is_err());
+    }
+
+    #[test]
+    fn test_onnx_names_dict() {
+        let buf = onnx_model(
+            &[("images", 1, &[1, 3, 640, 640])],
+            &[("output0", 1, &[1, 84, 8400])],
+        );
+        let buf = OnnxMetadataWriter::new(buf.clone())
+            .metadata_prop("names", r#"{0: 'person', 2: "it's", 1: 'traffic light'}"#)
+            .finalize()
+            .unwrap();
+        let model = parse_model(&buf).unwrap();
+        assert_eq!(
+            model.output_tensor_labels_locale(0, "").unwrap().0,
+            "person\ntraffic light\nit's".as_bytes()
+        );
+
+        // explicit labels take precedence
+        let buf = OnnxMetadataWriter::new(buf.clone())
+            .labels(0, ["a", "b"])
+            .finalize()
+            .unwrap();
+        let model = parse_model(&buf).unwrap();
+        assert_eq!(
+            model.output_tensor_labels_locale(0, "").unwrap().0,
+            "a\nb".as_bytes()
+        );
+
+        assert!(OnnxModelResource::parse_names_dict("{0: person}").is_none());
+        assert!(OnnxModelResource::parse_names_dict("[]").is_none());
+    }
+}
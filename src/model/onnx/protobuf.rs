@@ -0,0 +1,199 @@
+use crate::Error;
+
+/// Protobuf wire value, see <https://protobuf.dev/programming-guides/encoding/>
+#[derive(Debug, Clone, Copy)]
+pub(super) enum WireValue<'buf> {
+    Varint(u64),
+    Fixed64(u64),
+    LengthDelimited(&'buf [u8]),
+    Fixed32(u32),
+}
+
+impl<'buf> WireValue<'buf> {
+    #[inline(always)]
+    pub(super) fn as_varint(&self) -> Option<u64> {
+        match self {
+            WireValue::Varint(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn as_bytes(&self) -> Option<&'buf [u8]> {
+        match self {
+            WireValue::LengthDelimited(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn as_str(&self) -> Result<&'buf str, Error> {
+        match self {
+            WireValue::LengthDelimited(b) => std::str::from_utf8(b).map_err(|e| {
+                Error::ModelParseError(format!("Invalid protobuf utf-8 string: `{}`", e))
+            }),
+            _ => Err(Error::ModelParseError(
+                "Expect a protobuf string field".into(),
+            )),
+        }
+    }
+}
+
+/// A minimal protobuf message reader, which iterates all fields as (field number, value, raw field bytes).
+pub(super) struct ProtoReader<'buf> {
+    buf: &'buf [u8],
+    pos: usize,
+}
+
+impl<'buf> ProtoReader<'buf> {
+    #[inline(always)]
+    pub(super) fn new(buf: &'buf [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    #[inline]
+    fn read_varint(&mut self) -> Result<u64, Error> {
+        let mut res = 0u64;
+        let mut shift = 0;
+        loop {
+            if self.pos >= self.buf.len() {
+                return Err(Error::ModelParseError(
+                    "Unexpected end of protobuf varint".into(),
+                ));
+            }
+            let b = self.buf[self.pos];
+            self.pos += 1;
+            if shift >= 64 {
+                return Err(Error::ModelParseError("Protobuf varint overflow".into()));
+            }
+            res |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(res);
+            }
+            shift += 7;
+        }
+    }
+
+    #[inline]
+    fn read_bytes(&mut self, len: usize) -> Result<&'buf [u8], Error> {
+        if self.buf.len() - self.pos < len {
+            return Err(Error::ModelParseError(format!(
+                "Unexpected end of protobuf message, expect `{}` bytes but only `{}` left",
+                len,
+                self.buf.len() - self.pos
+            )));
+        }
+        let res = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(res)
+    }
+
+    #[inline]
+    fn next_field(&mut self) -> Result<(u32, WireValue<'buf>, &'buf [u8]), Error> {
+        let start = self.pos;
+        let key = self.read_varint()?;
+        let field = (key >> 3) as u32;
+        let value = match key & 0x7 {
+            0 => WireValue::Varint(self.read_varint()?),
+            1 => {
+                let mut b = [0u8; 8];
+                b.copy_from_slice(self.read_bytes(8)?);
+                WireValue::Fixed64(u64::from_le_bytes(b))
+            }
+            2 => {
+                let len = self.read_varint()? as usize;
+                WireValue::LengthDelimited(self.read_bytes(len)?)
+            }
+            5 => {
+                let mut b = [0u8; 4];
+                b.copy_from_slice(self.read_bytes(4)?);
+                WireValue::Fixed32(u32::from_le_bytes(b))
+            }
+            t => {
+                return Err(Error::ModelParseError(format!(
+                    "Unsupported protobuf wire type `{}`",
+                    t
+                )));
+            }
+        };
+        Ok((field, value, &self.buf[start..self.pos]))
+    }
+}
+
+impl<'buf> Iterator for ProtoReader<'buf> {
+    type Item = Result<(u32, WireValue<'buf>, &'buf [u8]), Error>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.buf.len() {
+            return None;
+        }
+        let res = self.next_field();
+        if res.is_err() {
+            // stop at the first error
+            self.pos = self.buf.len();
+        }
+        Some(res)
+    }
+}
+
+#[inline]
+pub(super) fn write_varint(buf: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        buf.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+#[inline(always)]
+pub(super) fn write_varint_field(buf: &mut Vec<u8>, field: u32, v: u64) {
+    write_varint(buf, (field as u64) << 3);
+    write_varint(buf, v);
+}
+
+#[inline(always)]
+pub(super) fn write_bytes_field(buf: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    write_varint(buf, ((field as u64) << 3) | 2);
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_proto_reader() {
+        let mut buf = Vec::new();
+        write_varint_field(&mut buf, 1, 300);
+        write_bytes_field(&mut buf, 2, b"abc");
+        write_varint_field(&mut buf, 16, u64::MAX);
+        buf.extend_from_slice(&[(3 << 3) | 5, 1, 0, 0, 0]);
+        buf.extend_from_slice(&[(4 << 3) | 1, 2, 0, 0, 0, 0, 0, 0, 0]);
+
+        let fields: Vec<_> = ProtoReader::new(&buf).map(|f| f.unwrap()).collect();
+        assert_eq!(fields.len(), 5);
+        assert_eq!(fields[0].0, 1);
+        assert_eq!(fields[0].1.as_varint(), Some(300));
+        assert_eq!(fields[0].2, &[0x08, 0xac, 0x02]);
+        assert_eq!(fields[1].0, 2);
+        assert_eq!(fields[1].1.as_str().unwrap(), "abc");
+        assert!(fields[1].1.as_varint().is_none());
+        assert_eq!(fields[2].0, 16);
+        assert_eq!(fields[2].1.as_varint(), Some(u64::MAX));
+        assert!(matches!(fields[3].1, WireValue::Fixed32(1)));
+        assert!(matches!(fields[4].1, WireValue::Fixed64(2)));
+        assert!(fields[4].1.as_bytes().is_none());
+
+        // truncated message
+        let mut reader = ProtoReader::new(&buf[..buf.len() - 1]);
+        assert_eq!(reader.by_ref().filter(|f| f.is_err()).count(), 1);
+        assert!(reader.next().is_none());
+        assert!(ProtoReader::new(&[0x12, 0x05, 0x01])
+            .next()
+            .unwrap()
+            .is_err());
+        assert!(ProtoReader::new(&[0x0b]).next().unwrap().is_err());
+    }
+}
@@ -13,3 +13,10 @@ mod image_segmenter_metadata_schema_generated;
 pub(super) use image_segmenter_metadata_schema_generated::mediapipe::tasks as custom_img_segmentation;
 #[cfg(feature = "vision")]
 pub(super) const CUSTOM_SEGMENTATION_METADATA_NAME: &'static str = "SEGMENTER_METADATA";
+
+#[cfg(feature = "vision")]
+mod object_detector_metadata_schema_generated;
+#[cfg(feature = "vision")]
+pub(super) use object_detector_metadata_schema_generated::mediapipe::tasks as custom_object_detector;
+#[cfg(feature = "vision")]
+pub(super) const CUSTOM_DETECTOR_METADATA_NAME: &'static str = "DETECTOR_METADATA";
@@ -0,0 +1,1599 @@
+use super::generated::{tflite as tflite_model, tflite_metadata};
+use super::TfLiteModelResource;
+use crate::model::zip::append_stored_zip_files;
+#[cfg(feature = "vision")]
+use crate::preprocess::vision::ImageColorSpaceType;
+use crate::Error;
+use flatbuffers::{FlatBufferBuilder, WIPOffset};
+use std::path::Path;
+
+/// Metadata of a model input tensor, such as image properties, normalization options, audio properties, etc.
+#[derive(Debug, Clone, Default)]
+pub struct InputTensorMetadata {
+    name: Option<String>,
+    description: Option<String>,
+    #[cfg(feature = "vision")]
+    image_color_space: Option<ImageColorSpaceType>,
+    // (mean, std)
+    normalization_options: Option<(Vec<f32>, Vec<f32>)>,
+    // (min, max)
+    stats: Option<(Vec<f32>, Vec<f32>)>,
+    // (sample rate, channels)
+    audio_properties: Option<(u32, u32)>,
+}
+
+impl InputTensorMetadata {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the tensor name.
+    #[inline(always)]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the tensor description.
+    #[inline(always)]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Mark the tensor as an image tensor (`NHWC` layout) with the color space.
+    #[cfg(feature = "vision")]
+    #[inline(always)]
+    pub fn image_properties(mut self, color_space: ImageColorSpaceType) -> Self {
+        self.image_color_space = Some(color_space);
+        self
+    }
+
+    /// Set the normalization options: `(input - mean) / std`. The length can be 1 or the number of channels.
+    #[inline(always)]
+    pub fn normalization_options(mut self, mean: Vec<f32>, std: Vec<f32>) -> Self {
+        self.normalization_options = Some((mean, std));
+        self
+    }
+
+    /// Set the min and max values of the tensor. The length can be 1 or the number of channels.
+    #[inline(always)]
+    pub fn stats(mut self, min: Vec<f32>, max: Vec<f32>) -> Self {
+        self.stats = Some((min, max));
+        self
+    }
+
+    /// Mark the tensor as an audio tensor with sample rate and number of channels.
+    #[inline(always)]
+    pub fn audio_properties(mut self, sample_rate: u32, channels: u32) -> Self {
+        self.audio_properties = Some((sample_rate, channels));
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LabelFile {
+    file_name: String,
+    locale: Option<String>,
+    content: Vec<u8>,
+}
+
+/// Metadata of a model output tensor, such as labels, score threshold, bounding box properties, etc.
+#[derive(Debug, Clone, Default)]
+pub struct OutputTensorMetadata {
+    name: Option<String>,
+    description: Option<String>,
+    labels: Vec<LabelFile>,
+    score_threshold: Option<f32>,
+    bounding_box_properties: Option<[usize; 4]>,
+}
+
+impl OutputTensorMetadata {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the tensor name. Some tasks use the name to find output tensors, such as `location`, `category`, `score`
+    /// and `number of detections` in object detection models.
+    #[inline(always)]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the tensor description.
+    #[inline(always)]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the labels, one label per line will be written to the associated file `file_name`.
+    #[inline(always)]
+    pub fn labels(
+        self,
+        file_name: impl Into<String>,
+        labels: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Self {
+        self.add_label_file(file_name.into(), None, labels)
+    }
+
+    /// Set the display names for the `locale`, such as `en`, `zh`.
+    /// The display names must be in the same order as labels.
+    #[inline(always)]
+    pub fn display_names(
+        self,
+        file_name: impl Into<String>,
+        locale: impl Into<String>,
+        display_names: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Self {
+        self.add_label_file(file_name.into(), Some(locale.into()), display_names)
+    }
+
+    /// Set the default score threshold.
+    #[inline(always)]
+    pub fn score_threshold(mut self, score_threshold: f32) -> Self {
+        self.score_threshold = Some(score_threshold);
+        self
+    }
+
+    /// Set the bounding box properties: the index of `left`, `top`, `right`, `bottom` in the last dimension.
+    /// The coordinates are ratios of image size.
+    #[inline(always)]
+    pub fn bounding_box_properties(mut self, index: [usize; 4]) -> Self {
+        self.bounding_box_properties = Some(index);
+        self
+    }
+
+    #[inline(always)]
+    fn add_label_file(
+        mut self,
+        file_name: String,
+        locale: Option<String>,
+        labels: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Self {
+        self.labels.push(LabelFile {
+            file_name,
+            locale,
+            content: lines_to_file(labels),
+        });
+        self
+    }
+}
+
+/// Anchors and decoding options of SSD detection models, which are written as MediaPipe `DETECTOR_METADATA`.
+/// Detection tasks use them instead of the builtin anchors and options.
+#[cfg(feature = "vision")]
+#[derive(Debug, Clone, Default)]
+pub struct DetectorMetadata {
+    // [x_center, y_center, width, height]
+    anchors: Vec<[f32; 4]>,
+    decoding_options: Option<crate::postprocess::SsdDecodingOptions>,
+}
+
+#[cfg(feature = "vision")]
+impl DetectorMetadata {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the fixed anchors: `[x_center, y_center, width, height]`, the number must be equal to the number of boxes.
+    #[inline(always)]
+    pub fn fixed_anchors(mut self, anchors: impl IntoIterator<Item = [f32; 4]>) -> Self {
+        self.anchors = anchors.into_iter().collect();
+        self
+    }
+
+    /// Set the number of classes, boxes and values per box predicted by the model.
+    #[inline(always)]
+    pub fn tensors_decoding(mut self, num_classes: u32, num_boxes: u32, num_coords: u32) -> Self {
+        let d = self.decoding_options.get_or_insert_with(Default::default);
+        d.num_classes = num_classes as usize;
+        d.num_boxes = num_boxes as usize;
+        d.num_coords = num_coords as usize;
+        self
+    }
+
+    /// Set the key points in the location tensor.
+    #[inline(always)]
+    pub fn key_points(
+        mut self,
+        keypoint_coord_offset: u32,
+        num_key_points: u32,
+        num_values_per_key_point: u32,
+    ) -> Self {
+        let d = self.decoding_options.get_or_insert_with(Default::default);
+        d.keypoint_coord_offset = keypoint_coord_offset as usize;
+        d.num_key_points = num_key_points as usize;
+        d.num_values_per_key_point = num_values_per_key_point as usize;
+        self
+    }
+
+    /// Set the scales to decode boxes, which are usually the input image size.
+    #[inline(always)]
+    pub fn scales(mut self, x_scale: f32, y_scale: f32, w_scale: f32, h_scale: f32) -> Self {
+        let d = self.decoding_options.get_or_insert_with(Default::default);
+        d.x_scale = x_scale;
+        d.y_scale = y_scale;
+        d.w_scale = w_scale;
+        d.h_scale = h_scale;
+        self
+    }
+
+    /// Set whether to apply exponential on box size.
+    #[inline(always)]
+    pub fn apply_exponential_on_box_size(mut self, apply_exponential_on_box_size: bool) -> Self {
+        self.decoding_options
+            .get_or_insert_with(Default::default)
+            .apply_exponential_on_box_size = apply_exponential_on_box_size;
+        self
+    }
+
+    /// Set whether to apply sigmoid function on the scores.
+    #[inline(always)]
+    pub fn sigmoid_score(mut self, sigmoid_score: bool) -> Self {
+        self.decoding_options
+            .get_or_insert_with(Default::default)
+            .sigmoid_score = sigmoid_score;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+enum TokenizerOptions {
+    Bert {
+        vocab_file_name: String,
+    },
+    Regex {
+        delim_regex_pattern: String,
+        vocab_file_name: String,
+    },
+}
+
+/// Write metadata and associated files into a TFLite model which has no metadata,
+/// so that the model can be used in tasks with essential information.
+/// If the model already has metadata, the metadata will be replaced.
+///
+/// ```rust
+/// use mediapipe_rs::model::metadata_writer::{
+///     InputTensorMetadata, MetadataWriter, OutputTensorMetadata,
+/// };
+/// use mediapipe_rs::preprocess::vision::ImageColorSpaceType;
+///
+/// let model = MetadataWriter::from_path("model_without_metadata.tflite")?
+///     .name("image classifier")
+///     .input_tensor(
+///         0,
+///         InputTensorMetadata::new()
+///             .image_properties(ImageColorSpaceType::RGB)
+///             .normalization_options(vec![127.5], vec![127.5]),
+///     )
+///     .output_tensor(
+///         0,
+///         OutputTensorMetadata::new()
+///             .labels("labels.txt", ["cat", "dog"])
+///             .score_threshold(0.3),
+///     )
+///     .finalize()?;
+/// std::fs::write("model.tflite", model)?;
+/// ```
+pub struct MetadataWriter {
+    model_buf: Vec<u8>,
+    name: Option<String>,
+    description: Option<String>,
+    version: Option<String>,
+    author: Option<String>,
+    license: Option<String>,
+    inputs: Vec<(usize, InputTensorMetadata)>,
+    outputs: Vec<(usize, OutputTensorMetadata)>,
+    tokenizer: Option<TokenizerOptions>,
+    #[cfg(feature = "vision")]
+    detector_metadata: Option<DetectorMetadata>,
+    // files which are referenced by model metadata or tokenizer
+    associated_files: Vec<(String, Vec<u8>)>,
+    model_associated_files: Vec<String>,
+}
+
+impl MetadataWriter {
+    /// Create a metadata writer for the TFLite model buffer.
+    #[inline(always)]
+    pub fn new(model_buf: Vec<u8>) -> Self {
+        Self {
+            model_buf,
+            name: None,
+            description: None,
+            version: None,
+            author: None,
+            license: None,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            tokenizer: None,
+            #[cfg(feature = "vision")]
+            detector_metadata: None,
+            associated_files: Vec::new(),
+            model_associated_files: Vec::new(),
+        }
+    }
+
+    /// Create a metadata writer for the TFLite model file.
+    #[inline(always)]
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Ok(Self::new(std::fs::read(path)?))
+    }
+
+    /// Set the model name.
+    #[inline(always)]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the model description.
+    #[inline(always)]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the model version.
+    #[inline(always)]
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Set the model author.
+    #[inline(always)]
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Set the model license.
+    #[inline(always)]
+    pub fn license(mut self, license: impl Into<String>) -> Self {
+        self.license = Some(license.into());
+        self
+    }
+
+    /// Set the metadata for input tensor at `index`.
+    #[inline(always)]
+    pub fn input_tensor(mut self, index: usize, metadata: InputTensorMetadata) -> Self {
+        self.inputs.retain(|(i, _)| *i != index);
+        self.inputs.push((index, metadata));
+        self
+    }
+
+    /// Set the metadata for output tensor at `index`.
+    #[inline(always)]
+    pub fn output_tensor(mut self, index: usize, metadata: OutputTensorMetadata) -> Self {
+        self.outputs.retain(|(i, _)| *i != index);
+        self.outputs.push((index, metadata));
+        self
+    }
+
+    /// Set the bert tokenizer for the text model, which has 3 input tensors (ids, mask, segment ids).
+    /// The vocab tokens will be written to the associated file `vocab_file_name`, one token per line.
+    #[inline(always)]
+    pub fn bert_tokenizer(
+        mut self,
+        vocab_file_name: impl Into<String>,
+        vocab: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Self {
+        let vocab_file_name = vocab_file_name.into();
+        self.associated_files
+            .push((vocab_file_name.clone(), lines_to_file(vocab)));
+        self.tokenizer = Some(TokenizerOptions::Bert { vocab_file_name });
+        self
+    }
+
+    /// Set the regex tokenizer for the text model, which has 1 input tensor.
+    /// The vocab `(token, index)` pairs will be written to the associated file `vocab_file_name`.
+    #[inline(always)]
+    pub fn regex_tokenizer<S: AsRef<str>>(
+        mut self,
+        delim_regex_pattern: impl Into<String>,
+        vocab_file_name: impl Into<String>,
+        vocab: impl IntoIterator<Item = (S, i32)>,
+    ) -> Self {
+        let vocab_file_name = vocab_file_name.into();
+        let lines = vocab
+            .into_iter()
+            .map(|(token, index)| format!("{} {}", token.as_ref(), index));
+        self.associated_files
+            .push((vocab_file_name.clone(), lines_to_file(lines)));
+        self.tokenizer = Some(TokenizerOptions::Regex {
+            delim_regex_pattern: delim_regex_pattern.into(),
+            vocab_file_name,
+        });
+        self
+    }
+
+    /// Set the anchors and decoding options for SSD detection models.
+    #[cfg(feature = "vision")]
+    #[inline(always)]
+    pub fn detector_metadata(mut self, detector_metadata: DetectorMetadata) -> Self {
+        self.detector_metadata = Some(detector_metadata);
+        self
+    }
+
+    /// Add a file to the model, such as a description file.
+    #[inline(always)]
+    pub fn associated_file(mut self, file_name: impl Into<String>, content: Vec<u8>) -> Self {
+        let file_name = file_name.into();
+        self.associated_files.push((file_name.clone(), content));
+        self.model_associated_files.push(file_name);
+        self
+    }
+
+    /// Write the metadata and associated files, return the new model buffer.
+    pub fn finalize(self) -> Result<Vec<u8>, Error> {
+        let (input_count, output_count) = {
+            let model = tflite_model::root_as_model(self.model_buf.as_slice())?;
+            let subgraph = match model.subgraphs() {
+                Some(s) if !s.is_empty() => s.get(0),
+                _ => {
+                    return Err(Error::ModelParseError("Model subgraph is empty".into()));
+                }
+            };
+            (
+                subgraph.inputs().map(|i| i.len()).unwrap_or(0),
+                subgraph.outputs().map(|o| o.len()).unwrap_or(0),
+            )
+        };
+        for (index, _) in self.inputs.iter() {
+            if *index >= input_count {
+                return Err(Error::ArgumentError(format!(
+                    "Input tensor index `{}` out of range, model has `{}` inputs",
+                    index, input_count
+                )));
+            }
+        }
+        for (index, _) in self.outputs.iter() {
+            if *index >= output_count {
+                return Err(Error::ArgumentError(format!(
+                    "Output tensor index `{}` out of range, model has `{}` outputs",
+                    index, output_count
+                )));
+            }
+        }
+
+        let mut files: Vec<(&str, &[u8])> = Vec::new();
+        let all_files = self.associated_files.iter().map(|(n, c)| (n, c)).chain(
+            self.outputs
+                .iter()
+                .flat_map(|(_, o)| o.labels.iter().map(|l| (&l.file_name, &l.content))),
+        );
+        for (name, content) in all_files {
+            match files.iter().find(|(n, _)| *n == name.as_str()) {
+                Some((_, c)) if *c != content.as_slice() => {
+                    return Err(Error::ArgumentError(format!(
+                        "Associated file `{}` has different contents",
+                        name
+                    )));
+                }
+                Some(_) => {}
+                None => files.push((name.as_str(), content.as_slice())),
+            }
+        }
+
+        let metadata = self.build_model_metadata(input_count, output_count)?;
+        let mut res =
+            Self::append_metadata_to_model(self.model_buf.as_slice(), metadata.as_slice())?;
+        if !files.is_empty() {
+            append_stored_zip_files(&mut res, files.as_slice())?;
+        }
+
+        // check the result
+        TfLiteModelResource::new(res.as_slice())?;
+        Ok(res)
+    }
+
+    fn build_model_metadata(
+        &self,
+        input_count: usize,
+        output_count: usize,
+    ) -> Result<Vec<u8>, Error> {
+        let mut fbb = FlatBufferBuilder::new();
+
+        let mut inputs = Vec::with_capacity(input_count);
+        for i in 0..input_count {
+            let m = self
+                .inputs
+                .iter()
+                .find(|(index, _)| *index == i)
+                .map(|(_, m)| m);
+            let regex_tokenizer = if i == 0 {
+                match self.tokenizer {
+                    Some(TokenizerOptions::Regex {
+                        ref delim_regex_pattern,
+                        ref vocab_file_name,
+                    }) => Some((delim_regex_pattern, vocab_file_name)),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+            inputs.push(Self::build_input_tensor_metadata(
+                &mut fbb,
+                m,
+                regex_tokenizer,
+            )?);
+        }
+        let inputs = fbb.create_vector(inputs.as_slice());
+
+        let mut outputs = Vec::with_capacity(output_count);
+        for i in 0..output_count {
+            let m = self
+                .outputs
+                .iter()
+                .find(|(index, _)| *index == i)
+                .map(|(_, m)| m);
+            outputs.push(Self::build_output_tensor_metadata(&mut fbb, m));
+        }
+        let outputs = fbb.create_vector(outputs.as_slice());
+
+        let input_process_units = match self.tokenizer {
+            Some(TokenizerOptions::Bert {
+                ref vocab_file_name,
+            }) => {
+                let vocab_file = Self::build_associated_file(
+                    &mut fbb,
+                    vocab_file_name,
+                    tflite_metadata::AssociatedFileType::VOCABULARY,
+                    None,
+                );
+                let vocab_file = fbb.create_vector(&[vocab_file]);
+                let options = tflite_metadata::BertTokenizerOptions::create(
+                    &mut fbb,
+                    &tflite_metadata::BertTokenizerOptionsArgs {
+                        vocab_file: Some(vocab_file),
+                    },
+                );
+                let process_unit = tflite_metadata::ProcessUnit::create(
+                    &mut fbb,
+                    &tflite_metadata::ProcessUnitArgs {
+                        options_type: tflite_metadata::ProcessUnitOptions::BertTokenizerOptions,
+                        options: Some(options.as_union_value()),
+                    },
+                );
+                Some(fbb.create_vector(&[process_unit]))
+            }
+            _ => None,
+        };
+
+        #[cfg(feature = "vision")]
+        let custom_metadata = match self.detector_metadata {
+            Some(ref d) => {
+                let data = Self::build_detector_metadata(d);
+                let data = fbb.create_vector(data.as_slice());
+                let name = fbb.create_string(super::generated::CUSTOM_DETECTOR_METADATA_NAME);
+                let m = tflite_metadata::CustomMetadata::create(
+                    &mut fbb,
+                    &tflite_metadata::CustomMetadataArgs {
+                        name: Some(name),
+                        data: Some(data),
+                    },
+                );
+                Some(fbb.create_vector(&[m]))
+            }
+            None => None,
+        };
+        #[cfg(not(feature = "vision"))]
+        let custom_metadata = None;
+
+        let subgraph = tflite_metadata::SubGraphMetadata::create(
+            &mut fbb,
+            &tflite_metadata::SubGraphMetadataArgs {
+                input_tensor_metadata: Some(inputs),
+                output_tensor_metadata: Some(outputs),
+                input_process_units,
+                custom_metadata,
+                ..Default::default()
+            },
+        );
+        let subgraph = fbb.create_vector(&[subgraph]);
+
+        let associated_files = if self.model_associated_files.is_empty() {
+            None
+        } else {
+            let mut files = Vec::with_capacity(self.model_associated_files.len());
+            for name in self.model_associated_files.iter() {
+                files.push(Self::build_associated_file(
+                    &mut fbb,
+                    name,
+                    tflite_metadata::AssociatedFileType::UNKNOWN,
+                    None,
+                ));
+            }
+            Some(fbb.create_vector(files.as_slice()))
+        };
+        let name = self.name.as_ref().map(|s| fbb.create_string(s));
+        let description = self.description.as_ref().map(|s| fbb.create_string(s));
+        let version = self.version.as_ref().map(|s| fbb.create_string(s));
+        let author = self.author.as_ref().map(|s| fbb.create_string(s));
+        let license = self.license.as_ref().map(|s| fbb.create_string(s));
+
+        let metadata = tflite_metadata::ModelMetadata::create(
+            &mut fbb,
+            &tflite_metadata::ModelMetadataArgs {
+                name,
+                description,
+                version,
+                subgraph_metadata: Some(subgraph),
+                author,
+                license,
+                associated_files,
+                min_parser_version: None,
+            },
+        );
+        tflite_metadata::finish_model_metadata_buffer(&mut fbb, metadata);
+        Ok(fbb.finished_data().to_vec())
+    }
+
+    #[cfg(feature = "vision")]
+    fn build_detector_metadata(detector_metadata: &DetectorMetadata) -> Vec<u8> {
+        use super::generated::custom_object_detector as od;
+
+        let mut fbb = FlatBufferBuilder::new();
+        let ssd_anchors_options = if detector_metadata.anchors.is_empty() {
+            None
+        } else {
+            let anchors: Vec<_> = detector_metadata
+                .anchors
+                .iter()
+                .map(|a| {
+                    od::FixedAnchor::create(
+                        &mut fbb,
+                        &od::FixedAnchorArgs {
+                            x_center: a[0],
+                            y_center: a[1],
+                            width: a[2],
+                            height: a[3],
+                        },
+                    )
+                })
+                .collect();
+            let anchors = fbb.create_vector(anchors.as_slice());
+            let schema = od::FixedAnchorsSchema::create(
+                &mut fbb,
+                &od::FixedAnchorsSchemaArgs {
+                    anchors: Some(anchors),
+                },
+            );
+            Some(od::SsdAnchorsOptions::create(
+                &mut fbb,
+                &od::SsdAnchorsOptionsArgs {
+                    fixed_anchors_schema: Some(schema),
+                },
+            ))
+        };
+        let tensors_decoding_options = detector_metadata.decoding_options.as_ref().map(|d| {
+            od::TensorsDecodingOptions::create(
+                &mut fbb,
+                &od::TensorsDecodingOptionsArgs {
+                    num_classes: d.num_classes as i32,
+                    num_boxes: d.num_boxes as i32,
+                    num_coords: d.num_coords as i32,
+                    keypoint_coord_offset: d.keypoint_coord_offset as i32,
+                    num_keypoints: d.num_key_points as i32,
+                    num_values_per_keypoint: d.num_values_per_key_point as i32,
+                    x_scale: d.x_scale,
+                    y_scale: d.y_scale,
+                    w_scale: d.w_scale,
+                    h_scale: d.h_scale,
+                    apply_exponential_on_box_size: d.apply_exponential_on_box_size,
+                    sigmoid_score: d.sigmoid_score,
+                },
+            )
+        });
+        let options = od::ObjectDetectorOptions::create(
+            &mut fbb,
+            &od::ObjectDetectorOptionsArgs {
+                min_parser_version: None,
+                ssd_anchors_options,
+                tensors_decoding_options,
+            },
+        );
+        od::finish_object_detector_options_buffer(&mut fbb, options);
+        fbb.finished_data().to_vec()
+    }
+
+    fn build_input_tensor_metadata<'a>(
+        fbb: &mut FlatBufferBuilder<'a>,
+        metadata: Option<&InputTensorMetadata>,
+        regex_tokenizer: Option<(&String, &String)>,
+    ) -> Result<WIPOffset<tflite_metadata::TensorMetadata<'a>>, Error> {
+        let mut process_units = Vec::new();
+        if let Some((delim_regex_pattern, vocab_file_name)) = regex_tokenizer {
+            let vocab_file = Self::build_associated_file(
+                fbb,
+                vocab_file_name,
+                tflite_metadata::AssociatedFileType::VOCABULARY,
+                None,
+            );
+            let vocab_file = fbb.create_vector(&[vocab_file]);
+            let delim_regex_pattern = fbb.create_string(delim_regex_pattern);
+            let options = tflite_metadata::RegexTokenizerOptions::create(
+                fbb,
+                &tflite_metadata::RegexTokenizerOptionsArgs {
+                    delim_regex_pattern: Some(delim_regex_pattern),
+                    vocab_file: Some(vocab_file),
+                },
+            );
+            process_units.push(tflite_metadata::ProcessUnit::create(
+                fbb,
+                &tflite_metadata::ProcessUnitArgs {
+                    options_type: tflite_metadata::ProcessUnitOptions::RegexTokenizerOptions,
+                    options: Some(options.as_union_value()),
+                },
+            ));
+        }
+
+        let metadata = match metadata {
+            Some(m) => m,
+            None => {
+                let process_units = if process_units.is_empty() {
+                    None
+                } else {
+                    Some(fbb.create_vector(process_units.as_slice()))
+                };
+                return Ok(tflite_metadata::TensorMetadata::create(
+                    fbb,
+                    &tflite_metadata::TensorMetadataArgs {
+                        process_units,
+                        ..Default::default()
+                    },
+                ));
+            }
+        };
+
+        if let Some((ref mean, ref std)) = metadata.normalization_options {
+            if mean.is_empty() || mean.len() != std.len() {
+                return Err(Error::ArgumentError(format!(
+                    "Normalization mean length `{}` and std length `{}` must be equal and non-zero",
+                    mean.len(),
+                    std.len()
+                )));
+            }
+            let mean = fbb.create_vector(mean.as_slice());
+            let std = fbb.create_vector(std.as_slice());
+            let options = tflite_metadata::NormalizationOptions::create(
+                fbb,
+                &tflite_metadata::NormalizationOptionsArgs {
+                    mean: Some(mean),
+                    std_: Some(std),
+                },
+            );
+            process_units.push(tflite_metadata::ProcessUnit::create(
+                fbb,
+                &tflite_metadata::ProcessUnitArgs {
+                    options_type: tflite_metadata::ProcessUnitOptions::NormalizationOptions,
+                    options: Some(options.as_union_value()),
+                },
+            ));
+        }
+        let process_units = if process_units.is_empty() {
+            None
+        } else {
+            Some(fbb.create_vector(process_units.as_slice()))
+        };
+
+        let stats = match metadata.stats {
+            Some((ref min, ref max)) => {
+                let min = fbb.create_vector(min.as_slice());
+                let max = fbb.create_vector(max.as_slice());
+                Some(tflite_metadata::Stats::create(
+                    fbb,
+                    &tflite_metadata::StatsArgs {
+                        max: Some(max),
+                        min: Some(min),
+                    },
+                ))
+            }
+            None => None,
+        };
+
+        let mut content = None;
+        #[cfg(feature = "vision")]
+        if let Some(color_space) = metadata.image_color_space {
+            let color_space = match color_space {
+                ImageColorSpaceType::RGB => tflite_metadata::ColorSpaceType::RGB,
+                ImageColorSpaceType::GRAYSCALE => tflite_metadata::ColorSpaceType::GRAYSCALE,
+                ImageColorSpaceType::UNKNOWN => tflite_metadata::ColorSpaceType::UNKNOWN,
+            };
+            let props = tflite_metadata::ImageProperties::create(
+                fbb,
+                &tflite_metadata::ImagePropertiesArgs {
+                    color_space,
+                    default_size: None,
+                },
+            );
+            content = Some((
+                tflite_metadata::ContentProperties::ImageProperties,
+                props.as_union_value(),
+            ));
+        }
+        if let Some((sample_rate, channels)) = metadata.audio_properties {
+            if content.is_some() {
+                return Err(Error::ArgumentError(
+                    "Input tensor cannot be both image and audio".into(),
+                ));
+            }
+            let props = tflite_metadata::AudioProperties::create(
+                fbb,
+                &tflite_metadata::AudioPropertiesArgs {
+                    sample_rate,
+                    channels,
+                },
+            );
+            content = Some((
+                tflite_metadata::ContentProperties::AudioProperties,
+                props.as_union_value(),
+            ));
+        }
+        let content = content.map(|(content_properties_type, props)| {
+            tflite_metadata::Content::create(
+                fbb,
+                &tflite_metadata::ContentArgs {
+                    content_properties_type,
+                    content_properties: Some(props),
+                    range: None,
+                },
+            )
+        });
+
+        let name = metadata.name.as_ref().map(|s| fbb.create_string(s));
+        let description = metadata.description.as_ref().map(|s| fbb.create_string(s));
+        Ok(tflite_metadata::TensorMetadata::create(
+            fbb,
+            &tflite_metadata::TensorMetadataArgs {
+                name,
+                description,
+                content,
+                process_units,
+                stats,
+                ..Default::default()
+            },
+        ))
+    }
+
+    fn build_output_tensor_metadata<'a>(
+        fbb: &mut FlatBufferBuilder<'a>,
+        metadata: Option<&OutputTensorMetadata>,
+    ) -> WIPOffset<tflite_metadata::TensorMetadata<'a>> {
+        let metadata = match metadata {
+            Some(m) => m,
+            None => {
+                return tflite_metadata::TensorMetadata::create(fbb, &Default::default());
+            }
+        };
+
+        let associated_files = if metadata.labels.is_empty() {
+            None
+        } else {
+            let mut files = Vec::with_capacity(metadata.labels.len());
+            for l in metadata.labels.iter() {
+                files.push(Self::build_associated_file(
+                    fbb,
+                    &l.file_name,
+                    tflite_metadata::AssociatedFileType::TENSOR_AXIS_LABELS,
+                    l.locale.as_ref(),
+                ));
+            }
+            Some(fbb.create_vector(files.as_slice()))
+        };
+
+        let process_units = metadata.score_threshold.map(|global_score_threshold| {
+            let options = tflite_metadata::ScoreThresholdingOptions::create(
+                fbb,
+                &tflite_metadata::ScoreThresholdingOptionsArgs {
+                    global_score_threshold,
+                },
+            );
+            let process_unit = tflite_metadata::ProcessUnit::create(
+                fbb,
+                &tflite_metadata::ProcessUnitArgs {
+                    options_type: tflite_metadata::ProcessUnitOptions::ScoreThresholdingOptions,
+                    options: Some(options.as_union_value()),
+                },
+            );
+            fbb.create_vector(&[process_unit])
+        });
+
+        let content = metadata.bounding_box_properties.map(|index| {
+            let index = fbb.create_vector(&index.map(|i| i as u32));
+            let props = tflite_metadata::BoundingBoxProperties::create(
+                fbb,
+                &tflite_metadata::BoundingBoxPropertiesArgs {
+                    index: Some(index),
+                    type_: tflite_metadata::BoundingBoxType::BOUNDARIES,
+                    coordinate_type: tflite_metadata::CoordinateType::RATIO,
+                },
+            );
+            tflite_metadata::Content::create(
+                fbb,
+                &tflite_metadata::ContentArgs {
+                    content_properties_type:
+                        tflite_metadata::ContentProperties::BoundingBoxProperties,
+                    content_properties: Some(props.as_union_value()),
+                    range: None,
+                },
+            )
+        });
+
+        let name = metadata.name.as_ref().map(|s| fbb.create_string(s));
+        let description = metadata.description.as_ref().map(|s| fbb.create_string(s));
+        tflite_metadata::TensorMetadata::create(
+            fbb,
+            &tflite_metadata::TensorMetadataArgs {
+                name,
+                description,
+                content,
+                process_units,
+                associated_files,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[inline(always)]
+    fn build_associated_file<'a>(
+        fbb: &mut FlatBufferBuilder<'a>,
+        name: &str,
+        type_: tflite_metadata::AssociatedFileType,
+        locale: Option<&String>,
+    ) -> WIPOffset<tflite_metadata::AssociatedFile<'a>> {
+        let name = fbb.create_string(name);
+        let locale = locale.map(|l| fbb.create_string(l));
+        tflite_metadata::AssociatedFile::create(
+            fbb,
+            &tflite_metadata::AssociatedFileArgs {
+                name: Some(name),
+                type_,
+                locale,
+                ..Default::default()
+            },
+        )
+    }
+
+    /*
+      FlatBuffers offsets always point forward, so a new root ``Model`` table is written before the original model,
+      and it references the original objects (subgraphs, operator codes, buffers, ...) directly.
+
+      [root offset]["TFL3"][Model vtable][Model table][buffers][metadata][new Buffer][new Metadata][name][data]
+      [padding][original model]
+    */
+    fn append_metadata_to_model(model_buf: &[u8], metadata: &[u8]) -> Result<Vec<u8>, Error> {
+        // Model table fields, see ``tflite_model::Model``
+        const FIELD_NUM: usize = 8;
+        const VERSION_FIELD: usize = 0;
+        const BUFFERS_FIELD: usize = 4;
+        const METADATA_FIELD: usize = 6;
+
+        const MODEL_VTABLE_POS: usize = 8;
+        const MODEL_VTABLE_SIZE: usize = 4 + 2 * FIELD_NUM;
+        const MODEL_TABLE_POS: usize = MODEL_VTABLE_POS + MODEL_VTABLE_SIZE;
+        const MODEL_TABLE_SIZE: usize = 4 + 4 * FIELD_NUM;
+        const BUFFERS_POS: usize = MODEL_TABLE_POS + MODEL_TABLE_SIZE;
+        // the original model must keep the alignment
+        const ALIGNMENT: usize = 16;
+
+        let model = tflite_model::root_as_model(model_buf)?;
+
+        macro_rules! read_u32 {
+            ( $pos:expr ) => {{
+                let pos = $pos;
+                u32::from_le_bytes([
+                    model_buf[pos],
+                    model_buf[pos + 1],
+                    model_buf[pos + 2],
+                    model_buf[pos + 3],
+                ]) as usize
+            }};
+        }
+        macro_rules! write_u32 {
+            ( $buf:expr, $pos:expr, $val:expr ) => {{
+                let pos = $pos;
+                $buf[pos..pos + 4].copy_from_slice(&($val as u32).to_le_bytes());
+            }};
+        }
+        macro_rules! write_u16 {
+            ( $buf:expr, $pos:expr, $val:expr ) => {{
+                let pos = $pos;
+                $buf[pos..pos + 2].copy_from_slice(&($val as u16).to_le_bytes());
+            }};
+        }
+
+        // the buffer has been verified, so the root table and vtable are valid.
+        let table_pos = read_u32!(0);
+        let vtable_pos = (table_pos as i64
+            - i32::from_le_bytes([
+                model_buf[table_pos],
+                model_buf[table_pos + 1],
+                model_buf[table_pos + 2],
+                model_buf[table_pos + 3],
+            ]) as i64) as usize;
+        let vtable_field_num =
+            (u16::from_le_bytes([model_buf[vtable_pos], model_buf[vtable_pos + 1]]) as usize - 4)
+                / 2;
+        let field_pos = |i: usize| -> Option<usize> {
+            if i >= vtable_field_num {
+                return None;
+            }
+            let p = vtable_pos + 4 + 2 * i;
+            match u16::from_le_bytes([model_buf[p], model_buf[p + 1]]) {
+                0 => None,
+                o => Some(table_pos + o as usize),
+            }
+        };
+        for i in FIELD_NUM..vtable_field_num {
+            if field_pos(i).is_some() {
+                return Err(Error::ModelParseError(
+                    "Unsupported model: unknown fields in model table".into(),
+                ));
+            }
+        }
+
+        // the absolute positions of original buffer tables and metadata tables
+        let vector_elements = |field: usize| -> Vec<usize> {
+            match field_pos(field) {
+                Some(p) => {
+                    let vector_pos = p + read_u32!(p);
+                    let len = read_u32!(vector_pos);
+                    (0..len)
+                        .map(|i| {
+                            let elem_pos = vector_pos + 4 + 4 * i;
+                            elem_pos + read_u32!(elem_pos)
+                        })
+                        .collect()
+                }
+                None => Vec::new(),
+            }
+        };
+        let buffers = vector_elements(BUFFERS_FIELD);
+        let mut metadata_tables = vector_elements(METADATA_FIELD);
+        if let Some(m) = model.metadata() {
+            // remove the old model metadata
+            let mut i = 0;
+            metadata_tables.retain(|_| {
+                let keep = m.get(i).name() != Some(TfLiteModelResource::METADATA_NAME);
+                i += 1;
+                keep
+            });
+        }
+
+        // compute the layout
+        let metadata_vector_pos = BUFFERS_POS + 4 + 4 * (buffers.len() + 1);
+        let buffer_vtable_pos = metadata_vector_pos + 4 + 4 * (metadata_tables.len() + 1);
+        let buffer_table_pos = buffer_vtable_pos + 8;
+        let metadata_vtable_pos = buffer_table_pos + 8;
+        let metadata_table_pos = metadata_vtable_pos + 8;
+        let name_pos = metadata_table_pos + 12;
+        let name = TfLiteModelResource::METADATA_NAME.as_bytes();
+        let data_pos = {
+            // string: length + bytes + '\0'
+            let end = name_pos + 4 + name.len() + 1;
+            // data bytes are aligned
+            (end + 4).div_ceil(ALIGNMENT) * ALIGNMENT - 4
+        };
+        let original_pos = (data_pos + 4 + metadata.len()).div_ceil(ALIGNMENT) * ALIGNMENT;
+
+        let mut res = vec![0u8; original_pos];
+        res.reserve(model_buf.len());
+
+        // root offset and file identifier
+        write_u32!(res, 0, MODEL_TABLE_POS);
+        res[4..8].copy_from_slice(TfLiteModelResource::HEAD_MAGIC);
+
+        // model vtable and table
+        write_u16!(res, MODEL_VTABLE_POS, MODEL_VTABLE_SIZE);
+        write_u16!(res, MODEL_VTABLE_POS + 2, MODEL_TABLE_SIZE);
+        write_u32!(res, MODEL_TABLE_POS, MODEL_TABLE_POS - MODEL_VTABLE_POS);
+        for i in 0..FIELD_NUM {
+            let new_field_pos = MODEL_TABLE_POS + 4 + 4 * i;
+            let val = match i {
+                VERSION_FIELD => field_pos(i).map(|p| read_u32!(p)),
+                BUFFERS_FIELD => Some(BUFFERS_POS - new_field_pos),
+                METADATA_FIELD => Some(metadata_vector_pos - new_field_pos),
+                _ => field_pos(i).map(|p| original_pos + p + read_u32!(p) - new_field_pos),
+            };
+            if let Some(val) = val {
+                write_u16!(
+                    res,
+                    MODEL_VTABLE_POS + 4 + 2 * i,
+                    new_field_pos - MODEL_TABLE_POS
+                );
+                write_u32!(res, new_field_pos, val);
+            }
+        }
+
+        // buffers vector, the last one is metadata buffer
+        write_u32!(res, BUFFERS_POS, buffers.len() + 1);
+        for (i, b) in buffers.iter().enumerate() {
+            let elem_pos = BUFFERS_POS + 4 + 4 * i;
+            write_u32!(res, elem_pos, original_pos + b - elem_pos);
+        }
+        let elem_pos = BUFFERS_POS + 4 + 4 * buffers.len();
+        write_u32!(res, elem_pos, buffer_table_pos - elem_pos);
+
+        // metadata vector, the last one is model metadata
+        write_u32!(res, metadata_vector_pos, metadata_tables.len() + 1);
+        for (i, m) in metadata_tables.iter().enumerate() {
+            let elem_pos = metadata_vector_pos + 4 + 4 * i;
+            write_u32!(res, elem_pos, original_pos + m - elem_pos);
+        }
+        let elem_pos = metadata_vector_pos + 4 + 4 * metadata_tables.len();
+        write_u32!(res, elem_pos, metadata_table_pos - elem_pos);
+
+        // buffer table: {data}
+        write_u16!(res, buffer_vtable_pos, 6);
+        write_u16!(res, buffer_vtable_pos + 2, 8);
+        write_u16!(res, buffer_vtable_pos + 4, 4);
+        write_u32!(res, buffer_table_pos, buffer_table_pos - buffer_vtable_pos);
+        write_u32!(res, buffer_table_pos + 4, data_pos - (buffer_table_pos + 4));
+
+        // metadata table: {name, buffer}
+        write_u16!(res, metadata_vtable_pos, 8);
+        write_u16!(res, metadata_vtable_pos + 2, 12);
+        write_u16!(res, metadata_vtable_pos + 4, 4);
+        write_u16!(res, metadata_vtable_pos + 6, 8);
+        write_u32!(
+            res,
+            metadata_table_pos,
+            metadata_table_pos - metadata_vtable_pos
+        );
+        write_u32!(
+            res,
+            metadata_table_pos + 4,
+            name_pos - (metadata_table_pos + 4)
+        );
+        write_u32!(res, metadata_table_pos + 8, buffers.len());
+
+        // name string
+        write_u32!(res, name_pos, name.len());
+        res[name_pos + 4..name_pos + 4 + name.len()].copy_from_slice(name);
+
+        // metadata buffer data
+        write_u32!(res, data_pos, metadata.len());
+        res[data_pos + 4..data_pos + 4 + metadata.len()].copy_from_slice(metadata);
+
+        res.extend_from_slice(model_buf);
+        tflite_model::root_as_model(res.as_slice())?;
+        Ok(res)
+    }
+}
+
+#[inline(always)]
+fn lines_to_file(lines: impl IntoIterator<Item = impl AsRef<str>>) -> Vec<u8> {
+    let mut res = Vec::new();
+    for l in lines {
+        res.extend_from_slice(l.as_ref().as_bytes());
+        res.push(b'\n');
+    }
+    res
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use crate::model::{ModelInfo, ModelResourceTrait};
+
+    // build a TFLite model without operators and metadata.
+    pub(crate) fn bare_model(
+        inputs: &[(&[i32], tflite_model::TensorType)],
+        outputs: &[(&[i32], tflite_model::TensorType)],
+    ) -> Vec<u8> {
+        let mut fbb = FlatBufferBuilder::new();
+        let mut tensors = Vec::new();
+        for (shape, tensor_type) in inputs.iter().chain(outputs.iter()) {
+            let shape = fbb.create_vector(shape);
+            tensors.push(tflite_model::Tensor::create(
+                &mut fbb,
+                &tflite_model::TensorArgs {
+                    shape: Some(shape),
+                    type_: *tensor_type,
+                    ..Default::default()
+                },
+            ));
+        }
+        let tensors = fbb.create_vector(tensors.as_slice());
+        let input_index: Vec<i32> = (0..inputs.len() as i32).collect();
+        let output_index: Vec<i32> =
+            (inputs.len() as i32..(inputs.len() + outputs.len()) as i32).collect();
+        let input_index = fbb.create_vector(input_index.as_slice());
+        let output_index = fbb.create_vector(output_index.as_slice());
+        let subgraph = tflite_model::SubGraph::create(
+            &mut fbb,
+            &tflite_model::SubGraphArgs {
+                tensors: Some(tensors),
+                inputs: Some(input_index),
+                outputs: Some(output_index),
+                ..Default::default()
+            },
+        );
+        let subgraphs = fbb.create_vector(&[subgraph]);
+        let buffer = tflite_model::Buffer::create(&mut fbb, &Default::default());
+        let buffers = fbb.create_vector(&[buffer]);
+        let description = fbb.create_string("bare model");
+        let model = tflite_model::Model::create(
+            &mut fbb,
+            &tflite_model::ModelArgs {
+                version: 3,
+                subgraphs: Some(subgraphs),
+                description: Some(description),
+                buffers: Some(buffers),
+                ..Default::default()
+            },
+        );
+        tflite_model::finish_model_buffer(&mut fbb, model);
+        fbb.finished_data().to_vec()
+    }
+
+    // build a TFLite model without operators and metadata, using the library tensor types.
+    pub(crate) fn bare_model_with_types(
+        inputs: &[(&[i32], crate::TensorType)],
+        outputs: &[(&[i32], crate::TensorType)],
+    ) -> Vec<u8> {
+        let convert = |tensors: &[(&[i32], crate::TensorType)]| {
+            tensors
+                .iter()
+                .map(|(shape, t)| {
+                    let t = match t {
+                        crate::TensorType::F16 => tflite_model::TensorType::FLOAT16,
+                        crate::TensorType::F32 => tflite_model::TensorType::FLOAT32,
+                        crate::TensorType::U8 => tflite_model::TensorType::UINT8,
+                        _ => tflite_model::TensorType::INT32,
+                    };
+                    (shape.to_vec(), t)
+                })
+                .collect::<Vec<_>>()
+        };
+        let inputs = convert(inputs);
+        let outputs = convert(outputs);
+        let inputs: Vec<_> = inputs.iter().map(|(s, t)| (s.as_slice(), *t)).collect();
+        let outputs: Vec<_> = outputs.iter().map(|(s, t)| (s.as_slice(), *t)).collect();
+        bare_model(&inputs, &outputs)
+    }
+
+    // build a TFLite model which has a string input and a float output, such as language detectors.
+    pub(crate) fn bare_string_input_model(output_shape: &[i32]) -> Vec<u8> {
+        bare_model(
+            &[(&[1], tflite_model::TensorType::STRING)],
+            &[(output_shape, tflite_model::TensorType::FLOAT32)],
+        )
+    }
+
+    fn model_metadata(buf: &[u8]) -> tflite_metadata::ModelMetadata<'_> {
+        let model = tflite_model::root_as_model(buf).unwrap();
+        TfLiteModelResource::parse_model_metadata(&model)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_classification_metadata() {
+        let model = bare_model(
+            &[(&[1, 224, 224, 3], tflite_model::TensorType::UINT8)],
+            &[(&[1, 3], tflite_model::TensorType::FLOAT32)],
+        );
+        let labels = ["cat", "dog", "bird"];
+        let writer = MetadataWriter::new(model.clone())
+            .name("classifier")
+            .version("v1")
+            .output_tensor(
+                0,
+                OutputTensorMetadata::new()
+                    .name("probability")
+                    .labels("labels.txt", labels)
+                    .display_names("labels_zh.txt", "zh", ["猫", "狗", "鸟"])
+                    .score_threshold(0.25),
+            )
+            .associated_file("README.md", b"classifier".to_vec());
+        #[cfg(feature = "vision")]
+        let writer = writer.input_tensor(
+            0,
+            InputTensorMetadata::new()
+                .name("image")
+                .image_properties(ImageColorSpaceType::RGB)
+                .normalization_options(vec![127.5], vec![127.5])
+                .stats(vec![-1.], vec![1.]),
+        );
+        let buf = writer.finalize().unwrap();
+
+        // the original model is not changed
+        let original = tflite_model::root_as_model(model.as_slice()).unwrap();
+        let new = tflite_model::root_as_model(buf.as_slice()).unwrap();
+        assert_eq!(new.version(), original.version());
+        assert_eq!(new.description(), Some("bare model"));
+        assert_eq!(new.buffers().unwrap().len(), 2);
+
+        let m = model_metadata(buf.as_slice());
+        assert_eq!(m.name(), Some("classifier"));
+        assert_eq!(m.version(), Some("v1"));
+        let output = m
+            .subgraph_metadata()
+            .unwrap()
+            .get(0)
+            .output_tensor_metadata();
+        let score_threshold = output.unwrap().get(0).process_units().unwrap().get(0);
+        assert_eq!(
+            score_threshold
+                .options_as_score_thresholding_options()
+                .unwrap()
+                .global_score_threshold(),
+            0.25
+        );
+
+        let model_info = ModelInfo::from_buffer(buf).unwrap();
+        assert_eq!(
+            model_info.input_tensor_shape(0),
+            Some([1, 224, 224, 3].as_slice())
+        );
+        assert_eq!(model_info.output_tensor_name(0), Some("probability"));
+        assert_eq!(model_info.output_tensor_labels(0).unwrap(), labels);
+        assert_eq!(
+            model_info.output_tensor_display_names(0, "zh").unwrap(),
+            ["猫", "狗", "鸟"]
+        );
+        assert_eq!(model_info.output_tensor_label_locales(0), ["zh"]);
+        assert_eq!(
+            model_info.output_tensor_process_units(0),
+            [crate::model::ProcessUnit::ScoreThresholding {
+                global_score_threshold: 0.25
+            }]
+        );
+        assert!(model_info.subgraph_input_process_units().is_empty());
+        assert_eq!(
+            model_info.associated_files(),
+            ["README.md", "labels.txt", "labels_zh.txt"]
+        );
+        assert_eq!(
+            model_info.associated_file("README.md").unwrap(),
+            Some(b"classifier".as_slice())
+        );
+
+        #[cfg(feature = "vision")]
+        {
+            let img = model_info
+                .to_tensor_info(0)
+                .unwrap()
+                .try_to_image()
+                .unwrap();
+            assert_eq!(img.color_space, ImageColorSpaceType::RGB);
+            assert_eq!(img.normalization_options, (vec![127.5], vec![127.5]));
+            assert_eq!(img.stats_min, vec![-1.]);
+            assert_eq!(img.stats_max, vec![1.]);
+            assert_eq!(img.width(), 224);
+            assert_eq!(
+                model_info.input_tensor_process_units(0),
+                [crate::model::ProcessUnit::Normalization {
+                    mean: vec![127.5],
+                    std: vec![127.5]
+                }]
+            );
+        }
+    }
+
+    #[cfg(feature = "vision")]
+    #[test]
+    fn test_detection_metadata() {
+        let model = bare_model(
+            &[(&[1, 320, 320, 3], tflite_model::TensorType::FLOAT32)],
+            &[
+                (&[1, 10, 4], tflite_model::TensorType::FLOAT32),
+                (&[1, 10], tflite_model::TensorType::FLOAT32),
+            ],
+        );
+        let buf = MetadataWriter::new(model)
+            .input_tensor(
+                0,
+                InputTensorMetadata::new().image_properties(ImageColorSpaceType::RGB),
+            )
+            .output_tensor(
+                0,
+                OutputTensorMetadata::new()
+                    .name("location")
+                    .bounding_box_properties([1, 0, 3, 2]),
+            )
+            .output_tensor(1, OutputTensorMetadata::new().name("category"))
+            .finalize()
+            .unwrap();
+
+        let model_info = ModelInfo::from_buffer(buf).unwrap();
+        assert_eq!(
+            model_info.output_tensor_bounding_box_properties(0),
+            Some([1, 0, 3, 2])
+        );
+        assert_eq!(model_info.output_tensor_bounding_box_properties(1), None);
+        assert_eq!(model_info.output_tensor_name(1), Some("category"));
+        assert!(model_info.associated_files().is_empty());
+    }
+
+    #[cfg(feature = "vision")]
+    #[test]
+    fn test_detector_metadata() {
+        let model = bare_model(
+            &[(&[1, 4, 4, 3], tflite_model::TensorType::FLOAT32)],
+            &[
+                (&[1, 2, 16], tflite_model::TensorType::FLOAT32),
+                (&[1, 2, 1], tflite_model::TensorType::FLOAT32),
+            ],
+        );
+        let buf = MetadataWriter::new(model.clone())
+            .detector_metadata(
+                DetectorMetadata::new()
+                    .fixed_anchors([[0.25, 0.5, 1., 1.], [0.75, 0.5, 1., 1.]])
+                    .tensors_decoding(1, 2, 16)
+                    .key_points(4, 6, 2)
+                    .scales(4., 4., 4., 4.)
+                    .sigmoid_score(true),
+            )
+            .finalize()
+            .unwrap();
+        let model_resource = TfLiteModelResource::new(buf.as_slice()).unwrap();
+        let meta = model_resource.ssd_detection_metadata().unwrap();
+        assert_eq!(meta.anchors.len(), 2);
+        assert_eq!(meta.anchors[1].x_center, 0.75);
+        assert_eq!(meta.anchors[1].w, 1.);
+        let d = meta.decoding_options.as_ref().unwrap();
+        assert_eq!((d.num_classes, d.num_boxes, d.num_coords), (1, 2, 16));
+        assert_eq!((d.keypoint_coord_offset, d.num_key_points), (4, 6));
+        assert_eq!(d.h_scale, 4.);
+        assert!(d.sigmoid_score);
+        assert!(!d.apply_exponential_on_box_size);
+
+        // anchors only
+        let buf = MetadataWriter::new(model.clone())
+            .detector_metadata(DetectorMetadata::new().fixed_anchors([[0.5, 0.5, 1., 1.]]))
+            .finalize()
+            .unwrap();
+        let model_resource = TfLiteModelResource::new(buf.as_slice()).unwrap();
+        let meta = model_resource.ssd_detection_metadata().unwrap();
+        assert_eq!(meta.anchors.len(), 1);
+        assert!(meta.decoding_options.is_none());
+
+        assert!(TfLiteModelResource::new(model.as_slice())
+            .unwrap()
+            .ssd_detection_metadata()
+            .is_none());
+    }
+
+    #[cfg(feature = "audio")]
+    #[test]
+    fn test_audio_metadata() {
+        let model = bare_model(
+            &[(&[1, 15600], tflite_model::TensorType::FLOAT32)],
+            &[(&[1, 521], tflite_model::TensorType::FLOAT32)],
+        );
+        let buf = MetadataWriter::new(model)
+            .input_tensor(0, InputTensorMetadata::new().audio_properties(16000, 1))
+            .finalize()
+            .unwrap();
+        let model_resource = TfLiteModelResource::new(buf.as_slice()).unwrap();
+        let audio = model_resource
+            .to_tensor_info(0)
+            .unwrap()
+            .try_to_audio()
+            .unwrap();
+        assert_eq!(audio.sample_rate, 16000);
+        assert_eq!(audio.num_channels, 1);
+        assert_eq!(audio.num_samples, 15600);
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn test_text_metadata() {
+        let model = bare_model(
+            &[
+                (&[1, 128], tflite_model::TensorType::INT32),
+                (&[1, 128], tflite_model::TensorType::INT32),
+                (&[1, 128], tflite_model::TensorType::INT32),
+            ],
+            &[(&[1, 2], tflite_model::TensorType::FLOAT32)],
+        );
+        let buf = MetadataWriter::new(model)
+            .bert_tokenizer("vocab.txt", ["[PAD]", "[UNK]", "[CLS]", "[SEP]", "hello"])
+            .finalize()
+            .unwrap();
+        let model_resource = TfLiteModelResource::new(buf.as_slice()).unwrap();
+        assert!(matches!(
+            model_resource.to_tensor_info(0).unwrap().try_to_text(),
+            Ok(crate::preprocess::text::TextToTensorInfo::BertModel {
+                max_seq_len: 128,
+                ..
+            })
+        ));
+
+        let model = bare_model(
+            &[(&[1, 256], tflite_model::TensorType::INT32)],
+            &[(&[1, 2], tflite_model::TensorType::FLOAT32)],
+        );
+        let buf = MetadataWriter::new(model)
+            .regex_tokenizer(
+                "[^\\w\\']+",
+                "vocab.txt",
+                [("<PAD>", 0), ("<START>", 1), ("<UNKNOWN>", 2), ("hello", 3)],
+            )
+            .finalize()
+            .unwrap();
+        let model_resource = TfLiteModelResource::new(buf.as_slice()).unwrap();
+        assert!(matches!(
+            model_resource.to_tensor_info(0).unwrap().try_to_text(),
+            Ok(crate::preprocess::text::TextToTensorInfo::RegexModel {
+                max_seq_len: 256,
+                ..
+            })
+        ));
+
+        // bert tokenizer needs 3 input tensors
+        let model = bare_model(
+            &[(&[1, 256], tflite_model::TensorType::INT32)],
+            &[(&[1, 2], tflite_model::TensorType::FLOAT32)],
+        );
+        assert!(MetadataWriter::new(model)
+            .bert_tokenizer("vocab.txt", ["[CLS]", "[SEP]"])
+            .finalize()
+            .is_err());
+    }
+
+    #[test]
+    fn test_rewrite_metadata() {
+        let model = bare_model(
+            &[(&[1, 4], tflite_model::TensorType::FLOAT32)],
+            &[(&[1, 2], tflite_model::TensorType::FLOAT32)],
+        );
+        let buf = MetadataWriter::new(model)
+            .name("first")
+            .output_tensor(0, OutputTensorMetadata::new().labels("a.txt", ["a", "b"]))
+            .finalize()
+            .unwrap();
+        let buf = MetadataWriter::new(buf)
+            .name("second")
+            .output_tensor(0, OutputTensorMetadata::new().labels("b.txt", ["c", "d"]))
+            .finalize()
+            .unwrap();
+
+        let new = tflite_model::root_as_model(buf.as_slice()).unwrap();
+        assert_eq!(new.metadata().unwrap().len(), 1);
+        assert_eq!(model_metadata(buf.as_slice()).name(), Some("second"));
+        let model_info = ModelInfo::from_buffer(buf).unwrap();
+        assert_eq!(model_info.output_tensor_labels(0).unwrap(), ["c", "d"]);
+        assert_eq!(model_info.associated_files(), ["b.txt"]);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(MetadataWriter::new(vec![0; 32]).finalize().is_err());
+
+        let model = bare_model(
+            &[(&[1, 4], tflite_model::TensorType::FLOAT32)],
+            &[(&[1, 2], tflite_model::TensorType::FLOAT32)],
+        );
+        assert!(MetadataWriter::new(model.clone())
+            .input_tensor(1, InputTensorMetadata::new())
+            .finalize()
+            .is_err());
+        assert!(MetadataWriter::new(model.clone())
+            .output_tensor(1, OutputTensorMetadata::new())
+            .finalize()
+            .is_err());
+        assert!(MetadataWriter::new(model.clone())
+            .input_tensor(
+                0,
+                InputTensorMetadata::new().normalization_options(vec![1., 2.], vec![1.])
+            )
+            .finalize()
+            .is_err());
+        // same file name with different contents
+        assert!(MetadataWriter::new(model.clone())
+            .output_tensor(0, OutputTensorMetadata::new().labels("a.txt", ["a", "b"]))
+            .associated_file("a.txt", vec![])
+            .finalize()
+            .is_err());
+        assert!(MetadataWriter::new(model).finalize().is_ok());
+    }
+}
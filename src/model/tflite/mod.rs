@@ -1,7 +1,13 @@
 mod generated;
+pub mod metadata_writer;
 
 use super::*;
 use generated::{tflite as tflite_model, tflite_metadata};
+#[cfg(feature = "text")]
+use std::borrow::Cow;
+
+#[cfg(feature = "text")]
+type VocabParseFn = for<'a> fn(MemoryTextFile<'a>) -> Result<HashMap<Cow<'a, str>, i32>, Error>;
 
 pub(crate) struct TfLiteModelResource<'buf> {
     output_tensor_metadata: Option<
@@ -15,12 +21,15 @@ pub(crate) struct TfLiteModelResource<'buf> {
     output_shape: Vec<Vec<usize>>,
     input_types: Vec<TensorType>,
     output_types: Vec<TensorType>,
+    input_quantization_parameters: Vec<Option<QuantizationParameters>>,
     output_quantization_parameters: Vec<Option<QuantizationParameters>>,
     to_tensor_info: Vec<ToTensorInfo<'buf>>,
     output_name_map: HashMap<&'buf str, usize>,
     associated_files: Option<ZipFiles<'buf>>,
     // now it only used for image segmentation
     output_activation: Activation,
+    #[cfg(feature = "vision")]
+    ssd_detection_metadata: Option<SsdDetectionMetadata>,
 }
 
 impl<'buf> TfLiteModelResource<'buf> {
@@ -30,7 +39,7 @@ impl<'buf> TfLiteModelResource<'buf> {
     const METADATA_NAME: &'static str = "TFLITE_METADATA";
 
     pub(super) fn new(buf: &'buf [u8]) -> Result<Self, Error> {
-        let associated_files = ZipFiles::try_new(buf).unwrap_or(None);
+        let associated_files = ZipFiles::try_new(buf)?;
         let model = tflite_model::root_as_model(buf)?;
         let mut _self = Self {
             output_tensor_metadata: None,
@@ -38,11 +47,14 @@ impl<'buf> TfLiteModelResource<'buf> {
             output_shape: Vec::new(),
             input_types: Vec::new(),
             output_types: Vec::new(),
+            input_quantization_parameters: Vec::new(),
             output_quantization_parameters: Vec::new(),
             to_tensor_info: Vec::new(),
             output_name_map: Default::default(),
             associated_files,
             output_activation: Default::default(),
+            #[cfg(feature = "vision")]
+            ssd_detection_metadata: None,
         };
         _self.parse_subgraph(&model)?;
         let metadata = Self::parse_model_metadata(&model)?;
@@ -81,7 +93,22 @@ impl<'buf> TfLiteModelResource<'buf> {
                     )));
                 }
                 let t = tensors.get(index);
-                self.input_types.push(Self::tflite_type_parse(t.type_())?);
+                let tensor_type = match t.type_() {
+                    // string tensor will be fed as serialized bytes
+                    #[cfg(feature = "text")]
+                    tflite_model::TensorType::STRING => {
+                        while self.to_tensor_info.len() < i {
+                            self.to_tensor_info.push(ToTensorInfo::new_none());
+                        }
+                        self.to_tensor_info
+                            .push(ToTensorInfo::new_text(TextToTensorInfo::StringModel));
+                        TensorType::U8
+                    }
+                    tflite_type => Self::tflite_type_parse(tflite_type)?,
+                };
+                self.input_types.push(tensor_type);
+                self.input_quantization_parameters
+                    .push(Self::tensor_quantization_parameters(&t));
                 if let Some(s) = t.shape() {
                     let len = s.len();
                     let mut shape = Vec::with_capacity(len);
@@ -140,21 +167,8 @@ impl<'buf> TfLiteModelResource<'buf> {
                     )));
                 }
 
-                if let Some(q) = t.quantization() {
-                    if let (Some(z), Some(s)) = (q.zero_point(), q.scale()) {
-                        if z.len() > 0 && s.len() > 0 {
-                            while self.output_quantization_parameters.len() < i {
-                                self.output_quantization_parameters.push(None);
-                            }
-                            self.output_quantization_parameters.push(Some(
-                                QuantizationParameters {
-                                    scale: s.get(0),
-                                    zero_point: z.get(0) as i32,
-                                },
-                            ));
-                        }
-                    }
-                }
+                self.output_quantization_parameters
+                    .push(Self::tensor_quantization_parameters(&t));
             }
         } else {
             return Err(Error::ModelParseError(
@@ -164,6 +178,20 @@ impl<'buf> TfLiteModelResource<'buf> {
         Ok(())
     }
 
+    #[inline]
+    fn tensor_quantization_parameters(
+        t: &tflite_model::Tensor<'buf>,
+    ) -> Option<QuantizationParameters> {
+        let q = t.quantization()?;
+        match (q.zero_point(), q.scale()) {
+            (Some(z), Some(s)) if !z.is_empty() && !s.is_empty() => Some(QuantizationParameters {
+                scale: s.get(0),
+                zero_point: z.get(0) as i32,
+            }),
+            _ => None,
+        }
+    }
+
     #[inline]
     fn parse_model_metadata(
         model: &tflite_model::Model<'buf>,
@@ -196,6 +224,79 @@ impl<'buf> TfLiteModelResource<'buf> {
         Ok(None)
     }
 
+    /// Parse the process units in model metadata, return empty process units if the model has no metadata.
+    pub(crate) fn parse_process_units(buf: &'buf [u8]) -> Result<MetadataProcessUnits, Error> {
+        let model = tflite_model::root_as_model(buf)?;
+        let mut res = MetadataProcessUnits::default();
+        let subgraph = match Self::parse_model_metadata(&model)?.and_then(|m| m.subgraph_metadata())
+        {
+            Some(s) if !s.is_empty() => s.get(0),
+            _ => return Ok(res),
+        };
+        if let Some(tensors) = subgraph.input_tensor_metadata() {
+            res.inputs = tensors
+                .iter()
+                .map(|t| Self::to_process_units(t.process_units()))
+                .collect();
+        }
+        if let Some(tensors) = subgraph.output_tensor_metadata() {
+            res.outputs = tensors
+                .iter()
+                .map(|t| Self::to_process_units(t.process_units()))
+                .collect();
+        }
+        res.subgraph_inputs = Self::to_process_units(subgraph.input_process_units());
+        res.subgraph_outputs = Self::to_process_units(subgraph.output_process_units());
+        Ok(res)
+    }
+
+    fn to_process_units(
+        units: Option<
+            flatbuffers::Vector<
+                'buf,
+                flatbuffers::ForwardsUOffset<tflite_metadata::ProcessUnit<'buf>>,
+            >,
+        >,
+    ) -> Vec<ProcessUnit> {
+        let units = match units {
+            Some(u) => u,
+            None => return Vec::new(),
+        };
+        units
+            .iter()
+            .filter_map(|u| {
+                if let Some(n) = u.options_as_normalization_options() {
+                    Some(ProcessUnit::Normalization {
+                        mean: n.mean().map(|v| v.iter().collect()).unwrap_or_default(),
+                        std: n.std_().map(|v| v.iter().collect()).unwrap_or_default(),
+                    })
+                } else if let Some(c) = u.options_as_score_calibration_options() {
+                    Some(ProcessUnit::ScoreCalibration {
+                        score_transformation: c
+                            .score_transformation()
+                            .variant_name()
+                            .unwrap_or("UNKNOWN")
+                            .to_string(),
+                        default_score: c.default_score(),
+                    })
+                } else if let Some(t) = u.options_as_score_thresholding_options() {
+                    Some(ProcessUnit::ScoreThresholding {
+                        global_score_threshold: t.global_score_threshold(),
+                    })
+                } else if u.options_as_bert_tokenizer_options().is_some() {
+                    Some(ProcessUnit::BertTokenizer)
+                } else if u.options_as_sentence_piece_tokenizer_options().is_some() {
+                    Some(ProcessUnit::SentencePieceTokenizer)
+                } else {
+                    u.options_as_regex_tokenizer_options()
+                        .map(|r| ProcessUnit::RegexTokenizer {
+                            delim_regex_pattern: r.delim_regex_pattern().map(|p| p.to_string()),
+                        })
+                }
+            })
+            .collect()
+    }
+
     #[inline]
     fn parse_model_metadata_content(
         &mut self,
@@ -278,6 +379,11 @@ impl<'buf> TfLiteModelResource<'buf> {
                                 ));
                             };
                         }
+                    } else if name == generated::CUSTOM_DETECTOR_METADATA_NAME {
+                        if let Some(data) = m.data() {
+                            self.ssd_detection_metadata =
+                                Some(Self::parse_detector_metadata(data.bytes())?);
+                        }
                     }
                 }
             }
@@ -285,6 +391,59 @@ impl<'buf> TfLiteModelResource<'buf> {
         Ok(())
     }
 
+    #[cfg(feature = "vision")]
+    fn parse_detector_metadata(buf: &[u8]) -> Result<SsdDetectionMetadata, Error> {
+        let meta = generated::custom_object_detector::root_as_object_detector_options(buf)?;
+        let mut res = SsdDetectionMetadata::default();
+        if let Some(anchors) = meta
+            .ssd_anchors_options()
+            .and_then(|o| o.fixed_anchors_schema())
+            .and_then(|s| s.anchors())
+        {
+            res.anchors.reserve(anchors.len());
+            for a in anchors.iter() {
+                res.anchors.push(crate::postprocess::Anchor {
+                    x_center: a.x_center(),
+                    y_center: a.y_center(),
+                    h: a.height(),
+                    w: a.width(),
+                });
+            }
+        }
+        if let Some(d) = meta.tensors_decoding_options() {
+            let to_usize = |v: i32, name: &str| {
+                if v < 0 {
+                    return Err(Error::ModelParseError(format!(
+                        "Invalid detector metadata: `{}` is negative: `{}`",
+                        name, v
+                    )));
+                }
+                Ok(v as usize)
+            };
+            res.decoding_options = Some(crate::postprocess::SsdDecodingOptions {
+                num_classes: to_usize(d.num_classes(), "num_classes")?,
+                num_boxes: to_usize(d.num_boxes(), "num_boxes")?,
+                num_coords: to_usize(d.num_coords(), "num_coords")?,
+                keypoint_coord_offset: to_usize(
+                    d.keypoint_coord_offset(),
+                    "keypoint_coord_offset",
+                )?,
+                num_key_points: to_usize(d.num_keypoints(), "num_keypoints")?,
+                num_values_per_key_point: to_usize(
+                    d.num_values_per_keypoint(),
+                    "num_values_per_keypoint",
+                )?,
+                x_scale: d.x_scale(),
+                y_scale: d.y_scale(),
+                w_scale: d.w_scale(),
+                h_scale: d.h_scale(),
+                apply_exponential_on_box_size: d.apply_exponential_on_box_size(),
+                sigmoid_score: d.sigmoid_score(),
+            });
+        }
+        Ok(res)
+    }
+
     #[cfg(feature = "vision")]
     #[inline]
     fn parse_vision_model_input_info(
@@ -419,13 +578,9 @@ impl<'buf> TfLiteModelResource<'buf> {
                     }
                     let max_seq_len = Self::get_max_seq_len(&self.input_shape)?;
 
-                    let mut f = self.process_vocab_files(b.vocab_file())?;
-                    let mut token_index_map = HashMap::new();
-                    let mut index = 0;
-                    while let Some(v) = f.next_line() {
-                        token_index_map.insert(v, index);
-                        index += 1;
-                    }
+                    let vocab = self.process_vocab_files(b.vocab_file())?;
+                    let token_index_map =
+                        Self::parse_vocab_file(vocab, Self::parse_bert_vocab_file)?;
 
                     let text_model_input =
                         TextToTensorInfo::new_bert_model(max_seq_len, token_index_map)?;
@@ -453,28 +608,9 @@ impl<'buf> TfLiteModelResource<'buf> {
                             Some(d) => d,
                         };
 
-                        let mut f = self.process_vocab_files(r.vocab_file())?;
-                        let mut token_index_map = HashMap::new();
-                        loop {
-                            let (token, index) = f.next_line_with_split_white_space();
-                            if token.is_none() {
-                                break;
-                            }
-                            let token = token.unwrap();
-                            if index.is_none() {
-                                return Err(Error::ModelParseError(format!(
-                                    "Cannot found index in vocab file at `{}`",
-                                    token
-                                )));
-                            }
-                            let index = index.as_ref().unwrap().parse().map_err(|e| {
-                                Error::ModelParseError(format!(
-                                    "Cannot parse vocab file index: `{:?}`",
-                                    e
-                                ))
-                            })?;
-                            token_index_map.insert(token.clone(), index);
-                        }
+                        let vocab = self.process_vocab_files(r.vocab_file())?;
+                        let token_index_map =
+                            Self::parse_vocab_file(vocab, Self::parse_regex_vocab_file)?;
 
                         let text_model_input = TextToTensorInfo::new_regex_model(
                             max_seq_len,
@@ -501,7 +637,7 @@ impl<'buf> TfLiteModelResource<'buf> {
                 flatbuffers::ForwardsUOffset<tflite_metadata::AssociatedFile<'buf>>,
             >,
         >,
-    ) -> Result<MemoryTextFile<'buf>, Error> {
+    ) -> Result<Cow<'buf, [u8]>, Error> {
         if files.is_none() || files.unwrap().len() == 0 {
             return Err(Error::ModelParseError(
                 "No vocab files have been found".into(),
@@ -522,7 +658,73 @@ impl<'buf> TfLiteModelResource<'buf> {
                 "Cannot get associated filename.".into(),
             ));
         };
-        Ok(MemoryTextFile::new(self.get_file_content(filename)?))
+        if self.associated_files.is_none() {
+            return Err(Error::ModelParseError(
+                "No associated files have been found in model asset.".into(),
+            ));
+        }
+        match self
+            .associated_files
+            .as_ref()
+            .unwrap()
+            .get_file_cow(filename)?
+        {
+            Some(c) => Ok(c),
+            None => Err(Error::ModelParseError(format!(
+                "Cannot find associated file `{}`",
+                filename
+            ))),
+        }
+    }
+
+    // stored vocab file can be zero-copy, but tokens of deflated vocab file must be owned.
+    #[cfg(feature = "text")]
+    #[inline]
+    fn parse_vocab_file(
+        vocab: Cow<'buf, [u8]>,
+        parse_fn: VocabParseFn,
+    ) -> Result<HashMap<Cow<'buf, str>, i32>, Error> {
+        match vocab {
+            Cow::Borrowed(b) => parse_fn(MemoryTextFile::new(b)),
+            Cow::Owned(o) => Ok(parse_fn(MemoryTextFile::new(o.as_slice()))?
+                .into_iter()
+                .map(|(k, v)| (Cow::Owned(k.into_owned()), v))
+                .collect()),
+        }
+    }
+
+    #[cfg(feature = "text")]
+    fn parse_bert_vocab_file(mut f: MemoryTextFile) -> Result<HashMap<Cow<str>, i32>, Error> {
+        let mut token_index_map = HashMap::new();
+        let mut index = 0;
+        while let Some(v) = f.next_line() {
+            token_index_map.insert(v, index);
+            index += 1;
+        }
+        Ok(token_index_map)
+    }
+
+    #[cfg(feature = "text")]
+    fn parse_regex_vocab_file(mut f: MemoryTextFile) -> Result<HashMap<Cow<str>, i32>, Error> {
+        let mut token_index_map = HashMap::new();
+        loop {
+            let (token, index) = f.next_line_with_split_white_space();
+            if token.is_none() {
+                break;
+            }
+            let token = token.unwrap();
+            if index.is_none() {
+                return Err(Error::ModelParseError(format!(
+                    "Cannot found index in vocab file at `{}`",
+                    token
+                )));
+            }
+            let index = index.as_ref().unwrap().parse().map_err(|e| {
+                Error::ModelParseError(format!("Cannot parse vocab file index: `{:?}`", e))
+            })?;
+            token_index_map.insert(token.clone(), index);
+        }
+        Ok(token_index_map)
     }
 
     // for bert and regex model.
@@ -560,13 +762,13 @@ impl<'buf> TfLiteModelResource<'buf> {
     }
 
     #[inline(always)]
-    fn get_file_content(&self, filename: &str) -> Result<&'buf [u8], Error> {
+    fn get_file_content(&self, filename: &str) -> Result<&[u8], Error> {
         if self.associated_files.is_none() {
             return Err(Error::ModelParseError(
                 "No associated files have been found in model asset.".into(),
             ));
         }
-        match self.associated_files.as_ref().unwrap().get_file(filename) {
+        match self.associated_files.as_ref().unwrap().get_file(filename)? {
             Some(c) => Ok(c),
             None => {
                 return Err(Error::ModelParseError(format!(
@@ -625,6 +827,22 @@ impl<'buf> ModelResourceTrait for TfLiteModelResource<'buf> {
         self.output_name_map.get(name).cloned()
     }
 
+    fn output_tensor_name(&self, index: usize) -> Option<&str> {
+        if let Some(o) = self.output_tensor_metadata {
+            if index < o.len() {
+                return o.get(index).name();
+            }
+        }
+        None
+    }
+
+    fn input_tensor_quantization_parameters(&self, index: usize) -> Option<QuantizationParameters> {
+        self.input_quantization_parameters
+            .get(index)
+            .cloned()
+            .flatten()
+    }
+
     fn output_tensor_quantization_parameters(
         &self,
         index: usize,
@@ -685,6 +903,27 @@ impl<'buf> ModelResourceTrait for TfLiteModelResource<'buf> {
         Ok((l.unwrap(), locale))
     }
 
+    fn output_tensor_labels_locales(&self, index: usize) -> Vec<&str> {
+        let mut res = Vec::new();
+        if let Some(o) = self.output_tensor_metadata {
+            if index < o.len() {
+                if let Some(files) = o.get(index).associated_files() {
+                    for f in files.iter() {
+                        let tp = f.type_();
+                        if tp == tflite_metadata::AssociatedFileType::TENSOR_AXIS_LABELS
+                            || tp == tflite_metadata::AssociatedFileType::TENSOR_VALUE_LABELS
+                        {
+                            if let Some(locale) = f.locale() {
+                                res.push(locale);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+
     #[cfg(feature = "vision")]
     fn output_bounding_box_properties(&self, index: usize, slice: &mut [usize]) -> bool {
         if let Some(o) = self.output_tensor_metadata {
@@ -706,6 +945,11 @@ impl<'buf> ModelResourceTrait for TfLiteModelResource<'buf> {
         false
     }
 
+    #[cfg(feature = "vision")]
+    fn ssd_detection_metadata(&self) -> Option<&SsdDetectionMetadata> {
+        self.ssd_detection_metadata.as_ref()
+    }
+
     fn to_tensor_info(&self, input_index: usize) -> Option<&ToTensorInfo> {
         self.to_tensor_info.get(input_index)
     }
@@ -713,6 +957,10 @@ impl<'buf> ModelResourceTrait for TfLiteModelResource<'buf> {
     fn output_activation(&self) -> Activation {
         self.output_activation
     }
+
+    fn associated_files(&self) -> Option<&ZipFiles> {
+        self.associated_files.as_ref()
+    }
 }
 
 // todo: The GPU backend isn't able to process int data. If the input tensor is quantized, forces the image preprocessing graph to use CPU backend.
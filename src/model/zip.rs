@@ -3,6 +3,7 @@
 use crate::Error;
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
 macro_rules! read_le_u16 {
     ( $buf:expr, $offset:expr ) => {{
@@ -26,7 +27,21 @@ macro_rules! read_le_u32 {
     }};
 }
 
-// todo: support zip64
+macro_rules! read_le_u64 {
+    ( $buf:expr, $offset:expr ) => {{
+        let start = $offset;
+        let mut arr = [0u8; 8];
+        arr.copy_from_slice(&$buf[start..start + 8]);
+        u64::from_le_bytes(arr)
+    }};
+}
+
+#[inline(always)]
+fn to_usize(value: u64, name: &str) -> Result<usize, Error> {
+    usize::try_from(value)
+        .map_err(|_| Error::ZipFileParseError(format!("{} `{}` is out of range", name, value)))
+}
+
 /*
 4.3.16  End of central directory record:
 
@@ -51,7 +66,7 @@ struct EndOfCentralDirectoryRecord<'buf> {
 
 impl<'buf> EndOfCentralDirectoryRecord<'buf> {
     const HEAD_SIGNATURE: &'static [u8] = &[0x50, 0x4b, 0x05, 0x06];
-    const MIN_SIZE: usize = 20; // at least 20 bytes
+    const MIN_SIZE: usize = 22; // at least 22 bytes
 
     const NUMBER_OF_THIS_DISK_POS: usize = 4;
     const DISK_WHERE_CENTRAL_DIRECTORY_STARTS_POS: usize = 6;
@@ -108,6 +123,17 @@ impl<'buf> EndOfCentralDirectoryRecord<'buf> {
         &self.buf[Self::COMMENT_POS..Self::COMMENT_POS + self.comment_length() as usize]
     }
 
+    // if any field overflows, the real value is stored in zip64 end of central directory record.
+    #[inline(always)]
+    fn is_zip64(&self) -> bool {
+        self.number_of_this_disk() == u16::MAX
+            || self.disk_where_central_directory_starts() == u16::MAX
+            || self.number_of_central_directory_records_on_this_disk() == u16::MAX
+            || self.total_number_of_central_directory_records() == u16::MAX
+            || self.size_of_central_directory() == u32::MAX
+            || self.offset_of_start_of_central_directory() == u32::MAX
+    }
+
     #[inline(always)]
     fn try_find_start_pos(buf: &'buf [u8]) -> usize {
         let mut start_pos = match buf.len().checked_sub(Self::MIN_SIZE) {
@@ -138,9 +164,24 @@ impl<'buf> EndOfCentralDirectoryRecord<'buf> {
                 buf.len()
             )));
         }
+        if start_pos + Self::MIN_SIZE > buf.len() {
+            return Err(Error::ZipFileParseError(format!(
+                "End of central directory is too short `{}`",
+                buf.len() - start_pos
+            )));
+        }
         let res = Self {
             buf: &buf[start_pos..],
         };
+        if start_pos + Self::COMMENT_POS + res.comment_length() as usize > buf.len() {
+            return Err(Error::ZipFileParseError(
+                "Comment length is too long".into(),
+            ));
+        }
+        // the central directory information will be checked in zip64 end of central directory record.
+        if res.is_zip64() {
+            return Ok(res);
+        }
         if res.number_of_this_disk() != 0
             || res.disk_where_central_directory_starts() != 0
             || res.number_of_central_directory_records_on_this_disk()
@@ -151,18 +192,14 @@ impl<'buf> EndOfCentralDirectoryRecord<'buf> {
             ));
         }
         let central_directory = res.offset_of_start_of_central_directory() as usize;
-        if central_directory >= start_pos
-            || central_directory + res.size_of_central_directory() as usize > buf.len()
+        if central_directory
+            .checked_add(res.size_of_central_directory() as usize)
+            .map_or(true, |end| end > start_pos)
         {
             return Err(Error::ZipFileParseError(
                 "Central directory information error".into(),
             ));
         }
-        if start_pos + Self::COMMENT_POS + res.comment_length() as usize > buf.len() {
-            return Err(Error::ZipFileParseError(
-                "Comment length is too long".into(),
-            ));
-        }
         return Ok(res);
     }
 
@@ -178,6 +215,199 @@ impl<'buf> EndOfCentralDirectoryRecord<'buf> {
     }
 }
 
+/*
+4.3.15 Zip64 end of central directory locator
+
+      zip64 end of central dir locator
+      signature                       4 bytes  (0x07064b50)
+      number of the disk with the
+      start of the zip64 end of
+      central directory               4 bytes
+      relative offset of the zip64
+      end of central directory record 8 bytes
+      total number of disks           4 bytes
+ */
+struct Zip64EndOfCentralDirectoryLocator<'buf> {
+    buf: &'buf [u8],
+}
+
+impl<'buf> Zip64EndOfCentralDirectoryLocator<'buf> {
+    const HEAD_SIGNATURE: &'static [u8] = &[0x50, 0x4b, 0x06, 0x07];
+    const SIZE: usize = 20;
+
+    const DISK_WHERE_ZIP64_END_OF_CENTRAL_DIRECTORY_STARTS_POS: usize = 4;
+    const OFFSET_OF_ZIP64_END_OF_CENTRAL_DIRECTORY_POS: usize = 8;
+    const TOTAL_NUMBER_OF_DISKS_POS: usize = 16;
+
+    #[inline(always)]
+    fn disk_where_zip64_end_of_central_directory_starts(&self) -> u32 {
+        read_le_u32!(
+            self.buf,
+            Self::DISK_WHERE_ZIP64_END_OF_CENTRAL_DIRECTORY_STARTS_POS
+        )
+    }
+
+    #[inline(always)]
+    fn offset_of_zip64_end_of_central_directory(&self) -> u64 {
+        read_le_u64!(self.buf, Self::OFFSET_OF_ZIP64_END_OF_CENTRAL_DIRECTORY_POS)
+    }
+
+    #[inline(always)]
+    fn total_number_of_disks(&self) -> u32 {
+        read_le_u32!(self.buf, Self::TOTAL_NUMBER_OF_DISKS_POS)
+    }
+
+    // the locator is immediately before the end of central directory record.
+    #[inline]
+    fn new_with_end_of_central_directory_pos(
+        buf: &'buf [u8],
+        end_of_central_directory_pos: usize,
+    ) -> Result<Self, Error> {
+        let start_pos = match end_of_central_directory_pos.checked_sub(Self::SIZE) {
+            Some(p) => p,
+            None => {
+                return Err(Error::ZipFileParseError(
+                    "Cannot find zip64 end of central directory locator.".into(),
+                ));
+            }
+        };
+        if &buf[start_pos..start_pos + 4] != Self::HEAD_SIGNATURE {
+            return Err(Error::ZipFileParseError(format!(
+                "Invalid zip64 end of central directory locator head magic `{:?}`",
+                &buf[start_pos..start_pos + 4]
+            )));
+        }
+        let res = Self {
+            buf: &buf[start_pos..end_of_central_directory_pos],
+        };
+        if res.disk_where_zip64_end_of_central_directory_starts() != 0
+            || res.total_number_of_disks() > 1
+        {
+            return Err(Error::ZipFileParseError(
+                "Unsupported multi-disk zip file.".into(),
+            ));
+        }
+        if res.offset_of_zip64_end_of_central_directory() >= start_pos as u64 {
+            return Err(Error::ZipFileParseError(
+                "Zip64 end of central directory offset error".into(),
+            ));
+        }
+        Ok(res)
+    }
+}
+
+/*
+4.3.14  Zip64 end of central directory record
+
+        zip64 end of central dir
+        signature                       4 bytes  (0x06064b50)
+        size of zip64 end of central
+        directory record                8 bytes
+        version made by                 2 bytes
+        version needed to extract       2 bytes
+        number of this disk             4 bytes
+        number of the disk with the
+        start of the central directory  4 bytes
+        total number of entries in the
+        central directory on this disk  8 bytes
+        total number of entries in the
+        central directory               8 bytes
+        size of the central directory   8 bytes
+        offset of start of central
+        directory with respect to
+        the starting disk number        8 bytes
+        zip64 extensible data sector    (variable size)
+ */
+struct Zip64EndOfCentralDirectoryRecord<'buf> {
+    buf: &'buf [u8],
+}
+
+impl<'buf> Zip64EndOfCentralDirectoryRecord<'buf> {
+    const HEAD_SIGNATURE: &'static [u8] = &[0x50, 0x4b, 0x06, 0x06];
+    const MIN_SIZE: usize = 56;
+
+    const NUMBER_OF_THIS_DISK_POS: usize = 16;
+    const DISK_WHERE_CENTRAL_DIRECTORY_STARTS_POS: usize = 20;
+    const NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_ON_THIS_DISK_POS: usize = 24;
+    const TOTAL_NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_POS: usize = 32;
+    const SIZE_OF_CENTRAL_DIRECTORY_POS: usize = 40;
+    const OFFSET_OF_START_OF_CENTRAL_DIRECTORY_POS: usize = 48;
+
+    #[inline(always)]
+    fn number_of_this_disk(&self) -> u32 {
+        read_le_u32!(self.buf, Self::NUMBER_OF_THIS_DISK_POS)
+    }
+
+    #[inline(always)]
+    fn disk_where_central_directory_starts(&self) -> u32 {
+        read_le_u32!(self.buf, Self::DISK_WHERE_CENTRAL_DIRECTORY_STARTS_POS)
+    }
+
+    #[inline(always)]
+    fn number_of_central_directory_records_on_this_disk(&self) -> u64 {
+        read_le_u64!(
+            self.buf,
+            Self::NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_ON_THIS_DISK_POS
+        )
+    }
+
+    #[inline(always)]
+    fn total_number_of_central_directory_records(&self) -> u64 {
+        read_le_u64!(
+            self.buf,
+            Self::TOTAL_NUMBER_OF_CENTRAL_DIRECTORY_RECORDS_POS
+        )
+    }
+
+    #[inline(always)]
+    fn size_of_central_directory(&self) -> u64 {
+        read_le_u64!(self.buf, Self::SIZE_OF_CENTRAL_DIRECTORY_POS)
+    }
+
+    #[inline(always)]
+    fn offset_of_start_of_central_directory(&self) -> u64 {
+        read_le_u64!(self.buf, Self::OFFSET_OF_START_OF_CENTRAL_DIRECTORY_POS)
+    }
+
+    #[inline]
+    fn new_with_start_pos(buf: &'buf [u8], start_pos: usize) -> Result<Self, Error> {
+        if start_pos + Self::MIN_SIZE > buf.len() {
+            return Err(Error::ZipFileParseError(format!(
+                "Zip64 end of central directory start pos error `{}`",
+                start_pos
+            )));
+        }
+        if &buf[start_pos..start_pos + 4] != Self::HEAD_SIGNATURE {
+            return Err(Error::ZipFileParseError(format!(
+                "Invalid zip64 end of central directory head magic `{:?}`",
+                &buf[start_pos..start_pos + 4]
+            )));
+        }
+        let res = Self {
+            buf: &buf[start_pos..],
+        };
+        if res.number_of_this_disk() != 0
+            || res.disk_where_central_directory_starts() != 0
+            || res.number_of_central_directory_records_on_this_disk()
+                != res.total_number_of_central_directory_records()
+        {
+            return Err(Error::ZipFileParseError(
+                "Unsupported multi-disk zip file.".into(),
+            ));
+        }
+        let central_directory = res.offset_of_start_of_central_directory();
+        if central_directory
+            .checked_add(res.size_of_central_directory())
+            .map_or(true, |end| end > start_pos as u64)
+        {
+            return Err(Error::ZipFileParseError(
+                "Central directory information error".into(),
+            ));
+        }
+        Ok(res)
+    }
+}
+
 /*
   4.3.12  Central directory structure:
      [central directory header 1]
@@ -212,6 +442,10 @@ impl<'buf> EndOfCentralDirectoryRecord<'buf> {
 */
 struct CentralDirectory<'buf> {
     buf: &'buf [u8],
+    // the real values, which may be read from zip64 extended information extra field.
+    file_size: usize,
+    compressed_file_size: usize,
+    local_file_header_offset: usize,
 }
 
 impl<'buf> CentralDirectory<'buf> {
@@ -235,6 +469,10 @@ impl<'buf> CentralDirectory<'buf> {
     const RELATIVE_OFFSET_OF_LOCAL_HEADER_POS: usize = 42;
     const FILE_NAME_POS: usize = 46;
 
+    const COMPRESSION_METHOD_STORE: u16 = 0;
+    const COMPRESSION_METHOD_DEFLATE: u16 = 8;
+    const ZIP64_EXTENDED_INFORMATION_HEADER_ID: u16 = 0x0001;
+
     #[inline]
     fn new(buf: &'buf [u8]) -> Result<Self, Error> {
         let len = buf.len();
@@ -252,31 +490,41 @@ impl<'buf> CentralDirectory<'buf> {
             )));
         }
 
-        let res = Self { buf };
+        let mut res = Self {
+            buf,
+            file_size: 0,
+            compressed_file_size: 0,
+            local_file_header_offset: 0,
+        };
         if len < res.size() {
             return Err(Error::ZipFileParseError(format!(
                 "Buffer length `{}` is too short! file name length is `{}`, extra field length is `{}`, file comment length is `{}`",
                 len, res.file_comment_length(), res.extra_field_length(), res.file_comment_length()
             )));
         }
-        if res.disk_number_start() != 0 {
-            return Err(Error::ZipFileParseError(format!(
-                "Disk number must be `0`, but get `{}`. (not support zip64 now)",
-                res.disk_number_start()
-            )));
-        }
-        if res.compression_method() != 0 {
+        res.parse_zip64_extended_information()?;
+
+        if res.general_purpose_bit_flag() & 1 != 0 {
             return Err(Error::ZipFileParseError(
-                "Only support `Store` compression method".into(),
+                "Unsupported encrypted zip file.".into(),
             ));
         }
-        let file_size = res.uncompressed_size() as usize;
-        if res.compressed_size() as usize != file_size {
-            return Err(Error::ZipFileParseError(format!(
-                "Compressed size `{}` != Uncompressed size `{}`",
-                res.compressed_size(),
-                file_size
-            )));
+        match res.compression_method() {
+            Self::COMPRESSION_METHOD_STORE => {
+                if res.compressed_file_size != res.file_size {
+                    return Err(Error::ZipFileParseError(format!(
+                        "Compressed size `{}` != Uncompressed size `{}`",
+                        res.compressed_file_size, res.file_size
+                    )));
+                }
+            }
+            Self::COMPRESSION_METHOD_DEFLATE => {}
+            m => {
+                return Err(Error::ZipFileParseError(format!(
+                    "Only support `Store` and `Deflate` compression method, but got `{}`",
+                    m
+                )));
+            }
         }
 
         Ok(res)
@@ -387,6 +635,83 @@ impl<'buf> CentralDirectory<'buf> {
             + self.extra_field_length() as usize
             + self.file_comment_length() as usize
     }
+
+    /*
+      4.5.3 -Zip64 Extended Information Extra Field (0x0001):
+        Original Size                 8 bytes
+        Compressed Size               8 bytes
+        Relative Header Offset        8 bytes
+        Disk Start Number             4 bytes
+
+      The fields only appear if the corresponding header fields are set to 0xFFFF or 0xFFFFFFFF.
+    */
+    fn parse_zip64_extended_information(&mut self) -> Result<(), Error> {
+        let mut file_size = self.uncompressed_size() as u64;
+        let mut compressed_file_size = self.compressed_size() as u64;
+        let mut local_file_header_offset = self.relative_offset_of_local_header() as u64;
+        let mut disk_number_start = self.disk_number_start() as u32;
+
+        if file_size == u32::MAX as u64
+            || compressed_file_size == u32::MAX as u64
+            || local_file_header_offset == u32::MAX as u64
+            || disk_number_start == u16::MAX as u32
+        {
+            let extra_field = self.extra_field();
+            let mut pos = 0;
+            let mut found = false;
+            while pos + 4 <= extra_field.len() {
+                let header_id = read_le_u16!(extra_field, pos);
+                let data_size = read_le_u16!(extra_field, pos + 2) as usize;
+                let data_start = pos + 4;
+                pos = data_start + data_size;
+                if pos > extra_field.len() {
+                    break;
+                }
+                if header_id != Self::ZIP64_EXTENDED_INFORMATION_HEADER_ID {
+                    continue;
+                }
+
+                let data = &extra_field[data_start..pos];
+                let mut offset = 0;
+                macro_rules! read_field {
+                    ( $field:ident, $max:expr, $read:ident, $size:expr ) => {
+                        if $field == $max {
+                            if offset + $size > data.len() {
+                                return Err(Error::ZipFileParseError(
+                                    "Zip64 extended information extra field is too short".into(),
+                                ));
+                            }
+                            $field = $read!(data, offset) as _;
+                            offset += $size;
+                        }
+                    };
+                }
+                read_field!(file_size, u32::MAX as u64, read_le_u64, 8);
+                read_field!(compressed_file_size, u32::MAX as u64, read_le_u64, 8);
+                read_field!(local_file_header_offset, u32::MAX as u64, read_le_u64, 8);
+                read_field!(disk_number_start, u16::MAX as u32, read_le_u32, 4);
+                found = true;
+                break;
+            }
+            if !found {
+                return Err(Error::ZipFileParseError(
+                    "Cannot find zip64 extended information extra field".into(),
+                ));
+            }
+        }
+
+        if disk_number_start != 0 {
+            return Err(Error::ZipFileParseError(format!(
+                "Disk number must be `0`, but get `{}`.",
+                disk_number_start
+            )));
+        }
+        self.file_size = to_usize(file_size, "Uncompressed size")?;
+        self.compressed_file_size = to_usize(compressed_file_size, "Compressed size")?;
+        self.local_file_header_offset =
+            to_usize(local_file_header_offset, "Local file header offset")?;
+        Ok(())
+    }
 }
 
 /*
@@ -520,20 +845,56 @@ impl<'buf> LocalFileHeader<'buf> {
     }
 }
 
+enum ZipFileContent {
+    // stored file, zero-copy
+    Stored(std::ops::Range<usize>),
+    // deflated file, inflated at the first access
+    Deflated {
+        range: std::ops::Range<usize>,
+        file_size: usize,
+        data: OnceLock<Vec<u8>>,
+    },
+}
+
 pub(crate) struct ZipFiles<'buf> {
     buf: &'buf [u8],
     end_of_central_directory_record: EndOfCentralDirectoryRecord<'buf>,
-    files: HashMap<Cow<'buf, str>, std::ops::Range<usize>>,
+    files: HashMap<Cow<'buf, str>, ZipFileContent>,
 }
 
 impl<'buf> ZipFiles<'buf> {
+    /// Parse the zip archive which may be appended to other data, such as the associated files of TFLite models.
+    /// Return [`None`] if there is no zip archive. Other data may contain the end of central directory signature by
+    /// chance, so the errors are only returned if the central directory starts with the zip magic.
     #[inline]
     pub fn try_new(buf: &'buf [u8]) -> Result<Option<Self>, Error> {
         let start_pos = EndOfCentralDirectoryRecord::try_find_start_pos(buf);
         if start_pos == buf.len() {
             return Ok(None);
         }
-        Self::new_with_start_pos(buf, start_pos).map(|z| Some(z))
+        match Self::new_with_start_pos(buf, start_pos) {
+            Ok(z) => Ok(Some(z)),
+            Err(e) if Self::has_central_directory_magic(buf, start_pos) => Err(e),
+            Err(_) => Ok(None),
+        }
+    }
+
+    // check the magic of the central directory (or the zip64 end of central directory locator)
+    // which is pointed by the end of central directory record.
+    fn has_central_directory_magic(buf: &'buf [u8], start_pos: usize) -> bool {
+        let r = match EndOfCentralDirectoryRecord::new_with_start_pos(buf, start_pos) {
+            Ok(r) => r,
+            Err(_) => return false,
+        };
+        if r.is_zip64() {
+            return start_pos
+                .checked_sub(Zip64EndOfCentralDirectoryLocator::SIZE)
+                .and_then(|p| buf.get(p..p + 4))
+                == Some(Zip64EndOfCentralDirectoryLocator::HEAD_SIGNATURE);
+        }
+        let offset = r.offset_of_start_of_central_directory() as usize;
+        r.total_number_of_central_directory_records() == 0
+            || buf.get(offset..offset.saturating_add(4)) == Some(CentralDirectory::HEAD_SIGNATURE)
     }
 
     #[inline]
@@ -550,21 +911,87 @@ impl<'buf> ZipFiles<'buf> {
     #[inline]
     fn new_with_start_pos(buf: &'buf [u8], start_pos: usize) -> Result<Self, Error> {
         let r = EndOfCentralDirectoryRecord::new_with_start_pos(buf, start_pos)?;
-        let num = r.total_number_of_central_directory_records();
-        let mut start = r.offset_of_start_of_central_directory() as usize;
-        let end = r.size_of_central_directory() as usize + start;
+        let (num, mut start, end) = if r.is_zip64() {
+            let locator = Zip64EndOfCentralDirectoryLocator::new_with_end_of_central_directory_pos(
+                buf, start_pos,
+            )?;
+            let zip64_record = Zip64EndOfCentralDirectoryRecord::new_with_start_pos(
+                buf,
+                to_usize(
+                    locator.offset_of_zip64_end_of_central_directory(),
+                    "Zip64 end of central directory offset",
+                )?,
+            )?;
+            let start = to_usize(
+                zip64_record.offset_of_start_of_central_directory(),
+                "Central directory offset",
+            )?;
+            let size = to_usize(
+                zip64_record.size_of_central_directory(),
+                "Central directory size",
+            )?;
+            (
+                zip64_record.total_number_of_central_directory_records(),
+                start,
+                start.checked_add(size).ok_or_else(|| {
+                    Error::ZipFileParseError("Central directory information error".into())
+                })?,
+            )
+        } else {
+            let start = r.offset_of_start_of_central_directory() as usize;
+            (
+                r.total_number_of_central_directory_records() as u64,
+                start,
+                r.size_of_central_directory() as usize + start,
+            )
+        };
+
+        if end > buf.len() {
+            return Err(Error::ZipFileParseError(
+                "Central directory is out of range".into(),
+            ));
+        }
 
         let mut files = HashMap::new();
         for _ in 0..num {
+            if start >= end {
+                return Err(Error::ZipFileParseError(format!(
+                    "Central directory records are less than `{}`",
+                    num
+                )));
+            }
             let c = CentralDirectory::new(&buf[start..end])?;
             let filename = String::from_utf8_lossy(c.file_name());
-            let local_file_head_offset = c.relative_offset_of_local_header() as usize;
+            let local_file_head_offset = c.local_file_header_offset;
+            if local_file_head_offset >= buf.len() {
+                return Err(Error::ZipFileParseError(format!(
+                    "Local file header offset `{}` is out of range",
+                    local_file_head_offset
+                )));
+            }
             let local_file_head = LocalFileHeader::new(&buf[local_file_head_offset..])?;
             let file_offset = local_file_head_offset + local_file_head.size();
-            files.insert(
-                filename,
-                file_offset..(file_offset + c.uncompressed_size() as usize),
-            );
+            let file_end = match file_offset.checked_add(c.compressed_file_size) {
+                Some(e) if e <= buf.len() => e,
+                _ => {
+                    return Err(Error::ZipFileParseError(format!(
+                        "File `{}` data is out of range",
+                        filename
+                    )));
+                }
+            };
+
+            let content = if c.compression_method() == CentralDirectory::COMPRESSION_METHOD_DEFLATE
+            {
+                ZipFileContent::Deflated {
+                    range: file_offset..file_end,
+                    file_size: c.file_size,
+                    data: OnceLock::new(),
+                }
+            } else {
+                ZipFileContent::Stored(file_offset..file_end)
+            };
+            files.insert(filename, content);
 
             start += c.size();
         }
@@ -575,24 +1002,200 @@ impl<'buf> ZipFiles<'buf> {
         })
     }
 
+    fn inflate(
+        &self,
+        name: &str,
+        range: &std::ops::Range<usize>,
+        size: usize,
+    ) -> Result<Vec<u8>, Error> {
+        let data =
+            miniz_oxide::inflate::decompress_to_vec_with_limit(&self.buf[range.clone()], size)
+                .map_err(|e| {
+                    Error::ZipFileParseError(format!("Cannot inflate file `{}`: `{:?}`", name, e))
+                })?;
+        if data.len() != size {
+            return Err(Error::ZipFileParseError(format!(
+                "Inflated size `{}` != Uncompressed size `{}` for file `{}`",
+                data.len(),
+                size,
+                name
+            )));
+        }
+        Ok(data)
+    }
+
+    /// Get the file content. Deflated files are inflated at the first access.
+    pub fn get_file(&self, name: &str) -> Result<Option<&[u8]>, Error> {
+        match self.files.get(name) {
+            None => Ok(None),
+            Some(ZipFileContent::Stored(r)) => Ok(Some(&self.buf[r.clone()])),
+            Some(ZipFileContent::Deflated {
+                range,
+                file_size,
+                data,
+            }) => {
+                if let Some(d) = data.get() {
+                    return Ok(Some(d.as_slice()));
+                }
+                let d = self.inflate(name, range, *file_size)?;
+                Ok(Some(data.get_or_init(|| d).as_slice()))
+            }
+        }
+    }
+
+    /// Get the file content with lifetime `'buf`. Stored files are zero-copy, deflated files will be copied.
     #[inline(always)]
-    pub fn get_file(&self, name: &str) -> Option<&'buf [u8]> {
-        self.files.get(name).map(|r| &self.buf[r.clone()])
+    pub fn get_file_cow(&self, name: &str) -> Result<Option<Cow<'buf, [u8]>>, Error> {
+        match self.files.get(name) {
+            Some(ZipFileContent::Stored(r)) => Ok(Some(Cow::Borrowed(&self.buf[r.clone()]))),
+            _ => Ok(self.get_file(name)?.map(|d| Cow::Owned(d.to_vec()))),
+        }
     }
 
+    /// Get the file offset in buffer. Return [`None`] if the file does not exist or is not stored.
     #[inline(always)]
     pub fn get_file_offset(&self, name: &str) -> Option<std::ops::Range<usize>> {
-        self.files.get(name).cloned()
+        match self.files.get(name) {
+            Some(ZipFileContent::Stored(r)) => Some(r.clone()),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(|k| k.as_ref())
+    }
+}
+
+// CRC-32 (IEEE 802.3) lookup table.
+const CRC_32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+};
+
+#[inline]
+fn crc_32(data: &[u8]) -> u32 {
+    let mut c = u32::MAX;
+    for b in data {
+        c = CRC_32_TABLE[((c ^ *b as u32) & 0xff) as usize] ^ (c >> 8);
+    }
+    !c
+}
+
+/// Append files to the end of `buf` as a zip archive without compression.
+/// The offsets in the archive are relative to the start of `buf`, so the archive can be appended to other data,
+/// such as TFLite model. (``ZipFiles`` can parse it from the whole buffer)
+pub(crate) fn append_stored_zip_files(
+    buf: &mut Vec<u8>,
+    files: &[(&str, &[u8])],
+) -> Result<(), Error> {
+    // MS-DOS date: 1980-01-01
+    const LAST_MOD_FILE_DATE: u16 = (1 << 5) | 1;
+    const VERSION: u16 = 20;
+    // file names are encoded using UTF-8
+    const GENERAL_PURPOSE_BIT_FLAG: u16 = 1 << 11;
+
+    if files.len() >= u16::MAX as usize {
+        return Err(Error::ZipFileParseError(format!(
+            "Too many files to write: `{}`",
+            files.len()
+        )));
+    }
+
+    let mut central_directory = Vec::new();
+    for (name, content) in files {
+        let local_file_header_offset = buf.len();
+        if local_file_header_offset + content.len() >= u32::MAX as usize
+            || name.len() > u16::MAX as usize
+        {
+            return Err(Error::ZipFileParseError(format!(
+                "File `{}` is too large to write",
+                name
+            )));
+        }
+        let crc = crc_32(content);
+
+        buf.extend_from_slice(LocalFileHeader::HEAD_MAGIC);
+        buf.extend_from_slice(&VERSION.to_le_bytes());
+        buf.extend_from_slice(&GENERAL_PURPOSE_BIT_FLAG.to_le_bytes());
+        buf.extend_from_slice(&CentralDirectory::COMPRESSION_METHOD_STORE.to_le_bytes());
+        buf.extend_from_slice(&0u16.to_le_bytes());
+        buf.extend_from_slice(&LAST_MOD_FILE_DATE.to_le_bytes());
+        buf.extend_from_slice(&crc.to_le_bytes());
+        buf.extend_from_slice(&(content.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&(content.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        buf.extend_from_slice(&0u16.to_le_bytes());
+        buf.extend_from_slice(name.as_bytes());
+        buf.extend_from_slice(content);
+
+        central_directory.extend_from_slice(CentralDirectory::HEAD_SIGNATURE);
+        central_directory.extend_from_slice(&VERSION.to_le_bytes());
+        central_directory.extend_from_slice(&VERSION.to_le_bytes());
+        central_directory.extend_from_slice(&GENERAL_PURPOSE_BIT_FLAG.to_le_bytes());
+        central_directory
+            .extend_from_slice(&CentralDirectory::COMPRESSION_METHOD_STORE.to_le_bytes());
+        central_directory.extend_from_slice(&0u16.to_le_bytes());
+        central_directory.extend_from_slice(&LAST_MOD_FILE_DATE.to_le_bytes());
+        central_directory.extend_from_slice(&crc.to_le_bytes());
+        central_directory.extend_from_slice(&(content.len() as u32).to_le_bytes());
+        central_directory.extend_from_slice(&(content.len() as u32).to_le_bytes());
+        central_directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        // extra field length, file comment length, disk number start, internal file attributes
+        central_directory.extend_from_slice(&[0; 8]);
+        // external file attributes
+        central_directory.extend_from_slice(&0u32.to_le_bytes());
+        central_directory.extend_from_slice(&(local_file_header_offset as u32).to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
     }
+
+    let central_directory_offset = buf.len();
+    if central_directory_offset + central_directory.len() >= u32::MAX as usize {
+        return Err(Error::ZipFileParseError(
+            "Zip file is too large to write".into(),
+        ));
+    }
+    buf.extend_from_slice(central_directory.as_slice());
+
+    buf.extend_from_slice(EndOfCentralDirectoryRecord::HEAD_SIGNATURE);
+    // number of this disk, disk where central directory starts
+    buf.extend_from_slice(&[0; 4]);
+    buf.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    buf.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    buf.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&(central_directory_offset as u32).to_le_bytes());
+    // comment length
+    buf.extend_from_slice(&0u16.to_le_bytes());
+    Ok(())
 }
 
 #[cfg(test)]
 mod test {
     use crate::model::zip::{
-        CentralDirectory, EndOfCentralDirectoryRecord, LocalFileHeader, ZipFiles,
+        append_stored_zip_files, crc_32, CentralDirectory, EndOfCentralDirectoryRecord,
+        LocalFileHeader, Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord,
+        ZipFiles,
     };
+    use std::borrow::Cow;
 
     const ZIP_PATH: &'static str = "assets/testdata/test.zip";
+    const DEFLATE_ZIP_PATH: &'static str = "assets/testdata/test_deflate.zip";
+    const ZIP64_PATH: &'static str = "assets/testdata/test_zip64.zip";
     const MODEL_PATH: &'static str =
         "assets/models/image_classification/efficientnet_lite0_fp32.tflite";
 
@@ -609,7 +1212,7 @@ mod test {
         assert_eq!(r.size_of_central_directory(), 150);
         assert_eq!(r.offset_of_start_of_central_directory(), 130);
         assert_eq!(r.comment_length(), 0);
-        assert_eq!(r.comment(), []);
+        assert_eq!(r.comment(), &[] as &[u8]);
     }
 
     #[test]
@@ -647,7 +1250,178 @@ mod test {
         let buf = std::fs::read(ZIP_PATH).unwrap();
         let zip_file = ZipFiles::new(buf.as_slice()).unwrap();
         assert_eq!(zip_file.files.len(), 2);
-        assert_eq!(zip_file.get_file("1.txt").unwrap(), &[49, 10]);
-        assert_eq!(zip_file.get_file("2.txt").unwrap(), &[50, 10]);
+        assert_eq!(zip_file.get_file("1.txt").unwrap().unwrap(), &[49, 10]);
+        assert_eq!(zip_file.get_file("2.txt").unwrap().unwrap(), &[50, 10]);
+    }
+
+    #[test]
+    fn test_deflate_zip_file() {
+        let buf = std::fs::read(DEFLATE_ZIP_PATH).unwrap();
+        let zip_file = ZipFiles::new(buf.as_slice()).unwrap();
+        assert_eq!(zip_file.files.len(), 2);
+        // 1.txt is deflated, 2.txt is stored
+        assert_eq!(
+            zip_file.get_file("1.txt").unwrap().unwrap(),
+            "1\n".repeat(100).as_bytes()
+        );
+        assert_eq!(zip_file.get_file("2.txt").unwrap().unwrap(), &[50, 10]);
+        assert!(zip_file.get_file_offset("1.txt").is_none());
+        assert!(zip_file.get_file_offset("2.txt").is_some());
+        assert!(matches!(
+            zip_file.get_file_cow("1.txt"),
+            Ok(Some(Cow::Owned(_)))
+        ));
+        assert!(matches!(
+            zip_file.get_file_cow("2.txt"),
+            Ok(Some(Cow::Borrowed(_)))
+        ));
+        assert!(zip_file.get_file("3.txt").unwrap().is_none());
+
+        // corrupted deflate stream, which is between 1.txt local file header and 2.txt local file header
+        let end = zip_file.get_file_offset("2.txt").unwrap().start - 30 - "2.txt".len();
+        drop(zip_file);
+        let mut buf = buf;
+        buf[30 + "1.txt".len()..end].fill(0xff);
+        // deflated files are inflated at the first access
+        let zip_file = ZipFiles::new(buf.as_slice()).unwrap();
+        assert!(zip_file.get_file("1.txt").is_err());
+        assert!(zip_file.get_file_cow("1.txt").is_err());
+        assert_eq!(zip_file.get_file("2.txt").unwrap().unwrap(), &[50, 10]);
+    }
+
+    #[test]
+    fn test_zip64_end_of_central_directory_record() {
+        let buf = std::fs::read(ZIP64_PATH).unwrap();
+        let start_pos = EndOfCentralDirectoryRecord::try_find_start_pos(buf.as_slice());
+        let r = EndOfCentralDirectoryRecord::new_with_start_pos(buf.as_slice(), start_pos).unwrap();
+        assert!(r.is_zip64());
+        assert_eq!(r.total_number_of_central_directory_records(), u16::MAX);
+        assert_eq!(r.size_of_central_directory(), u32::MAX);
+        assert_eq!(r.offset_of_start_of_central_directory(), u32::MAX);
+
+        let locator = Zip64EndOfCentralDirectoryLocator::new_with_end_of_central_directory_pos(
+            buf.as_slice(),
+            start_pos,
+        )
+        .unwrap();
+        assert_eq!(locator.offset_of_zip64_end_of_central_directory(), 269);
+        assert_eq!(locator.total_number_of_disks(), 1);
+
+        let r = Zip64EndOfCentralDirectoryRecord::new_with_start_pos(
+            buf.as_slice(),
+            locator.offset_of_zip64_end_of_central_directory() as usize,
+        )
+        .unwrap();
+        assert_eq!(r.number_of_this_disk(), 0);
+        assert_eq!(r.disk_where_central_directory_starts(), 0);
+        assert_eq!(r.number_of_central_directory_records_on_this_disk(), 2);
+        assert_eq!(r.total_number_of_central_directory_records(), 2);
+        assert_eq!(r.size_of_central_directory(), 150);
+        assert_eq!(r.offset_of_start_of_central_directory(), 119);
+
+        let c = CentralDirectory::new(&buf[119..269]).unwrap();
+        assert_eq!(String::from_utf8_lossy(c.file_name()), "1.txt");
+        assert_eq!(c.uncompressed_size(), u32::MAX);
+        assert_eq!(c.relative_offset_of_local_header(), 0);
+        assert_eq!(c.file_size, 2);
+        assert_eq!(c.compressed_file_size, 2);
+        assert_eq!(c.local_file_header_offset, 0);
+
+        let c = CentralDirectory::new(&buf[119 + c.size()..269]).unwrap();
+        assert_eq!(String::from_utf8_lossy(c.file_name()), "2.txt");
+        assert_eq!(c.compressed_size(), u32::MAX);
+        assert_eq!(c.relative_offset_of_local_header(), u32::MAX);
+        assert_eq!(c.file_size, 200);
+        assert_eq!(c.compressed_file_size, 7);
+        assert_eq!(c.local_file_header_offset, 57);
+    }
+
+    #[test]
+    fn test_zip64_file() {
+        let buf = std::fs::read(ZIP64_PATH).unwrap();
+        let zip_file = ZipFiles::new(buf.as_slice()).unwrap();
+        assert_eq!(zip_file.files.len(), 2);
+        assert_eq!(zip_file.get_file("1.txt").unwrap().unwrap(), &[49, 10]);
+        assert_eq!(
+            zip_file.get_file("2.txt").unwrap().unwrap(),
+            "2\n".repeat(100).as_bytes()
+        );
+        assert!(zip_file.get_file_offset("1.txt").is_some());
+        assert!(zip_file.get_file_offset("2.txt").is_none());
+
+        // wrong offsets must be reported as errors rather than panic.
+        let mut buf_with_prefix = vec![0u8; 16];
+        buf_with_prefix.extend_from_slice(buf.as_slice());
+        assert!(ZipFiles::new(buf_with_prefix.as_slice()).is_err());
+
+        // missing zip64 end of central directory locator
+        let mut buf = buf;
+        let start_pos = EndOfCentralDirectoryRecord::try_find_start_pos(buf.as_slice());
+        buf[start_pos - 20..start_pos - 16].fill(0);
+        assert!(ZipFiles::new(buf.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_zip64_file_size_overflow() {
+        let mut buf = std::fs::read(ZIP64_PATH).unwrap();
+        // the zip64 extended information of 2.txt: uncompressed size, compressed size, relative header offset
+        let c = CentralDirectory::new(&buf[119..269]).unwrap();
+        let start = 119 + c.size();
+        let c = CentralDirectory::new(&buf[start..269]).unwrap();
+        let extra_field_start =
+            start + CentralDirectory::FILE_NAME_POS + c.file_name_length() as usize;
+        assert_eq!(
+            read_le_u16!(buf, extra_field_start),
+            CentralDirectory::ZIP64_EXTENDED_INFORMATION_HEADER_ID
+        );
+        let compressed_size_pos = extra_field_start + 4 + 8;
+        buf[compressed_size_pos..compressed_size_pos + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(ZipFiles::new(buf.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_try_new() {
+        let mut buf = vec![1u8; 17];
+        append_stored_zip_files(&mut buf, &[("1.txt", b"1\n")]).unwrap();
+        let zip_file = ZipFiles::try_new(buf.as_slice()).unwrap().unwrap();
+        assert_eq!(zip_file.get_file("1.txt").unwrap().unwrap(), b"1\n");
+
+        // no zip archive
+        assert!(ZipFiles::try_new(&[1u8; 64]).unwrap().is_none());
+
+        // the end of central directory signature appears in other data by chance
+        let mut other = vec![1u8; 64];
+        other[10..14].copy_from_slice(EndOfCentralDirectoryRecord::HEAD_SIGNATURE);
+        assert!(ZipFiles::try_new(other.as_slice()).unwrap().is_none());
+
+        // corrupted zip archive must be reported
+        let local_file_header_pos = 17;
+        buf[local_file_header_pos..local_file_header_pos + 4].fill(0);
+        assert!(ZipFiles::try_new(buf.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_append_stored_zip_files() {
+        let buf = std::fs::read(ZIP_PATH).unwrap();
+        let r = EndOfCentralDirectoryRecord::new(buf.as_slice()).unwrap();
+        let c = CentralDirectory::new(&buf[r.offset_of_start_of_central_directory() as usize..])
+            .unwrap();
+        assert_eq!(crc_32(&[49, 10]), c.crc_32());
+
+        // append to other data
+        let mut buf = vec![1u8; 17];
+        let files: [(&str, &[u8]); 3] = [("1.txt", b"1\n"), ("2.txt", b"2\n"), ("空.txt", b"")];
+        append_stored_zip_files(&mut buf, &files).unwrap();
+        assert_eq!(&buf[..17], &[1u8; 17]);
+        let zip_file = ZipFiles::new(buf.as_slice()).unwrap();
+        assert_eq!(zip_file.files.len(), 3);
+        for (name, content) in files {
+            assert_eq!(zip_file.get_file(name).unwrap().unwrap(), content);
+            assert!(zip_file.get_file_offset(name).is_some());
+        }
+
+        let mut buf = Vec::new();
+        append_stored_zip_files(&mut buf, &[]).unwrap();
+        assert_eq!(ZipFiles::new(buf.as_slice()).unwrap().files.len(), 0);
     }
 }
@@ -8,6 +8,7 @@ use std::fmt::{Display, Formatter};
 ///
 /// [1]: https://www.tensorflow.org/lite/convert/metadata
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Category {
     /// The index of the category in the classification model output.
     pub index: u32,
@@ -3,6 +3,7 @@ use std::fmt::{Display, Formatter};
 
 /// Defines classification results for a given classifier head.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Classifications {
     /// The index of the classifier head (i.e. output tensor) these categories
     /// refer to. This is useful for multi-head models.
@@ -19,6 +20,7 @@ pub struct Classifications {
 
 /// Defines classification results of a model.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClassificationResult {
     /// The classification results for each head of the model.
     pub classifications: Vec<Classifications>,
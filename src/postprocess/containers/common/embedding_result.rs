@@ -3,6 +3,7 @@
 /// One and only one of the two 'float_embedding' and 'quantized_embedding' will
 /// contain data, based on whether or not the embedder was configured to perform scalar quantization.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Embedding {
     /// The index of the embedder head (i.e. output tensor) this embedding comes from. This is useful for multi-head models.
     pub head_index: usize,
@@ -20,6 +21,7 @@ pub struct Embedding {
 }
 
 /// Defines embedding results of a model.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EmbeddingResult {
     /// The embedding results for each head of the model.
     pub embeddings: Vec<Embedding>,
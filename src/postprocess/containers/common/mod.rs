@@ -7,7 +7,9 @@ mod results_iter_impl;
 mod category;
 mod classification_result;
 mod embedding_result;
+mod output_tensors;
 
 pub use category::*;
 pub use classification_result::*;
 pub use embedding_result::*;
+pub use output_tensors::*;
@@ -0,0 +1,241 @@
+use crate::postprocess::ops::Dequantize;
+use crate::postprocess::QuantizationParameters;
+use crate::{Error, TensorType};
+
+/// Data of a model output tensor.
+/// Quantized tensors are dequantized to ```F32```, and ```F16``` tensors are converted to ```F32```.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TensorData {
+    F32(Vec<f32>),
+    U8(Vec<u8>),
+    I32(Vec<i32>),
+}
+
+impl Default for TensorData {
+    #[inline(always)]
+    fn default() -> Self {
+        TensorData::F32(Vec::new())
+    }
+}
+
+impl TensorData {
+    /// Get the number of elements.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        match self {
+            TensorData::F32(v) => v.len(),
+            TensorData::U8(v) => v.len(),
+            TensorData::I32(v) => v.len(),
+        }
+    }
+
+    /// Return true if the tensor has no elements.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the data as ```f32``` slice, return ```None``` if the data type is not ```F32```.
+    #[inline(always)]
+    pub fn as_f32(&self) -> Option<&[f32]> {
+        match self {
+            TensorData::F32(v) => Some(v.as_slice()),
+            _ => None,
+        }
+    }
+
+    /// Get the data as ```u8``` slice, return ```None``` if the data type is not ```U8```.
+    #[inline(always)]
+    pub fn as_u8(&self) -> Option<&[u8]> {
+        match self {
+            TensorData::U8(v) => Some(v.as_slice()),
+            _ => None,
+        }
+    }
+
+    /// Get the data as ```i32``` slice, return ```None``` if the data type is not ```I32```.
+    #[inline(always)]
+    pub fn as_i32(&self) -> Option<&[i32]> {
+        match self {
+            TensorData::I32(v) => Some(v.as_slice()),
+            _ => None,
+        }
+    }
+
+    /// Convert the raw output bytes to typed data, reuse the allocated buffer if the type is not changed.
+    /// Return an error if the tensor type is not supported.
+    pub(crate) fn copy_from_bytes(
+        &mut self,
+        bytes: &[u8],
+        tensor_type: TensorType,
+        quantization_parameters: Option<QuantizationParameters>,
+    ) -> Result<(), Error> {
+        match (tensor_type, quantization_parameters) {
+            (TensorType::U8, Some(q)) => {
+                bytes.dequantize_to_buf(q, self.f32_buf(bytes.len()));
+            }
+            (TensorType::U8, None) => {
+                if !matches!(self, TensorData::U8(_)) {
+                    *self = TensorData::U8(Vec::new());
+                }
+                if let TensorData::U8(v) = self {
+                    v.clear();
+                    v.extend_from_slice(bytes);
+                }
+            }
+            (TensorType::F32, _) => {
+                let out = self.f32_buf(bytes.len() / 4);
+                for (o, b) in out.iter_mut().zip(bytes.chunks_exact(4)) {
+                    *o = f32::from_ne_bytes([b[0], b[1], b[2], b[3]]);
+                }
+            }
+            (TensorType::F16, _) => {
+                let out = self.f32_buf(bytes.len() / 2);
+                for (o, b) in out.iter_mut().zip(bytes.chunks_exact(2)) {
+                    *o = f16_to_f32(u16::from_ne_bytes([b[0], b[1]]));
+                }
+            }
+            (TensorType::I32, _) => {
+                if !matches!(self, TensorData::I32(_)) {
+                    *self = TensorData::I32(Vec::new());
+                }
+                if let TensorData::I32(v) = self {
+                    v.clear();
+                    v.extend(
+                        bytes
+                            .chunks_exact(4)
+                            .map(|b| i32::from_ne_bytes([b[0], b[1], b[2], b[3]])),
+                    );
+                }
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                return Err(Error::ModelInconsistentError(format!(
+                    "Unsupported output tensor type `{:?}`",
+                    tensor_type
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn f32_buf(&mut self, len: usize) -> &mut [f32] {
+        if !matches!(self, TensorData::F32(_)) {
+            *self = TensorData::F32(Vec::new());
+        }
+        match self {
+            TensorData::F32(v) => {
+                v.resize(len, 0.);
+                v.as_mut_slice()
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[inline(always)]
+fn f16_to_f32(h: u16) -> f32 {
+    let sign = ((h >> 15) as u32) << 31;
+    let exp = ((h >> 10) & 0x1f) as u32;
+    let mant = (h & 0x3ff) as u32;
+    let bits = if exp == 0 {
+        if mant == 0 {
+            sign
+        } else {
+            // subnormal number, normalize it
+            let mut e = 127 - 15 + 1;
+            let mut m = mant;
+            while m & 0x400 == 0 {
+                m <<= 1;
+                e -= 1;
+            }
+            sign | (e << 23) | ((m & 0x3ff) << 13)
+        }
+    } else if exp == 0x1f {
+        // inf or nan
+        sign | 0x7f80_0000 | (mant << 13)
+    } else {
+        sign | ((exp + 127 - 15) << 23) | (mant << 13)
+    };
+    f32::from_bits(bits)
+}
+
+/// A model output tensor.
+#[derive(Debug, Clone, Default)]
+pub struct OutputTensor {
+    /// The optional name of the output tensor, as provided in the model if present.
+    pub name: Option<String>,
+    /// The shape of the output tensor.
+    pub shape: Vec<usize>,
+    /// The tensor data.
+    pub data: TensorData,
+}
+
+/// All output tensors of one model inference.
+#[derive(Debug, Clone, Default)]
+pub struct OutputTensors {
+    /// The output tensors, in the same order as model outputs.
+    pub tensors: Vec<OutputTensor>,
+
+    /// The optional timestamp (in milliseconds) of the input data, such as the video frame or audio chunk.
+    pub timestamp_ms: Option<u64>,
+}
+
+impl OutputTensors {
+    /// Get the output tensor at `index`.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<&OutputTensor> {
+        self.tensors.get(index)
+    }
+
+    /// Get the output tensor by name.
+    #[inline(always)]
+    pub fn get_by_name(&self, name: &str) -> Option<&OutputTensor> {
+        self.tensors
+            .iter()
+            .find(|t| t.name.as_ref().is_some_and(|n| n == name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tensor_data() {
+        let mut data = TensorData::default();
+        let bytes: Vec<u8> = [1f32, -2.5].iter().flat_map(|f| f.to_ne_bytes()).collect();
+        data.copy_from_bytes(&bytes, TensorType::F32, None).unwrap();
+        assert_eq!(data.as_f32(), Some([1f32, -2.5].as_slice()));
+
+        data.copy_from_bytes(&[0, 10, 255], TensorType::U8, None)
+            .unwrap();
+        assert_eq!(data.as_u8(), Some([0u8, 10, 255].as_slice()));
+        assert!(data.as_f32().is_none());
+
+        let q = QuantizationParameters {
+            scale: 0.5,
+            zero_point: 10,
+        };
+        data.copy_from_bytes(&[0, 10, 255], TensorType::U8, Some(q))
+            .unwrap();
+        assert_eq!(data.as_f32(), Some([-5f32, 0., 122.5].as_slice()));
+
+        let bytes: Vec<u8> = [7i32, -1].iter().flat_map(|i| i.to_ne_bytes()).collect();
+        data.copy_from_bytes(&bytes, TensorType::I32, None).unwrap();
+        assert_eq!(data.as_i32(), Some([7i32, -1].as_slice()));
+        assert_eq!(data.len(), 2);
+
+        // 1.0, -2.0, 65504 (max), 2^-24 (min subnormal), 0, inf
+        let bytes: Vec<u8> = [0x3c00u16, 0xc000, 0x7bff, 0x0001, 0x0000, 0x7c00]
+            .iter()
+            .flat_map(|h| h.to_ne_bytes())
+            .collect();
+        data.copy_from_bytes(&bytes, TensorType::F16, None).unwrap();
+        assert_eq!(
+            data.as_f32(),
+            Some([1f32, -2., 65504., 2f32.powi(-24), 0., f32::INFINITY].as_slice())
+        );
+    }
+}
@@ -10,6 +10,11 @@ mod audio;
 #[cfg(feature = "audio")]
 pub use audio::*;
 
+#[cfg(feature = "text")]
+mod text;
+#[cfg(feature = "text")]
+pub use text::*;
+
 #[cfg(feature = "vision")]
 mod vision;
 #[cfg(feature = "vision")]
@@ -0,0 +1,58 @@
+use crate::postprocess::ClassificationResult;
+use std::fmt::{Display, Formatter};
+
+/// A language code and its probability.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LanguagePrediction {
+    /// An i18n language / locale code, e.g. "en" for English, "uz" for Uzbek,
+    /// "ja"-Latn for Japanese (romaji).
+    pub language_code: String,
+
+    /// The probability of the language, in \[0,1\].
+    pub probability: f32,
+}
+
+/// Defines language detection results of a model.
+/// The predictions are sorted by descending probability.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LanguageDetectorResult {
+    /// The predicted languages and their probabilities.
+    pub predictions: Vec<LanguagePrediction>,
+}
+
+impl From<ClassificationResult> for LanguageDetectorResult {
+    fn from(classification_result: ClassificationResult) -> Self {
+        let predictions = match classification_result.classifications.into_iter().next() {
+            Some(c) => c
+                .categories
+                .into_iter()
+                .map(|category| LanguagePrediction {
+                    language_code: category
+                        .category_name
+                        .unwrap_or_else(|| category.index.to_string()),
+                    probability: category.score,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        Self { predictions }
+    }
+}
+
+impl Display for LanguageDetectorResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "LanguageDetectorResult:")?;
+        if self.predictions.is_empty() {
+            return writeln!(f, "  No Prediction");
+        }
+        for i in 0..self.predictions.len() {
+            let p = self.predictions.get(i).unwrap();
+            writeln!(f, "  Prediction #{}:", i)?;
+            writeln!(f, "    Language code: \"{}\"", p.language_code)?;
+            writeln!(f, "    Probability:   {}", p.probability)?;
+        }
+        Ok(())
+    }
+}
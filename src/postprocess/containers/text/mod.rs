@@ -0,0 +1,3 @@
+mod language_detection_result;
+
+pub use language_detection_result::*;
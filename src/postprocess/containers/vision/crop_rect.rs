@@ -2,6 +2,7 @@ use crate::postprocess::NormalizedRect;
 
 /// Image crop params
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CropRect {
     pub x_min: f32,
     pub y_min: f32,
@@ -3,6 +3,7 @@ use std::fmt::{Display, Formatter};
 
 /// Detection for a single bounding box.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Detection {
     /// A vector of detected categories.
     pub categories: Vec<Category>,
@@ -18,6 +19,7 @@ pub struct Detection {
 
 /// Detection results of a model.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DetectionResult {
     /// A vector of Detections.
     pub detections: Vec<Detection>,
@@ -47,3 +49,42 @@ impl Display for DetectionResult {
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let result = DetectionResult {
+            detections: vec![Detection {
+                categories: vec![Category {
+                    index: 1,
+                    score: 0.75,
+                    category_name: Some("cat".into()),
+                    display_name: None,
+                }],
+                bounding_box: Rect {
+                    left: 0.1,
+                    top: 0.2,
+                    right: 0.3,
+                    bottom: 0.4,
+                },
+                key_points: Some(vec![NormalizedKeypoint {
+                    x: 0.5,
+                    y: 0.25,
+                    label: None,
+                    score: Some(1.),
+                }]),
+            }],
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains(r#""category_name":"cat""#));
+        let r: DetectionResult = serde_json::from_str(&json).unwrap();
+        let d = &r.detections[0];
+        assert_eq!(d.bounding_box, result.detections[0].bounding_box);
+        assert_eq!(d.categories[0].index, 1);
+        assert_eq!(d.key_points.as_ref().unwrap()[0].y, 0.25);
+        assert_eq!(serde_json::to_string(&r).unwrap(), json);
+    }
+}
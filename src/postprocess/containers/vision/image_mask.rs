@@ -5,3 +5,91 @@ pub type ImageConfidenceMask = ImageBuffer<Luma<f32>, Vec<f32>>;
 
 /// each pixel represents the class which the pixel in the original image was predicted to belong to.
 pub type ImageCategoryMask = ImageBuffer<Luma<u8>, Vec<u8>>;
+
+/// Serialize the masks as ```{ width, height, data }```, where data is the row-major pixel values.
+#[cfg(feature = "serde")]
+pub(crate) mod mask_serde {
+    use image::{ImageBuffer, Luma, Primitive};
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    struct MaskRef<'a, T> {
+        width: u32,
+        height: u32,
+        data: &'a [T],
+    }
+
+    #[derive(Deserialize)]
+    struct MaskData<T> {
+        width: u32,
+        height: u32,
+        data: Vec<T>,
+    }
+
+    #[inline]
+    fn to_ref<T: Primitive>(mask: &ImageBuffer<Luma<T>, Vec<T>>) -> MaskRef<'_, T> {
+        MaskRef {
+            width: mask.width(),
+            height: mask.height(),
+            data: mask.as_raw(),
+        }
+    }
+
+    #[inline]
+    fn from_data<T: Primitive, E: Error>(
+        m: MaskData<T>,
+    ) -> Result<ImageBuffer<Luma<T>, Vec<T>>, E> {
+        let (width, height, len) = (m.width, m.height, m.data.len());
+        ImageBuffer::from_raw(width, height, m.data).ok_or_else(|| {
+            E::custom(format!(
+                "The mask size `{}x{}` does not match the data length `{}`",
+                width, height, len
+            ))
+        })
+    }
+
+    /// For ```Option<ImageCategoryMask>```
+    pub(crate) mod category_mask {
+        use super::*;
+        use crate::postprocess::ImageCategoryMask;
+
+        pub(crate) fn serialize<S: Serializer>(
+            mask: &Option<ImageCategoryMask>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            mask.as_ref().map(to_ref).serialize(serializer)
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<ImageCategoryMask>, D::Error> {
+            Option::<MaskData<u8>>::deserialize(deserializer)?
+                .map(from_data)
+                .transpose()
+        }
+    }
+
+    /// For ```Option<Vec<ImageConfidenceMask>>```
+    pub(crate) mod confidence_masks {
+        use super::*;
+        use crate::postprocess::ImageConfidenceMask;
+
+        pub(crate) fn serialize<S: Serializer>(
+            masks: &Option<Vec<ImageConfidenceMask>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            masks
+                .as_ref()
+                .map(|m| m.iter().map(to_ref).collect::<Vec<_>>())
+                .serialize(serializer)
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Vec<ImageConfidenceMask>>, D::Error> {
+            Option::<Vec<MaskData<f32>>>::deserialize(deserializer)?
+                .map(|m| m.into_iter().map(from_data).collect())
+                .transpose()
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt::{Display, Formatter};
 /// A keypoint, defined by the coordinates (x, y), normalized
 /// by the image dimensions.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NormalizedKeypoint {
     /// x in normalized image coordinates.
     pub x: f32,
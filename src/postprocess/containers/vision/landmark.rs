@@ -6,6 +6,7 @@ use std::ops::{Deref, DerefMut};
 /// landmark coordinates are in meters. z represents the landmark depth, and the
 /// smaller the value the closer the world landmark is to the camera.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Landmark {
     pub x: f32,
     pub y: f32,
@@ -27,6 +28,7 @@ pub struct Landmark {
 
 /// A list of Landmarks.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Landmarks(pub Vec<Landmark>);
 
 impl Deref for Landmarks {
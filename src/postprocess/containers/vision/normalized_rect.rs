@@ -3,6 +3,7 @@ use super::{Detection, Rect};
 /// A rectangle with rotation in normalized coordinates. The values of box center
 /// location and size are within [0, 1].
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NormalizedRect {
     /// Location of the center of the rectangle in image coordinates.
     /// The (0.0, 0.0) point is at the (top, left) corner.
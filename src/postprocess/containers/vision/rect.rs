@@ -4,6 +4,7 @@ use std::ops::{Add, Sub};
 /// Defines a rectangle, used e.g. as part of detection results or as input
 /// region-of-interest.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect<T: Sized + Clone + Copy + Send + Sync + Debug + Display + Add + Sub + 'static> {
     pub left: T,
     pub top: T,
@@ -0,0 +1,364 @@
+use crate::postprocess::{Category, ClassificationResult};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// The confusion matrix of top-1 predictions, keyed by the category names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfusionMatrix {
+    /// The category names, which are the row and column labels.
+    pub labels: Vec<String>,
+    /// ```counts[ground truth][prediction]```, the indices are the same as ```labels```.
+    pub counts: Vec<Vec<usize>>,
+    /// The number of samples of each ground truth without top-1 prediction
+    /// (no categories, or the top-1 category has no category name).
+    pub no_prediction: Vec<usize>,
+}
+
+impl ConfusionMatrix {
+    /// Get the number of samples of the ground truth label which are predicted as the prediction label.
+    #[inline]
+    pub fn get(&self, ground_truth: &str, prediction: &str) -> usize {
+        let index = |l: &str| self.labels.iter().position(|n| n == l);
+        match (index(ground_truth), index(prediction)) {
+            (Some(g), Some(p)) => self.counts[g][p],
+            _ => 0,
+        }
+    }
+}
+
+impl Display for ConfusionMatrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .labels
+            .iter()
+            .map(|l| l.chars().count())
+            .chain(["(none)".len(), 8])
+            .max()
+            .unwrap();
+        write!(f, "{:>width$}", "", width = width)?;
+        for label in self.labels.iter().chain(std::iter::once(&"(none)".into())) {
+            write!(f, " {:>width$}", label, width = width)?;
+        }
+        writeln!(f)?;
+        for (i, label) in self.labels.iter().enumerate() {
+            write!(f, "{:>width$}", label, width = width)?;
+            for count in self.counts[i]
+                .iter()
+                .chain(std::iter::once(&self.no_prediction[i]))
+            {
+                write!(f, " {:>width$}", count, width = width)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// The evaluation result of one category.
+#[derive(Debug, Clone)]
+pub struct CategoryEvaluation {
+    pub name: String,
+    /// The number of samples whose ground truth is this category.
+    pub support: usize,
+    /// The precision of top-1 predictions, 0 if the category is never predicted.
+    pub precision: f32,
+    /// The recall of top-1 predictions, 0 if the category has no samples.
+    pub recall: f32,
+    /// The harmonic mean of precision and recall, 0 if both are 0.
+    pub f1: f32,
+}
+
+/// The classification evaluation result.
+#[derive(Debug, Clone)]
+pub struct ClassificationEvaluation {
+    pub num_samples: usize,
+    pub top_1_accuracy: f32,
+    /// The ```k``` of top-k accuracy.
+    pub top_k: usize,
+    /// The ratio of samples whose ground truth is in the top-k predictions.
+    pub top_k_accuracy: f32,
+    /// The mean precision of categories which have samples.
+    pub macro_precision: f32,
+    /// The mean recall of categories which have samples.
+    pub macro_recall: f32,
+    /// The mean F1 score of categories which have samples.
+    pub macro_f1: f32,
+    /// The results of all categories, in the order of confusion matrix labels.
+    pub categories: Vec<CategoryEvaluation>,
+    pub confusion_matrix: ConfusionMatrix,
+}
+
+impl ClassificationEvaluation {
+    /// Get the evaluation result of the category by name.
+    #[inline]
+    pub fn category(&self, name: &str) -> Option<&CategoryEvaluation> {
+        self.categories.iter().find(|c| c.name == name)
+    }
+}
+
+impl Display for ClassificationEvaluation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "ClassificationEvaluation ({} samples):",
+            self.num_samples
+        )?;
+        writeln!(f, "  Top-1 accuracy: {:.4}", self.top_1_accuracy)?;
+        writeln!(
+            f,
+            "  Top-{} accuracy: {:.4}",
+            self.top_k, self.top_k_accuracy
+        )?;
+        writeln!(
+            f,
+            "  Macro precision {:.4}, recall {:.4}, F1 {:.4}",
+            self.macro_precision, self.macro_recall, self.macro_f1
+        )?;
+        for c in self.categories.iter() {
+            writeln!(
+                f,
+                "  Category \"{}\": Precision {:.4}, Recall {:.4}, F1 {:.4}, Support {}",
+                c.name, c.precision, c.recall, c.f1, c.support
+            )?;
+        }
+        writeln!(
+            f,
+            "  Confusion matrix (row: ground truth, column: prediction):"
+        )?;
+        write!(f, "{}", self.confusion_matrix)
+    }
+}
+
+/// Accumulate the classification results with the ground truth category names, and compute the
+/// top-1/top-k accuracy, per category precision/recall/F1 and the confusion matrix.
+///
+/// The predictions are matched with the ground truth by [`Category::category_name`].
+/// It can be used for results of ```ImageClassifier```, ```TextClassifier``` and ```AudioClassifier```.
+pub struct ClassificationEvaluator {
+    top_k: usize,
+    head_index: usize,
+    labels: Vec<String>,
+    label_indices: HashMap<String, usize>,
+    /// ```counts[ground truth][prediction]```
+    counts: Vec<Vec<usize>>,
+    no_prediction: Vec<usize>,
+    num_samples: usize,
+    num_top_k_correct: usize,
+}
+
+impl Default for ClassificationEvaluator {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClassificationEvaluator {
+    /// Create a new evaluator with the default top-k ```5```.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            top_k: 5,
+            head_index: 0,
+            labels: Vec::new(),
+            label_indices: HashMap::new(),
+            counts: Vec::new(),
+            no_prediction: Vec::new(),
+            num_samples: 0,
+            num_top_k_correct: 0,
+        }
+    }
+
+    /// Set the ```k``` of top-k accuracy, default is ```5```.
+    #[inline(always)]
+    pub fn top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k.max(1);
+        self
+    }
+
+    /// Set the classifier head to evaluate for multi-head models, default is ```0```.
+    #[inline(always)]
+    pub fn head_index(mut self, head_index: usize) -> Self {
+        self.head_index = head_index;
+        self
+    }
+
+    /// Add the category names in order, such as the model labels, so that the categories which never appear
+    /// are also in the results. Otherwise the categories are in the order they first appear.
+    pub fn labels<S: AsRef<str>>(mut self, labels: impl IntoIterator<Item = S>) -> Self {
+        for label in labels {
+            self.label_index(label.as_ref());
+        }
+        self
+    }
+
+    fn label_index(&mut self, label: &str) -> usize {
+        if let Some(i) = self.label_indices.get(label) {
+            return *i;
+        }
+        let index = self.labels.len();
+        self.labels.push(label.to_string());
+        self.label_indices.insert(label.to_string(), index);
+        for row in self.counts.iter_mut() {
+            row.push(0);
+        }
+        self.counts.push(vec![0; index + 1]);
+        self.no_prediction.push(0);
+        index
+    }
+
+    /// Add the classification result with the ground truth category name.
+    /// The categories of the evaluated head are used, and a result without the head has no prediction.
+    #[inline]
+    pub fn add(&mut self, result: &ClassificationResult, ground_truth: &str) {
+        let categories = result
+            .classifications
+            .iter()
+            .find(|c| c.head_index == self.head_index)
+            .map(|c| c.categories.as_slice())
+            .unwrap_or(&[]);
+        self.add_categories(categories, ground_truth);
+    }
+
+    /// Add the predicted categories with the ground truth category name.
+    pub fn add_categories(&mut self, categories: &[Category], ground_truth: &str) {
+        let mut sorted: Vec<&Category> = categories.iter().collect();
+        sorted.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        let g = self.label_index(ground_truth);
+        self.num_samples += 1;
+        if sorted
+            .iter()
+            .take(self.top_k)
+            .any(|c| c.category_name.as_deref() == Some(ground_truth))
+        {
+            self.num_top_k_correct += 1;
+        }
+        match sorted.first().and_then(|c| c.category_name.as_ref()) {
+            Some(name) => {
+                let p = self.label_index(name);
+                self.counts[g][p] += 1;
+            }
+            None => self.no_prediction[g] += 1,
+        }
+    }
+
+    /// Compute the metrics of the added results.
+    pub fn evaluate(&self) -> ClassificationEvaluation {
+        let ratio = |a: usize, b: usize| if b == 0 { 0. } else { a as f32 / b as f32 };
+        let n = self.labels.len();
+        let mut num_top_1_correct = 0;
+        let categories: Vec<CategoryEvaluation> = (0..n)
+            .map(|i| {
+                let tp = self.counts[i][i];
+                num_top_1_correct += tp;
+                let support = self.counts[i].iter().sum::<usize>() + self.no_prediction[i];
+                let predicted = (0..n).map(|g| self.counts[g][i]).sum();
+                let precision = ratio(tp, predicted);
+                let recall = ratio(tp, support);
+                let f1 = if precision + recall > 0. {
+                    2. * precision * recall / (precision + recall)
+                } else {
+                    0.
+                };
+                CategoryEvaluation {
+                    name: self.labels[i].clone(),
+                    support,
+                    precision,
+                    recall,
+                    f1,
+                }
+            })
+            .collect();
+
+        let num_classes = categories.iter().filter(|c| c.support > 0).count();
+        let mean = |f: fn(&CategoryEvaluation) -> f32| {
+            let sum: f32 = categories.iter().filter(|c| c.support > 0).map(f).sum();
+            ratio(1, num_classes) * sum
+        };
+        ClassificationEvaluation {
+            num_samples: self.num_samples,
+            top_1_accuracy: ratio(num_top_1_correct, self.num_samples),
+            top_k: self.top_k,
+            top_k_accuracy: ratio(self.num_top_k_correct, self.num_samples),
+            macro_precision: mean(|c| c.precision),
+            macro_recall: mean(|c| c.recall),
+            macro_f1: mean(|c| c.f1),
+            categories,
+            confusion_matrix: ConfusionMatrix {
+                labels: self.labels.clone(),
+                counts: self.counts.clone(),
+                no_prediction: self.no_prediction.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::postprocess::Classifications;
+
+    fn result(names: &[(&str, f32)]) -> ClassificationResult {
+        ClassificationResult {
+            classifications: vec![Classifications {
+                head_index: 0,
+                head_name: None,
+                categories: names
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (name, score))| Category {
+                        index: i as u32,
+                        score: *score,
+                        category_name: Some(name.to_string()),
+                        display_name: None,
+                    })
+                    .collect(),
+            }],
+            timestamp_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_classification_evaluation() {
+        let mut evaluator = ClassificationEvaluator::new()
+            .top_k(2)
+            .labels(["cat", "dog", "bird"]);
+        evaluator.add(&result(&[("cat", 0.9), ("dog", 0.1)]), "cat");
+        // not sorted by score
+        evaluator.add(&result(&[("cat", 0.3), ("dog", 0.6)]), "cat");
+        evaluator.add(&result(&[("dog", 0.8), ("cat", 0.2)]), "dog");
+        evaluator.add(&result(&[("fish", 0.8), ("cat", 0.1)]), "dog");
+        evaluator.add(&result(&[]), "cat");
+
+        let e = evaluator.evaluate();
+        assert_eq!(e.num_samples, 5);
+        assert!((e.top_1_accuracy - 0.4).abs() < 1e-6);
+        assert!((e.top_k_accuracy - 0.6).abs() < 1e-6);
+
+        let m = &e.confusion_matrix;
+        assert_eq!(m.labels, vec!["cat", "dog", "bird", "fish"]);
+        assert_eq!(m.get("cat", "dog"), 1);
+        assert_eq!(m.get("dog", "fish"), 1);
+        assert_eq!(m.get("bird", "cat"), 0);
+        assert_eq!(m.no_prediction, vec![1, 0, 0, 0]);
+
+        let cat = e.category("cat").unwrap();
+        assert_eq!(cat.support, 3);
+        assert!((cat.precision - 1.).abs() < 1e-6);
+        assert!((cat.recall - 1. / 3.).abs() < 1e-6);
+        assert!((cat.f1 - 0.5).abs() < 1e-6);
+        let dog = e.category("dog").unwrap();
+        assert!((dog.precision - 0.5).abs() < 1e-6);
+        assert!((dog.recall - 0.5).abs() < 1e-6);
+        assert_eq!(e.category("bird").unwrap().support, 0);
+        assert_eq!(e.category("fish").unwrap().precision, 0.);
+        // mean of cat and dog
+        assert!((e.macro_precision - 0.75).abs() < 1e-6);
+        assert!((e.macro_f1 - 0.5).abs() < 1e-6);
+
+        let e = ClassificationEvaluator::new().evaluate();
+        assert_eq!(e.num_samples, 0);
+        assert_eq!(e.top_1_accuracy, 0.);
+    }
+}
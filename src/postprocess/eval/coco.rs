@@ -0,0 +1,544 @@
+use crate::postprocess::DetectionResult;
+use crate::Error;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+/// An image in the COCO dataset.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CocoImage {
+    pub id: u64,
+    pub file_name: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A ground truth object annotation in the COCO dataset.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CocoAnnotation {
+    #[serde(default)]
+    pub id: u64,
+    pub image_id: u64,
+    pub category_id: u64,
+    /// ```[x, y, width, height]``` in pixels.
+    pub bbox: [f32; 4],
+    /// Crowd annotations are ignored, the detections matched with them are neither true positives nor false positives.
+    #[serde(default)]
+    pub iscrowd: u8,
+}
+
+/// A category in the COCO dataset.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CocoCategory {
+    pub id: u64,
+    pub name: String,
+}
+
+/// The ground truth in COCO json format. Only the fields used by the bounding box evaluation are parsed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CocoDataset {
+    pub images: Vec<CocoImage>,
+    pub annotations: Vec<CocoAnnotation>,
+    pub categories: Vec<CocoCategory>,
+}
+
+impl CocoDataset {
+    /// Parse the COCO json string.
+    #[inline]
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Parse the COCO json file.
+    #[inline]
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    /// Get the image by the file name.
+    #[inline]
+    pub fn image_by_file_name(&self, file_name: &str) -> Option<&CocoImage> {
+        self.images.iter().find(|i| i.file_name == file_name)
+    }
+}
+
+/// The evaluation result of one category.
+#[derive(Debug, Clone)]
+pub struct CocoCategoryEvaluation {
+    pub category_id: u64,
+    pub name: String,
+    /// The number of ground truth objects (excluding crowd annotations).
+    pub num_ground_truths: usize,
+    /// The average precision at IoU thresholds ```[0.5:0.05:0.95]```.
+    pub ap: f32,
+    /// The average precision at IoU threshold 0.5.
+    pub ap50: f32,
+    /// The average precision at IoU threshold 0.75.
+    pub ap75: f32,
+    /// The recall at IoU thresholds ```[0.5:0.05:0.95]```, with max 100 detections per image.
+    pub recall: f32,
+}
+
+/// The COCO bounding box evaluation result. The values are the means of categories which have ground truth objects,
+/// or -1 if there are no such categories.
+#[derive(Debug, Clone)]
+pub struct CocoEvaluation {
+    /// The number of evaluated images.
+    pub num_images: usize,
+    /// The mean average precision at IoU thresholds ```[0.5:0.05:0.95]```.
+    pub map: f32,
+    /// The mean average precision at IoU threshold 0.5.
+    pub ap50: f32,
+    /// The mean average precision at IoU threshold 0.75.
+    pub ap75: f32,
+    /// The mean recall at IoU thresholds ```[0.5:0.05:0.95]```, with max 100 detections per image.
+    pub recall: f32,
+    /// The number of detections whose category cannot be found in the dataset.
+    pub num_unknown_detections: usize,
+    /// The results of categories which have ground truth objects.
+    pub categories: Vec<CocoCategoryEvaluation>,
+}
+
+impl Display for CocoEvaluation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "CocoEvaluation ({} images):", self.num_images)?;
+        writeln!(f, "  mAP@[.5:.95]: {:.4}", self.map)?;
+        writeln!(f, "  AP50:         {:.4}", self.ap50)?;
+        writeln!(f, "  AP75:         {:.4}", self.ap75)?;
+        writeln!(f, "  Recall@100:   {:.4}", self.recall)?;
+        if self.num_unknown_detections != 0 {
+            writeln!(f, "  Unknown detections: {}", self.num_unknown_detections)?;
+        }
+        for c in self.categories.iter() {
+            writeln!(
+                f,
+                "  Category {} ({}): AP {:.4}, AP50 {:.4}, AP75 {:.4}, Recall {:.4}, Objects {}",
+                c.category_id, c.name, c.ap, c.ap50, c.ap75, c.recall, c.num_ground_truths
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// IoU thresholds ```[0.5:0.05:0.95]```
+const NUM_IOU_THRESHOLDS: usize = 10;
+/// Recall thresholds ```[0:0.01:1]```
+const NUM_RECALL_THRESHOLDS: usize = 101;
+const MAX_DETECTIONS: usize = 100;
+
+/// ```[x, y, width, height]``` in pixels
+type CocoBox = [f32; 4];
+/// (image id, category id)
+type ImageCategoryKey = (u64, u64);
+
+#[inline(always)]
+fn iou_threshold(t: usize) -> f32 {
+    0.5 + 0.05 * t as f32
+}
+
+/// Intersection over union of ```[x, y, w, h]``` boxes, the union is the detection area for crowd ground truth.
+#[inline]
+fn box_iou(dt: &CocoBox, gt: &CocoBox, is_crowd: bool) -> f32 {
+    let w = (dt[0] + dt[2]).min(gt[0] + gt[2]) - dt[0].max(gt[0]);
+    let h = (dt[1] + dt[3]).min(gt[1] + gt[3]) - dt[1].max(gt[1]);
+    if w <= 0. || h <= 0. {
+        return 0.;
+    }
+    let intersection = w * h;
+    let union = if is_crowd {
+        dt[2] * dt[3]
+    } else {
+        dt[2] * dt[3] + gt[2] * gt[3] - intersection
+    };
+    if union <= 0. {
+        0.
+    } else {
+        intersection / union
+    }
+}
+
+/// A detection after matching: the score, and whether it matched (or is ignored) at each IoU threshold.
+struct MatchedDetection {
+    score: f32,
+    matched: [bool; NUM_IOU_THRESHOLDS],
+    ignored: [bool; NUM_IOU_THRESHOLDS],
+}
+
+/// Match the detections (sorted by score) to the ground truth of one image and one category, like ```pycocotools```.
+fn match_detections(dts: &[(f32, CocoBox)], gts: &[(&CocoBox, bool)]) -> Vec<MatchedDetection> {
+    // the crowd annotations are at the end, and are matched only if no other ground truth can be matched
+    let mut gts = gts.to_vec();
+    gts.sort_by_key(|g| g.1);
+    let ious: Vec<Vec<f32>> = dts
+        .iter()
+        .map(|(_, d)| gts.iter().map(|(g, crowd)| box_iou(d, g, *crowd)).collect())
+        .collect();
+
+    let mut results: Vec<MatchedDetection> = dts
+        .iter()
+        .map(|(score, _)| MatchedDetection {
+            score: *score,
+            matched: [false; NUM_IOU_THRESHOLDS],
+            ignored: [false; NUM_IOU_THRESHOLDS],
+        })
+        .collect();
+    for t in 0..NUM_IOU_THRESHOLDS {
+        let mut gt_matched = vec![false; gts.len()];
+        for (d, result) in results.iter_mut().enumerate() {
+            let mut best_iou = iou_threshold(t).min(1. - 1e-10);
+            let mut m: Option<usize> = None;
+            for (g, (_, crowd)) in gts.iter().enumerate() {
+                if gt_matched[g] && !crowd {
+                    continue;
+                }
+                // matched to a regular ground truth, and now reach the crowd ones
+                if m.is_some_and(|m| !gts[m].1) && *crowd {
+                    break;
+                }
+                if ious[d][g] < best_iou {
+                    continue;
+                }
+                best_iou = ious[d][g];
+                m = Some(g);
+            }
+            if let Some(m) = m {
+                gt_matched[m] = true;
+                result.matched[t] = true;
+                result.ignored[t] = gts[m].1;
+            }
+        }
+    }
+    results
+}
+
+/// Compute the average precision and the recall of one category at one IoU threshold.
+fn average_precision(detections: &[MatchedDetection], t: usize, num_gts: usize) -> (f32, f32) {
+    let mut precisions = Vec::with_capacity(detections.len());
+    let mut recalls = Vec::with_capacity(detections.len());
+    let (mut tp, mut fp) = (0usize, 0usize);
+    for d in detections.iter().filter(|d| !d.ignored[t]) {
+        if d.matched[t] {
+            tp += 1;
+        } else {
+            fp += 1;
+        }
+        recalls.push(tp as f64 / num_gts as f64);
+        precisions.push(tp as f64 / (tp + fp) as f64);
+    }
+    // make the precision monotonically decreasing
+    for i in (1..precisions.len()).rev() {
+        if precisions[i - 1] < precisions[i] {
+            precisions[i - 1] = precisions[i];
+        }
+    }
+    let mut sum = 0.;
+    let mut p = 0;
+    for r in 0..NUM_RECALL_THRESHOLDS {
+        let recall_threshold = r as f64 / (NUM_RECALL_THRESHOLDS - 1) as f64;
+        while p < recalls.len() && recalls[p] < recall_threshold {
+            p += 1;
+        }
+        if p == recalls.len() {
+            break;
+        }
+        sum += precisions[p];
+    }
+    (
+        (sum / NUM_RECALL_THRESHOLDS as f64) as f32,
+        recalls.last().cloned().unwrap_or(0.) as f32,
+    )
+}
+
+/// Evaluate detection results with the COCO dataset, using the COCO bounding box metrics
+/// (the same as ```pycocotools``` with area range ```all``` and max 100 detections per image).
+///
+/// Only the images added to the evaluator are evaluated. The category of a detection is its first category,
+/// which is found in the dataset by the category name (or display name). Models without labels can use the
+/// category index with an offset as the category id by [`CocoEvaluator::category_index_offset`].
+pub struct CocoEvaluator<'a> {
+    dataset: &'a CocoDataset,
+    category_index_offset: Option<i64>,
+    /// image id -> (image width, image height)
+    images: HashMap<u64, (f32, f32)>,
+    evaluated_images: HashSet<u64>,
+    /// (image id, category id) -> [(score, box)]
+    detections: HashMap<ImageCategoryKey, Vec<(f32, CocoBox)>>,
+    num_unknown_detections: usize,
+}
+
+impl<'a> CocoEvaluator<'a> {
+    /// Create a new evaluator with the ground truth dataset.
+    #[inline]
+    pub fn new(dataset: &'a CocoDataset) -> Self {
+        Self {
+            dataset,
+            category_index_offset: None,
+            images: dataset
+                .images
+                .iter()
+                .map(|i| (i.id, (i.width as f32, i.height as f32)))
+                .collect(),
+            evaluated_images: HashSet::new(),
+            detections: HashMap::new(),
+            num_unknown_detections: 0,
+        }
+    }
+
+    /// Use ```category index + offset``` as the category id if the category name is not found in the dataset.
+    /// E.g. the offset is 1 for models whose index 0 is the COCO category id 1 (person).
+    #[inline(always)]
+    pub fn category_index_offset(mut self, offset: i64) -> Self {
+        self.category_index_offset = Some(offset);
+        self
+    }
+
+    fn category_id(&self, category: &crate::postprocess::Category) -> Option<u64> {
+        let by_name = |name: &Option<String>| {
+            name.as_ref().and_then(|n| {
+                self.dataset
+                    .categories
+                    .iter()
+                    .find(|c| &c.name == n)
+                    .map(|c| c.id)
+            })
+        };
+        by_name(&category.category_name)
+            .or_else(|| by_name(&category.display_name))
+            .or_else(|| {
+                let id = category.index as i64 + self.category_index_offset?;
+                self.dataset
+                    .categories
+                    .iter()
+                    .find(|c| c.id as i64 == id)
+                    .map(|c| c.id)
+            })
+    }
+
+    /// Add the detection result of the image. Adding the same image again will append the detections.
+    pub fn add(&mut self, image_id: u64, result: &DetectionResult) -> Result<(), Error> {
+        let (width, height) = *self.images.get(&image_id).ok_or_else(|| {
+            Error::ArgumentError(format!("Image id `{}` is not in the dataset", image_id))
+        })?;
+        self.evaluated_images.insert(image_id);
+        for d in result.detections.iter() {
+            let Some(category) = d.categories.first() else {
+                continue;
+            };
+            let Some(category_id) = self.category_id(category) else {
+                self.num_unknown_detections += 1;
+                continue;
+            };
+            let b = &d.bounding_box;
+            self.detections
+                .entry((image_id, category_id))
+                .or_default()
+                .push((
+                    category.score,
+                    [
+                        b.left * width,
+                        b.top * height,
+                        (b.right - b.left) * width,
+                        (b.bottom - b.top) * height,
+                    ],
+                ));
+        }
+        Ok(())
+    }
+
+    /// Add the detection result of the image by the image file name.
+    #[inline]
+    pub fn add_by_file_name(
+        &mut self,
+        file_name: &str,
+        result: &DetectionResult,
+    ) -> Result<(), Error> {
+        let image_id = self
+            .dataset
+            .image_by_file_name(file_name)
+            .ok_or_else(|| {
+                Error::ArgumentError(format!("Image `{}` is not in the dataset", file_name))
+            })?
+            .id;
+        self.add(image_id, result)
+    }
+
+    /// Compute the COCO metrics of the added detection results.
+    pub fn evaluate(&self) -> CocoEvaluation {
+        let mut image_ids: Vec<u64> = self.evaluated_images.iter().cloned().collect();
+        image_ids.sort_unstable();
+
+        let mut gts: HashMap<ImageCategoryKey, Vec<(&CocoBox, bool)>> = HashMap::new();
+        for a in self.dataset.annotations.iter() {
+            if self.evaluated_images.contains(&a.image_id) {
+                gts.entry((a.image_id, a.category_id))
+                    .or_default()
+                    .push((&a.bbox, a.iscrowd != 0));
+            }
+        }
+
+        let mut categories = Vec::new();
+        for c in self.dataset.categories.iter() {
+            let mut matched = Vec::new();
+            let mut num_gts = 0;
+            for image_id in image_ids.iter() {
+                let key = (*image_id, c.id);
+                let image_gts = gts.get(&key).map(|g| g.as_slice()).unwrap_or(&[]);
+                num_gts += image_gts.iter().filter(|g| !g.1).count();
+                let mut dts = self.detections.get(&key).cloned().unwrap_or_default();
+                dts.sort_by(|a, b| b.0.total_cmp(&a.0));
+                dts.truncate(MAX_DETECTIONS);
+                matched.extend(match_detections(&dts, image_gts));
+            }
+            if num_gts == 0 {
+                continue;
+            }
+            // stable sort to keep the image order for the same scores
+            matched.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+            let mut aps = [0f32; NUM_IOU_THRESHOLDS];
+            let mut recall = 0.;
+            for (t, ap) in aps.iter_mut().enumerate() {
+                let (a, r) = average_precision(&matched, t, num_gts);
+                *ap = a;
+                recall += r;
+            }
+            categories.push(CocoCategoryEvaluation {
+                category_id: c.id,
+                name: c.name.clone(),
+                num_ground_truths: num_gts,
+                ap: aps.iter().sum::<f32>() / NUM_IOU_THRESHOLDS as f32,
+                ap50: aps[0],
+                ap75: aps[5],
+                recall: recall / NUM_IOU_THRESHOLDS as f32,
+            });
+        }
+
+        let mean = |f: fn(&CocoCategoryEvaluation) -> f32| {
+            if categories.is_empty() {
+                -1.
+            } else {
+                categories.iter().map(f).sum::<f32>() / categories.len() as f32
+            }
+        };
+        CocoEvaluation {
+            num_images: image_ids.len(),
+            map: mean(|c| c.ap),
+            ap50: mean(|c| c.ap50),
+            ap75: mean(|c| c.ap75),
+            recall: mean(|c| c.recall),
+            num_unknown_detections: self.num_unknown_detections,
+            categories,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::postprocess::{Category, Detection, Rect};
+
+    const DATASET: &str = r#"{
+        "images": [
+            {"id": 1, "file_name": "a.jpg", "width": 100, "height": 100},
+            {"id": 2, "file_name": "b.jpg", "width": 200, "height": 100}
+        ],
+        "annotations": [
+            {"id": 1, "image_id": 1, "category_id": 1, "bbox": [10, 10, 20, 20], "area": 400, "iscrowd": 0},
+            {"id": 2, "image_id": 1, "category_id": 2, "bbox": [50, 50, 40, 40], "area": 1600, "iscrowd": 0},
+            {"id": 3, "image_id": 2, "category_id": 1, "bbox": [0, 0, 100, 50], "area": 5000, "iscrowd": 0},
+            {"id": 4, "image_id": 2, "category_id": 1, "bbox": [100, 0, 100, 100], "area": 10000, "iscrowd": 1}
+        ],
+        "categories": [{"id": 1, "name": "cat"}, {"id": 2, "name": "dog"}, {"id": 3, "name": "bird"}]
+    }"#;
+
+    fn detection(
+        name: &str,
+        score: f32,
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+    ) -> Detection {
+        Detection {
+            categories: vec![Category {
+                index: 0,
+                score,
+                category_name: Some(name.into()),
+                display_name: None,
+            }],
+            bounding_box: Rect {
+                left,
+                top,
+                right,
+                bottom,
+            },
+            key_points: None,
+        }
+    }
+
+    #[test]
+    fn test_coco_evaluation() {
+        let dataset = CocoDataset::from_json(DATASET).unwrap();
+        assert_eq!(dataset.image_by_file_name("b.jpg").unwrap().id, 2);
+        assert!(CocoDataset::from_json("{}").is_err());
+
+        // perfect detections
+        let mut evaluator = CocoEvaluator::new(&dataset);
+        let a = DetectionResult {
+            detections: vec![
+                detection("cat", 0.9, 0.1, 0.1, 0.3, 0.3),
+                detection("dog", 0.8, 0.5, 0.5, 0.9, 0.9),
+            ],
+        };
+        let b = DetectionResult {
+            detections: vec![
+                detection("cat", 0.9, 0., 0., 0.5, 0.5),
+                // matched with the crowd annotation, so it is ignored
+                detection("cat", 0.95, 0.6, 0.1, 0.8, 0.9),
+                detection("unknown", 0.9, 0., 0., 0.5, 0.5),
+            ],
+        };
+        evaluator.add(1, &a).unwrap();
+        evaluator.add_by_file_name("b.jpg", &b).unwrap();
+        assert!(evaluator.add(3, &a).is_err());
+        let e = evaluator.evaluate();
+        assert_eq!(e.num_images, 2);
+        assert_eq!(e.num_unknown_detections, 1);
+        assert_eq!(e.categories.len(), 2);
+        assert_eq!(e.categories[0].num_ground_truths, 2);
+        assert!((e.map - 1.).abs() < 1e-6);
+        assert!((e.recall - 1.).abs() < 1e-6);
+
+        // IoU of the cat is 0.64, and a false positive with the highest score
+        let mut evaluator = CocoEvaluator::new(&dataset);
+        let a = DetectionResult {
+            detections: vec![
+                detection("cat", 0.9, 0.1, 0.1, 0.26, 0.26),
+                detection("cat", 0.95, 0.6, 0.1, 0.8, 0.3),
+            ],
+        };
+        evaluator.add(1, &a).unwrap();
+        let e = evaluator.evaluate();
+        assert_eq!(e.num_images, 1);
+        assert_eq!(e.categories.len(), 2);
+        let cat = &e.categories[0];
+        assert!((cat.ap50 - 0.5).abs() < 1e-6);
+        assert_eq!(cat.ap75, 0.);
+        // matched at thresholds 0.5, 0.55, 0.6
+        assert!((cat.ap - 0.15).abs() < 1e-6);
+        assert!((cat.recall - 0.3).abs() < 1e-6);
+        assert_eq!(e.categories[1].ap, 0.);
+        assert!((e.map - 0.075).abs() < 1e-6);
+
+        // category index as id
+        let mut evaluator = CocoEvaluator::new(&dataset).category_index_offset(1);
+        let mut a = DetectionResult {
+            detections: vec![detection("", 0.9, 0.1, 0.1, 0.3, 0.3)],
+        };
+        a.detections[0].categories[0].category_name = None;
+        evaluator.add(1, &a).unwrap();
+        assert!((evaluator.evaluate().categories[0].ap - 1.).abs() < 1e-6);
+        assert_eq!(CocoEvaluator::new(&dataset).evaluate().map, -1.);
+    }
+}
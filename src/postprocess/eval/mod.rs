@@ -0,0 +1,12 @@
+//! Evaluate the task results with the ground truth, such as the COCO mean average precision for object detection
+//! and the accuracy of classification.
+//!
+//! The COCO evaluation parses the json ground truth, so it requires the ```eval``` feature.
+
+mod classification;
+pub use classification::*;
+
+#[cfg(all(feature = "vision", feature = "eval"))]
+mod coco;
+#[cfg(all(feature = "vision", feature = "eval"))]
+pub use coco::*;
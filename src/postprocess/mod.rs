@@ -4,11 +4,23 @@ pub use containers::*;
 
 /// stateless operators for tensor
 mod ops;
-pub(crate) use ops::{Activation, QuantizationParameters};
+pub use ops::{Activation, QuantizationParameters};
 
 /// stateful objects, convert tensor to results
 mod processing;
 pub(crate) use processing::*;
+#[cfg(feature = "vision")]
+pub use processing::{
+    NonMaxSuppressionAlgorithm, NonMaxSuppressionOverlapType, SoftNmsDecay, YoloDecodingOptions,
+};
+
+/// Evaluate task results with the ground truth.
+#[cfg(any(feature = "vision", feature = "audio", feature = "text"))]
+pub mod eval;
+
+/// Protobuf messages compatible with google mediapipe, and the conversions with result containers.
+#[cfg(feature = "protobuf")]
+pub mod proto;
 
 /// Utils to to make use of task results, such as drawing utils.
 pub mod utils;
@@ -7,6 +7,8 @@ use std::collections::HashSet;
 enum Label<'a> {
     Deny,
     Allowed((Cow<'a, str>, Option<Cow<'a, str>>)),
+    // the model has no labels, the category only has index
+    Unnamed,
 }
 
 pub(crate) struct CategoriesFilter<'a> {
@@ -79,6 +81,30 @@ impl<'a> CategoriesFilter<'a> {
         }
     }
 
+    /// For models without labels, such as YOLO models exported without metadata, the categories only have indices.
+    /// No category names can match the allow list or the deny list.
+    pub(crate) fn new_without_labels(option: &ClassificationOptions, num_classes: usize) -> Self {
+        let is_allow_list = !option.category_allow_list.is_empty();
+        Self {
+            labels: (0..num_classes)
+                .map(|_| {
+                    if is_allow_list {
+                        Label::Deny
+                    } else {
+                        Label::Unnamed
+                    }
+                })
+                .collect(),
+            score_threshold: option.score_threshold,
+        }
+    }
+
+    /// Override the score threshold, such as the default threshold of model type.
+    #[inline(always)]
+    pub(crate) fn set_score_threshold(&mut self, score_threshold: f32) {
+        self.score_threshold = score_threshold;
+    }
+
     #[inline(always)]
     fn add_labels_locale(labels: &mut Vec<Label<'a>>, labels_locale: &'a [u8]) {
         let mut iter = labels.iter_mut();
@@ -93,13 +119,24 @@ impl<'a> CategoriesFilter<'a> {
     #[inline(always)]
     pub fn create_category(&self, index: usize, score: f32) -> Option<Category> {
         if score >= self.score_threshold {
-            if let Some(Label::Allowed((l, l_locale))) = self.labels.get(index) {
-                return Some(Category {
-                    index: index as u32,
-                    score,
-                    category_name: Some(l.clone().into_owned()),
-                    display_name: l_locale.clone().map(|l| l.into_owned()),
-                });
+            match self.labels.get(index) {
+                Some(Label::Allowed((l, l_locale))) => {
+                    return Some(Category {
+                        index: index as u32,
+                        score,
+                        category_name: Some(l.clone().into_owned()),
+                        display_name: l_locale.clone().map(|l| l.into_owned()),
+                    });
+                }
+                Some(Label::Unnamed) => {
+                    return Some(Category {
+                        index: index as u32,
+                        score,
+                        category_name: None,
+                        display_name: None,
+                    });
+                }
+                _ => {}
             }
         }
         None
@@ -85,3 +85,7 @@ pub(crate) use common::*;
 mod vision;
 #[cfg(feature = "vision")]
 pub(crate) use vision::*;
+#[cfg(feature = "vision")]
+pub use vision::{
+    NonMaxSuppressionAlgorithm, NonMaxSuppressionOverlapType, SoftNmsDecay, YoloDecodingOptions,
+};
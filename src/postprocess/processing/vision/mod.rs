@@ -5,9 +5,15 @@ mod ssd_anchors_generator;
 mod tensors_to_detection;
 mod tensors_to_landmarks;
 mod tensors_to_segmentation;
+mod yolo_to_detection;
 
 pub(crate) use non_max_suppression::*;
+pub use non_max_suppression::{
+    NonMaxSuppressionAlgorithm, NonMaxSuppressionOverlapType, SoftNmsDecay,
+};
 pub(crate) use ssd_anchors_generator::*;
 pub(crate) use tensors_to_detection::*;
 pub(crate) use tensors_to_landmarks::*;
 pub(crate) use tensors_to_segmentation::*;
+pub use yolo_to_detection::YoloDecodingOptions;
+pub(crate) use yolo_to_detection::*;
@@ -1,16 +1,38 @@
 use crate::postprocess::{Detection, DetectionResult, Rect};
 
-#[derive(Debug, Copy, Clone)]
+/// The overlap similarity of two boxes used by non-maximum-suppression.
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum NonMaxSuppressionOverlapType {
+    /// Intersection area / the area of the minimal box containing both boxes.
     Jaccard,
+    /// Intersection area / the area of the second box.
     ModifiedJaccard,
+    /// Intersection area / union area.
     IntersectionOverUnion,
+    /// Distance-IoU: IoU - (center distance / diagonal length of the minimal box containing both boxes)^2.
+    /// reference: https://arxiv.org/abs/1911.08287
+    DistanceIntersectionOverUnion,
 }
 
-#[derive(Debug, Copy, Clone)]
+/// The algorithm of non-maximum-suppression.
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum NonMaxSuppressionAlgorithm {
+    /// Remove the boxes overlapped with a higher scored box.
     DEFAULT,
+    /// Merge the overlapped boxes, weighted by scores.
     WEIGHTED,
+    /// Soft-NMS: decay the scores of overlapped boxes instead of removing them.
+    /// reference: https://arxiv.org/abs/1704.04503
+    SOFT(SoftNmsDecay),
+}
+
+/// The score decay function of Soft-NMS.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SoftNmsDecay {
+    /// `score *= 1 - overlap` if the overlap is greater than the suppression threshold.
+    Linear,
+    /// `score *= exp(-overlap^2 / sigma)`, the suppression threshold is not used.
+    Gaussian { sigma: f32 },
 }
 
 pub struct NonMaxSuppression {
@@ -18,6 +40,10 @@ pub struct NonMaxSuppression {
     algorithm: NonMaxSuppressionAlgorithm,
     max_results: usize,
     min_suppression_threshold: f32,
+    /// Only suppress the boxes which have the same category.
+    per_class: bool,
+    /// The boxes whose score decays below the threshold are removed, for Soft-NMS only.
+    min_score_threshold: f32,
 }
 
 impl NonMaxSuppression {
@@ -33,6 +59,8 @@ impl NonMaxSuppression {
             algorithm: NonMaxSuppressionAlgorithm::DEFAULT,
             max_results,
             min_suppression_threshold: 1.0, // default
+            per_class: false,
+            min_score_threshold: 0.0,
         }
     }
 
@@ -63,6 +91,16 @@ impl NonMaxSuppression {
         self.min_suppression_threshold = min_suppression_threshold;
     }
 
+    #[inline(always)]
+    pub fn set_per_class(&mut self, per_class: bool) {
+        self.per_class = per_class;
+    }
+
+    #[inline(always)]
+    pub fn set_min_score_threshold(&mut self, min_score_threshold: f32) {
+        self.min_score_threshold = min_score_threshold;
+    }
+
     #[inline(always)]
     pub fn max_results(mut self, max_results: i32) -> Self {
         self.max_results = if max_results < 0 {
@@ -98,22 +136,36 @@ impl NonMaxSuppression {
             NonMaxSuppressionAlgorithm::WEIGHTED => {
                 self.non_max_suppression_weighted(&mut detection_result.detections, indexed_scores);
             }
+            NonMaxSuppressionAlgorithm::SOFT(decay) => {
+                self.non_max_suppression_soft(&mut detection_result.detections, decay);
+            }
         }
     }
 
+    /// Return true if the two detections can suppress each other.
+    #[inline(always)]
+    fn suppressible(&self, class_1: u32, detection_2: &Detection) -> bool {
+        !self.per_class || class_1 == detection_2.categories[0].index
+    }
+
     fn non_max_suppression(
         &self,
         detections: &mut Vec<Detection>,
         indexed_scores: Vec<(usize, f32)>,
     ) {
         let mut retains = vec![false; detections.len()];
-        let mut retained_locations = Vec::new();
+        let mut retained_indices = Vec::new();
         for (index, score) in indexed_scores {
-            let location = &detections[index].bounding_box;
+            let detection = &detections[index];
             let mut suppressed = false;
 
-            for retained_location in &retained_locations {
-                let similarity = self.overlap_similarity(location, *retained_location);
+            for retained_index in &retained_indices {
+                let retained = &detections[*retained_index];
+                if !self.suppressible(detection.categories[0].index, retained) {
+                    continue;
+                }
+                let similarity =
+                    self.overlap_similarity(&detection.bounding_box, &retained.bounding_box);
                 if similarity > self.min_suppression_threshold {
                     suppressed = true;
                     break;
@@ -122,8 +174,8 @@ impl NonMaxSuppression {
 
             if !suppressed {
                 retains[index] = true;
-                retained_locations.push(location);
-                if retained_locations.len() >= self.max_results {
+                retained_indices.push(index);
+                if retained_indices.len() >= self.max_results {
                     break;
                 }
             }
@@ -143,6 +195,7 @@ impl NonMaxSuppression {
         while !indexed_scores.is_empty() {
             let detection = &mut in_detections[indexed_scores[0].0];
             let categories = std::mem::take(&mut detection.categories);
+            let class_index = categories[0].index;
             let mut bounding_box = detection.bounding_box.clone();
             let mut key_points = detection.key_points.take();
             let mut total_score = indexed_scores[0].1;
@@ -161,6 +214,10 @@ impl NonMaxSuppression {
             for i in 1..indexed_scores.len() {
                 let indexed_score = indexed_scores[i];
                 let rest_detection = &in_detections[indexed_score.0];
+                if !self.suppressible(class_index, rest_detection) {
+                    remains.push(indexed_score);
+                    continue;
+                }
 
                 let similarity = self.overlap_similarity(location, &rest_detection.bounding_box);
                 if similarity > self.min_suppression_threshold {
@@ -213,14 +270,68 @@ impl NonMaxSuppression {
         }
     }
 
+    fn non_max_suppression_soft(&self, out_detections: &mut Vec<Detection>, decay: SoftNmsDecay) {
+        let mut remains: Vec<usize> = (0..out_detections.len()).collect();
+        let mut selected = Vec::new();
+        while !remains.is_empty() && selected.len() < self.max_results {
+            // select the box with max score
+            let mut pos = 0;
+            for i in 1..remains.len() {
+                if out_detections[remains[i]].categories[0].score
+                    > out_detections[remains[pos]].categories[0].score
+                {
+                    pos = i;
+                }
+            }
+            let index = remains.swap_remove(pos);
+            selected.push(index);
+
+            // decay the scores of rest boxes
+            let class_index = out_detections[index].categories[0].index;
+            let location = out_detections[index].bounding_box.clone();
+            for i in remains.iter() {
+                let rest_detection = &mut out_detections[*i];
+                if !self.suppressible(class_index, rest_detection) {
+                    continue;
+                }
+                let similarity = self.overlap_similarity(&location, &rest_detection.bounding_box);
+                let weight = match decay {
+                    SoftNmsDecay::Linear => {
+                        if similarity > self.min_suppression_threshold {
+                            1. - similarity
+                        } else {
+                            1.
+                        }
+                    }
+                    SoftNmsDecay::Gaussian { sigma } => {
+                        let similarity = similarity.max(0.);
+                        (-similarity * similarity / sigma).exp()
+                    }
+                };
+                rest_detection.categories[0].score *= weight;
+            }
+            remains.retain(|i| out_detections[*i].categories[0].score >= self.min_score_threshold);
+        }
+
+        // output in the selected order, e.g. sorted by decayed scores
+        let mut in_detections: Vec<Option<Detection>> = std::mem::take(out_detections)
+            .into_iter()
+            .map(Some)
+            .collect();
+        for index in selected {
+            out_detections.push(in_detections[index].take().unwrap());
+        }
+    }
+
     #[inline]
     fn overlap_similarity(&self, rect_1: &Rect<f32>, rect_2: &Rect<f32>) -> f32 {
-        if let Some(intersection) = rect_1.intersect(rect_2) {
+        let similarity = if let Some(intersection) = rect_1.intersect(rect_2) {
             let intersection_area = intersection.area();
             let normalization = match self.overlap_type {
                 NonMaxSuppressionOverlapType::Jaccard => rect_1.union(rect_2).area(),
                 NonMaxSuppressionOverlapType::ModifiedJaccard => rect_2.area(),
-                NonMaxSuppressionOverlapType::IntersectionOverUnion => {
+                NonMaxSuppressionOverlapType::IntersectionOverUnion
+                | NonMaxSuppressionOverlapType::DistanceIntersectionOverUnion => {
                     rect_1.area() + rect_2.area() - intersection_area
                 }
             };
@@ -231,6 +342,133 @@ impl NonMaxSuppression {
             }
         } else {
             0.
+        };
+
+        if self.overlap_type == NonMaxSuppressionOverlapType::DistanceIntersectionOverUnion {
+            let enclosing = rect_1.union(rect_2);
+            let diagonal = enclosing.width().powi(2) + enclosing.height().powi(2);
+            if diagonal > 0. {
+                let dx = (rect_1.left + rect_1.right - rect_2.left - rect_2.right) * 0.5;
+                let dy = (rect_1.top + rect_1.bottom - rect_2.top - rect_2.bottom) * 0.5;
+                return similarity - (dx * dx + dy * dy) / diagonal;
+            }
         }
+        similarity
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::postprocess::Category;
+
+    fn detection(index: u32, score: f32, left: f32, top: f32, size: f32) -> Detection {
+        Detection {
+            categories: vec![Category {
+                index,
+                score,
+                category_name: None,
+                display_name: None,
+            }],
+            bounding_box: Rect {
+                left,
+                top,
+                right: left + size,
+                bottom: top + size,
+            },
+            key_points: None,
+        }
+    }
+
+    fn run(nms: &NonMaxSuppression) -> Vec<(u32, f32)> {
+        let mut result = DetectionResult {
+            detections: vec![
+                detection(0, 0.9, 0.1, 0.1, 0.4),
+                // same position, another category
+                detection(1, 0.8, 0.1, 0.1, 0.4),
+                // IoU with the first box is 0.6
+                detection(0, 0.7, 0.2, 0.1, 0.4),
+                detection(0, 0.6, 0.6, 0.6, 0.3),
+            ],
+        };
+        nms.do_nms(&mut result);
+        result
+            .detections
+            .iter()
+            .map(|d| (d.categories[0].index, d.categories[0].score))
+            .collect()
+    }
+
+    #[test]
+    fn test_nms_per_class() {
+        let mut nms = NonMaxSuppression::new(-1)
+            .overlap_type(NonMaxSuppressionOverlapType::IntersectionOverUnion);
+        nms.set_min_suppression_threshold(0.5);
+        assert_eq!(run(&nms), vec![(0, 0.9), (0, 0.6)]);
+        nms.set_per_class(true);
+        assert_eq!(run(&nms), vec![(0, 0.9), (1, 0.8), (0, 0.6)]);
+
+        nms.set_algorithm(NonMaxSuppressionAlgorithm::WEIGHTED);
+        let res = run(&nms);
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[1], (1, 0.8));
+    }
+
+    #[test]
+    fn test_soft_nms() {
+        let mut nms = NonMaxSuppression::new(-1)
+            .overlap_type(NonMaxSuppressionOverlapType::IntersectionOverUnion)
+            .algorithm(NonMaxSuppressionAlgorithm::SOFT(SoftNmsDecay::Linear));
+        nms.set_min_suppression_threshold(0.5);
+        nms.set_min_score_threshold(0.1);
+        let res = run(&nms);
+        // the second box is removed because the IoU is 1, the third box is decayed
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[0], (0, 0.9));
+        assert_eq!(res[1], (0, 0.6));
+        assert!((res[2].1 - 0.7 * 0.4).abs() < 1e-5);
+
+        nms.set_per_class(true);
+        assert_eq!(run(&nms).len(), 4);
+
+        nms.set_per_class(false);
+        nms.set_min_score_threshold(0.01);
+        nms.set_algorithm(NonMaxSuppressionAlgorithm::SOFT(SoftNmsDecay::Gaussian {
+            sigma: 0.5,
+        }));
+        let res = run(&nms);
+        assert_eq!(res.len(), 4);
+        assert_eq!(res[1], (0, 0.6));
+        assert!((res[2].1 - 0.7 * (-0.36f32 / 0.5).exp()).abs() < 1e-5);
+        // decayed by the first and the third box
+        assert!((res[3].1 - 0.8 * (-2f32).exp() * (-0.72f32).exp()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_diou() {
+        let nms = NonMaxSuppression::new(-1)
+            .overlap_type(NonMaxSuppressionOverlapType::DistanceIntersectionOverUnion);
+        let r1 = Rect {
+            left: 0.,
+            top: 0.,
+            right: 2.,
+            bottom: 2.,
+        };
+        let r2 = Rect {
+            left: 1.,
+            top: 0.,
+            right: 3.,
+            bottom: 2.,
+        };
+        // IoU = 2 / 6, center distance = 1, diagonal = 9 + 4
+        let diou = nms.overlap_similarity(&r1, &r2);
+        assert!((diou - (2. / 6. - 1. / 13.)).abs() < 1e-6);
+        let r3 = Rect {
+            left: 4.,
+            top: 0.,
+            right: 6.,
+            bottom: 2.,
+        };
+        assert!(nms.overlap_similarity(&r1, &r3) < 0.);
     }
 }
@@ -210,6 +210,37 @@ impl SsdAnchorsBuilder {
         ans
     }
 
+    /// Check the options before generating, avoid panic for invalid user options.
+    pub fn check(&self) -> Result<(), crate::Error> {
+        let err = |msg: String| Err(crate::Error::ArgumentError(msg));
+        if self.num_layers == 0 {
+            return err("The number of anchor layers cannot be `0`".into());
+        }
+        if !self.feature_map_height.is_empty() && !self.feature_map_width.is_empty() {
+            if self.feature_map_height.len() < self.num_layers
+                || self.feature_map_width.len() < self.num_layers
+            {
+                return err(format!(
+                    "Expect `{}` feature map sizes, but got `{}`",
+                    self.num_layers,
+                    self.feature_map_height
+                        .len()
+                        .min(self.feature_map_width.len())
+                ));
+            }
+        } else if self.strides.len() < self.num_layers {
+            return err(format!(
+                "Expect `{}` anchor strides, but got `{}`",
+                self.num_layers,
+                self.strides.len()
+            ));
+        }
+        if self.strides.iter().any(|s| *s <= 0) {
+            return err(format!("Invalid anchor strides `{:?}`", self.strides));
+        }
+        Ok(())
+    }
+
     /// Size of input images.
     pub fn input_size_width(mut self, input_size_width: u32) -> Self {
         self.input_size_width = input_size_width;
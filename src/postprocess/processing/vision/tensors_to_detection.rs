@@ -21,6 +21,60 @@ impl Default for DetectionBoxFormat {
     }
 }
 
+/// Options to decode the raw tensors of SSD detection models, which can be read from model metadata.
+#[derive(Debug, Clone)]
+pub(crate) struct SsdDecodingOptions {
+    /// The number of output classes predicted by the detection model.
+    pub num_classes: usize,
+    /// The number of output boxes predicted by the detection model.
+    pub num_boxes: usize,
+    /// The number of output values per boxes predicted by the detection model.
+    pub num_coords: usize,
+    /// The offset of keypoint coordinates in the location tensor.
+    pub keypoint_coord_offset: usize,
+    /// The number of predicted key points.
+    pub num_key_points: usize,
+    /// The dimension of each keypoint, e.g. number of values predicted for each keypoint.
+    pub num_values_per_key_point: usize,
+    /// Parameters for decoding SSD detection model.
+    pub x_scale: f32,
+    pub y_scale: f32,
+    pub w_scale: f32,
+    pub h_scale: f32,
+    pub apply_exponential_on_box_size: bool,
+    pub sigmoid_score: bool,
+}
+
+impl Default for SsdDecodingOptions {
+    fn default() -> Self {
+        Self {
+            num_classes: 1,
+            num_boxes: 0,
+            num_coords: 4,
+            keypoint_coord_offset: 4,
+            num_key_points: 0,
+            num_values_per_key_point: 2,
+            x_scale: 0.0,
+            y_scale: 0.0,
+            w_scale: 0.0,
+            h_scale: 0.0,
+            apply_exponential_on_box_size: false,
+            sigmoid_score: false,
+        }
+    }
+}
+
+impl SsdDecodingOptions {
+    /// Check whether the location tensor can hold the bounding box and key points.
+    #[inline(always)]
+    pub(crate) fn is_valid(&self) -> bool {
+        self.num_classes > 0
+            && self.num_coords >= 4
+            && self.num_coords
+                >= self.keypoint_coord_offset + self.num_key_points * self.num_values_per_key_point
+    }
+}
+
 struct ToDetectionOptions {
     /// The number of output classes predicted by the detection model.
     /// if categories buffer is not None, num_classes must be 1
@@ -234,6 +288,22 @@ impl<'a> TensorsToDetection<'a> {
         self.options.h_scale = h_scale;
     }
 
+    /// Set the options to decode SSD model outputs, including the number of classes, coords, key points and scales.
+    pub(crate) fn set_decoding_options(&mut self, decoding_options: &SsdDecodingOptions) {
+        self.options.num_classes = decoding_options.num_classes;
+        self.options.num_coords = decoding_options.num_coords;
+        self.options.num_key_points = decoding_options.num_key_points;
+        self.options.num_values_per_key_point = decoding_options.num_values_per_key_point;
+        self.options.keypoint_coord_offset = decoding_options.keypoint_coord_offset;
+        self.options.x_scale = decoding_options.x_scale;
+        self.options.y_scale = decoding_options.y_scale;
+        self.options.w_scale = decoding_options.w_scale;
+        self.options.h_scale = decoding_options.h_scale;
+        self.options.apply_exponential_on_box_size = decoding_options.apply_exponential_on_box_size;
+        self.options.sigmoid_score = decoding_options.sigmoid_score;
+        check_options_valid!(self.options);
+    }
+
     #[inline(always)]
     pub(crate) fn set_sigmoid_score(&mut self, sigmoid_score: bool) {
         self.options.sigmoid_score = sigmoid_score;
@@ -265,6 +335,16 @@ impl<'a> TensorsToDetection<'a> {
             .set_min_suppression_threshold(min_suppression_threshold);
     }
 
+    #[inline(always)]
+    pub(crate) fn set_nms_per_class(&mut self, per_class: bool) {
+        self.nms.set_per_class(per_class);
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_nms_min_score_threshold(&mut self, min_score_threshold: f32) {
+        self.nms.set_min_score_threshold(min_score_threshold);
+    }
+
     #[inline(always)]
     pub(crate) fn location_buf(&mut self) -> &mut [u8] {
         self.location_buf.data_buffer.as_mut_slice()
@@ -289,7 +369,7 @@ impl<'a> TensorsToDetection<'a> {
         if let Some(ref mut c) = self.categories_buf {
             realloc_output_buffer!(c, num_boxes);
         }
-        realloc_output_buffer!(self.location_buf, num_boxes * num_boxes);
+        realloc_output_buffer!(self.location_buf, num_boxes * self.options.num_coords);
     }
 
     pub(crate) fn result(&mut self, num_boxes: usize) -> DetectionResult {
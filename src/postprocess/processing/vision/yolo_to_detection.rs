@@ -0,0 +1,344 @@
+use super::*;
+use crate::postprocess::{CategoriesFilter, Detection, DetectionResult, Rect};
+use crate::Error;
+
+/// Options to decode the single output tensor of YOLO-family models (e.g. YOLOv5, YOLOv8).
+/// `None` means detecting it from the model.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct YoloDecodingOptions {
+    /// Whether the output tensor is transposed, e.g. `[1, 4 + num_classes, num_boxes]` (YOLOv8) instead of
+    /// `[1, num_boxes, 4 + num_classes]`. Default is detected from the shape (the longer dimension is boxes).
+    pub transposed: Option<bool>,
+    /// Whether each box has an objectness score after the box coordinates, e.g. YOLOv5.
+    /// Default is detected from the number of labels, or from the layout if the labels count is not matched:
+    /// transposed outputs have no objectness.
+    pub objectness: Option<bool>,
+    /// Whether the box coordinates are normalized to `[0, 1]`, otherwise they are in pixels of the model input.
+    /// Default is detected from the model format: TensorFlowLite exports are normalized, and other formats
+    /// (such as ONNX) are in pixels.
+    pub normalized: Option<bool>,
+}
+
+/// The resolved layout of YOLO-family model output: `[x_center, y_center, width, height, (objectness), scores...]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct YoloOutputLayout {
+    pub num_boxes: usize,
+    pub num_classes: usize,
+    pub transposed: bool,
+    pub objectness: bool,
+    pub normalized: bool,
+}
+
+impl YoloOutputLayout {
+    /// Return true if the output shape likes a YOLO-family single output tensor, e.g. `[1, N, C]`.
+    #[inline]
+    pub(crate) fn is_yolo_shape(shape: &[usize]) -> bool {
+        let dims: Vec<usize> = shape.iter().cloned().filter(|d| *d != 1).collect();
+        dims.len() == 2 && dims[0].min(dims[1]) > 4
+    }
+
+    /// Resolve the output layout from the tensor shape, the labels count, the model format and the user options.
+    pub(crate) fn new(
+        shape: &[usize],
+        num_labels: usize,
+        model_encoding: crate::GraphEncoding,
+        options: &YoloDecodingOptions,
+    ) -> Result<Self, Error> {
+        let dims: Vec<usize> = shape.iter().cloned().filter(|d| *d != 1).collect();
+        if dims.len() != 2 {
+            return Err(Error::ModelInconsistentError(format!(
+                "Expect YOLO output shape `[1, num_boxes, 4 + num_classes]` or transposed, but got `{:?}`",
+                shape
+            )));
+        }
+        let transposed = options.transposed.unwrap_or(dims[0] < dims[1]);
+        let (num_boxes, channels) = if transposed {
+            (dims[1], dims[0])
+        } else {
+            (dims[0], dims[1])
+        };
+        let objectness = match options.objectness {
+            Some(o) => o,
+            None if channels == num_labels + 5 => true,
+            None if channels == num_labels + 4 => false,
+            None => !transposed,
+        };
+        let num_coords = if objectness { 5 } else { 4 };
+        if channels <= num_coords {
+            return Err(Error::ModelInconsistentError(format!(
+                "YOLO output has `{}` values per box, which cannot hold the box and class scores",
+                channels
+            )));
+        }
+        Ok(Self {
+            num_boxes,
+            num_classes: channels - num_coords,
+            transposed,
+            objectness,
+            normalized: options.normalized.unwrap_or(matches!(
+                model_encoding,
+                crate::GraphEncoding::TensorflowLite
+            )),
+        })
+    }
+
+    #[inline(always)]
+    fn channels(&self) -> usize {
+        self.num_classes + if self.objectness { 5 } else { 4 }
+    }
+}
+
+/// Convert YOLO-family single output tensor to detection results.
+pub(crate) struct YoloToDetection<'a> {
+    nms: NonMaxSuppression,
+    categories_filter: CategoriesFilter<'a>,
+    output_buf: OutputBuffer,
+    layout: YoloOutputLayout,
+    /// Size of model input, to normalize the box coordinates in pixels.
+    input_width: f32,
+    input_height: f32,
+}
+
+impl<'a> YoloToDetection<'a> {
+    #[inline]
+    pub(crate) fn new(
+        categories_filter: CategoriesFilter<'a>,
+        max_results: i32,
+        output_buf: (TensorType, Option<QuantizationParameters>),
+        layout: YoloOutputLayout,
+        input_width: u32,
+        input_height: u32,
+    ) -> Self {
+        let elem_size = layout.num_boxes * layout.channels();
+        Self {
+            nms: NonMaxSuppression::new(max_results),
+            categories_filter,
+            output_buf: empty_output_buffer!(output_buf, elem_size),
+            layout,
+            input_width: input_width as f32,
+            input_height: input_height as f32,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn nms_mut(&mut self) -> &mut NonMaxSuppression {
+        &mut self.nms
+    }
+
+    #[inline(always)]
+    pub(crate) fn output_buf(&mut self) -> &mut [u8] {
+        self.output_buf.data_buffer.as_mut_slice()
+    }
+
+    pub(crate) fn result(&mut self) -> DetectionResult {
+        let data = output_buffer_mut_slice!(self.output_buf);
+        let layout = self.layout;
+        let channels = layout.channels();
+        debug_assert!(data.len() >= layout.num_boxes * channels);
+        let value = |box_index: usize, channel: usize| -> f32 {
+            if layout.transposed {
+                data[channel * layout.num_boxes + box_index]
+            } else {
+                data[box_index * channels + channel]
+            }
+        };
+
+        let (x_scale, y_scale) = if layout.normalized {
+            (1., 1.)
+        } else {
+            (1. / self.input_width, 1. / self.input_height)
+        };
+        let score_offset = if layout.objectness { 5 } else { 4 };
+
+        let mut detections = Vec::new();
+        for i in 0..layout.num_boxes {
+            let mut class_index = 0;
+            let mut max_score = value(i, score_offset);
+            for c in 1..layout.num_classes {
+                let s = value(i, score_offset + c);
+                if s > max_score {
+                    max_score = s;
+                    class_index = c;
+                }
+            }
+            if layout.objectness {
+                max_score *= value(i, 4);
+            }
+
+            if let Some(category) = self
+                .categories_filter
+                .create_category(class_index, max_score)
+            {
+                let x_center = value(i, 0) * x_scale;
+                let y_center = value(i, 1) * y_scale;
+                let half_w = value(i, 2) * x_scale * 0.5;
+                let half_h = value(i, 3) * y_scale * 0.5;
+                detections.push(Detection {
+                    categories: vec![category],
+                    bounding_box: Rect {
+                        left: x_center - half_w,
+                        top: y_center - half_h,
+                        right: x_center + half_w,
+                        bottom: y_center + half_h,
+                    },
+                    key_points: None,
+                });
+            }
+        }
+
+        let mut result = DetectionResult { detections };
+        self.nms.do_nms(&mut result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::GraphEncoding;
+
+    #[test]
+    fn test_yolo_output_layout() {
+        let options = YoloDecodingOptions::default();
+        assert!(YoloOutputLayout::is_yolo_shape(&[1, 84, 8400]));
+        assert!(!YoloOutputLayout::is_yolo_shape(&[1, 25, 4]));
+        assert!(!YoloOutputLayout::is_yolo_shape(&[1, 1]));
+
+        // yolov8
+        let layout =
+            YoloOutputLayout::new(&[1, 84, 8400], 80, GraphEncoding::TensorflowLite, &options)
+                .unwrap();
+        assert_eq!((layout.num_boxes, layout.num_classes), (8400, 80));
+        assert!(layout.transposed && !layout.objectness);
+        // yolov5
+        let layout =
+            YoloOutputLayout::new(&[1, 25200, 85], 80, GraphEncoding::TensorflowLite, &options)
+                .unwrap();
+        assert_eq!((layout.num_boxes, layout.num_classes), (25200, 80));
+        assert!(!layout.transposed && layout.objectness);
+        // labels are not matched, detect from the layout
+        let layout =
+            YoloOutputLayout::new(&[1, 25200, 85], 0, GraphEncoding::TensorflowLite, &options)
+                .unwrap();
+        assert!(layout.objectness);
+        let layout =
+            YoloOutputLayout::new(&[1, 25200, 85], 81, GraphEncoding::TensorflowLite, &options)
+                .unwrap();
+        assert!(!layout.objectness);
+        // options
+        let options = YoloDecodingOptions {
+            transposed: Some(true),
+            objectness: Some(true),
+            normalized: Some(true),
+        };
+        let layout =
+            YoloOutputLayout::new(&[1, 8, 6], 0, GraphEncoding::TensorflowLite, &options).unwrap();
+        assert_eq!((layout.num_boxes, layout.num_classes), (6, 3));
+        assert!(
+            YoloOutputLayout::new(&[1, 5, 8], 0, GraphEncoding::TensorflowLite, &options).is_err()
+        );
+        assert!(
+            YoloOutputLayout::new(&[8, 6, 2], 0, GraphEncoding::TensorflowLite, &options).is_err()
+        );
+        // the coordinates are normalized for tflite models and in pixels for others, unless it is set
+        assert!(layout.normalized);
+        let options = YoloDecodingOptions::default();
+        let layout =
+            YoloOutputLayout::new(&[1, 84, 8400], 80, GraphEncoding::TensorflowLite, &options);
+        assert!(layout.unwrap().normalized);
+        let layout = YoloOutputLayout::new(&[1, 84, 8400], 80, GraphEncoding::Onnx, &options);
+        assert!(!layout.unwrap().normalized);
+        let options = YoloDecodingOptions {
+            normalized: Some(true),
+            ..Default::default()
+        };
+        let layout = YoloOutputLayout::new(&[1, 84, 8400], 80, GraphEncoding::Onnx, &options);
+        assert!(layout.unwrap().normalized);
+    }
+
+    #[test]
+    fn test_yolo_to_detection() {
+        let labels = b"a\nb";
+        let filter = CategoriesFilter::new_full(0.3, labels, None);
+        let options = YoloDecodingOptions {
+            transposed: Some(false),
+            ..Default::default()
+        };
+        let layout = YoloOutputLayout::new(&[1, 3, 7], 2, GraphEncoding::Onnx, &options).unwrap();
+        assert!(layout.objectness);
+        let mut yolo = YoloToDetection::new(filter, -1, (TensorType::F32, None), layout, 100, 50);
+        yolo.nms_mut()
+            .set_overlap_type(NonMaxSuppressionOverlapType::IntersectionOverUnion);
+        yolo.nms_mut().set_min_suppression_threshold(0.5);
+        // x, y, w, h, objectness, score a, score b
+        let data: [[f32; 7]; 3] = [
+            [50., 25., 20., 10., 0.9, 0.1, 0.8],
+            [51., 25., 20., 10., 0.7, 0.1, 0.9],
+            [10., 10., 10., 10., 0.5, 0.5, 0.1],
+        ];
+        let bytes: Vec<u8> = data
+            .iter()
+            .flatten()
+            .flat_map(|f| f.to_ne_bytes())
+            .collect();
+        yolo.output_buf().copy_from_slice(&bytes);
+        let result = yolo.result();
+        assert_eq!(result.detections.len(), 1);
+        let d = &result.detections[0];
+        assert_eq!(d.categories[0].index, 1);
+        assert_eq!(d.categories[0].category_name.as_deref(), Some("b"));
+        assert!((d.categories[0].score - 0.72).abs() < 1e-6);
+        assert_eq!(
+            d.bounding_box,
+            Rect {
+                left: 0.4,
+                top: 0.4,
+                right: 0.6,
+                bottom: 0.6,
+            }
+        );
+
+        // models without labels
+        let mut filter = CategoriesFilter::new_without_labels(&Default::default(), 2);
+        filter.set_score_threshold(0.3);
+        let mut yolo = YoloToDetection::new(filter, -1, (TensorType::F32, None), layout, 100, 50);
+        yolo.output_buf().copy_from_slice(&bytes);
+        let result = yolo.result();
+        assert_eq!(result.detections.len(), 2);
+        assert_eq!(result.detections[0].categories[0].index, 1);
+        assert!(result.detections[0].categories[0].category_name.is_none());
+
+        // normalized coordinates, an outlier box does not change the coordinate space
+        let layout = YoloOutputLayout::new(&[1, 2, 7], 2, GraphEncoding::TensorflowLite, &options);
+        let filter = CategoriesFilter::new_full(0.3, labels, None);
+        let mut yolo = YoloToDetection::new(
+            filter,
+            -1,
+            (TensorType::F32, None),
+            layout.unwrap(),
+            100,
+            50,
+        );
+        let data: [[f32; 7]; 2] = [
+            [0.5, 0.5, 0.2, 0.2, 0.9, 0.1, 0.8],
+            [300., 300., 0.2, 0.2, 0.01, 0.9, 0.1],
+        ];
+        let bytes: Vec<u8> = data
+            .iter()
+            .flatten()
+            .flat_map(|f| f.to_ne_bytes())
+            .collect();
+        yolo.output_buf().copy_from_slice(&bytes);
+        let result = yolo.result();
+        assert_eq!(result.detections.len(), 1);
+        assert_eq!(
+            result.detections[0].bounding_box,
+            Rect {
+                left: 0.4,
+                top: 0.4,
+                right: 0.6,
+                bottom: 0.6,
+            }
+        );
+    }
+}
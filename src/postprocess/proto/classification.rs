@@ -0,0 +1,99 @@
+use crate::postprocess::{Category, Classifications};
+
+/// ```mediapipe.Classification``` in ```mediapipe/framework/formats/classification.proto```
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Classification {
+    /// The index of the class in the corresponding label map.
+    #[prost(int32, optional, tag = "1")]
+    pub index: Option<i32>,
+    /// The probability score for this class.
+    #[prost(float, optional, tag = "2")]
+    pub score: Option<f32>,
+    /// Label or name of the class.
+    #[prost(string, optional, tag = "3")]
+    pub label: Option<String>,
+    /// Optional human-readable string for display purposes.
+    #[prost(string, optional, tag = "4")]
+    pub display_name: Option<String>,
+}
+
+/// ```mediapipe.ClassificationList``` in ```mediapipe/framework/formats/classification.proto```
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ClassificationList {
+    #[prost(message, repeated, tag = "1")]
+    pub classification: Vec<Classification>,
+}
+
+impl From<&Category> for Classification {
+    fn from(c: &Category) -> Self {
+        Self {
+            index: Some(c.index as i32),
+            score: Some(c.score),
+            label: c.category_name.clone(),
+            display_name: c.display_name.clone(),
+        }
+    }
+}
+
+impl From<&Classification> for Category {
+    fn from(c: &Classification) -> Self {
+        Self {
+            index: c.index.unwrap_or(0).max(0) as u32,
+            score: c.score.unwrap_or(0.),
+            category_name: c.label.clone(),
+            display_name: c.display_name.clone(),
+        }
+    }
+}
+
+impl From<&Classifications> for ClassificationList {
+    fn from(c: &Classifications) -> Self {
+        Self {
+            classification: c.categories.iter().map(Classification::from).collect(),
+        }
+    }
+}
+
+impl From<&ClassificationList> for Classifications {
+    /// The head index is 0, and the head name is ```None```, because the ```ClassificationList``` has no head info.
+    fn from(c: &ClassificationList) -> Self {
+        Self {
+            head_index: 0,
+            head_name: None,
+            categories: c.classification.iter().map(Category::from).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use prost::Message;
+
+    #[test]
+    fn test_classification_proto() {
+        let classifications = Classifications {
+            head_index: 0,
+            head_name: None,
+            categories: vec![Category {
+                index: 2,
+                score: 0.5,
+                category_name: Some("a".into()),
+                display_name: None,
+            }],
+        };
+        let list = ClassificationList::from(&classifications);
+        let buf = list.encode_to_vec();
+        // field 1 (len 10): { index: 2, score: 0.5, label: "a" }
+        assert_eq!(
+            buf,
+            vec![0x0a, 10, 0x08, 2, 0x15, 0, 0, 0, 0x3f, 0x1a, 1, b'a']
+        );
+        let c = Classifications::from(&ClassificationList::decode(buf.as_slice()).unwrap());
+        assert_eq!(c.categories.len(), 1);
+        assert_eq!(c.categories[0].index, 2);
+        assert_eq!(c.categories[0].score, 0.5);
+        assert_eq!(c.categories[0].category_name.as_deref(), Some("a"));
+        assert_eq!(c.categories[0].display_name, None);
+    }
+}
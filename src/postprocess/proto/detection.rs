@@ -0,0 +1,310 @@
+use crate::postprocess::{Category, DetectionResult, NormalizedKeypoint, Rect};
+use crate::Error;
+
+/// ```mediapipe.LocationData``` in ```mediapipe/framework/formats/location_data.proto```.
+/// The binary mask is not supported and will be ignored when decoding.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LocationData {
+    #[prost(enumeration = "LocationDataFormat", optional, tag = "1")]
+    pub format: Option<i32>,
+    #[prost(message, optional, tag = "2")]
+    pub bounding_box: Option<BoundingBox>,
+    #[prost(message, optional, tag = "3")]
+    pub relative_bounding_box: Option<RelativeBoundingBox>,
+    #[prost(message, repeated, tag = "5")]
+    pub relative_keypoints: Vec<RelativeKeypoint>,
+}
+
+/// ```mediapipe.LocationData.Format```, the supported formats for representing location data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum LocationDataFormat {
+    /// The full image.
+    Global = 0,
+    /// A rectangle in pixels.
+    BoundingBox = 1,
+    /// A rectangle normalized to [0, 1] by the image size.
+    RelativeBoundingBox = 2,
+    /// A binary mask.
+    Mask = 3,
+}
+
+/// ```mediapipe.LocationData.BoundingBox```, a rectangle in pixels.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BoundingBox {
+    #[prost(int32, optional, tag = "1")]
+    pub xmin: Option<i32>,
+    #[prost(int32, optional, tag = "2")]
+    pub ymin: Option<i32>,
+    #[prost(int32, optional, tag = "3")]
+    pub width: Option<i32>,
+    #[prost(int32, optional, tag = "4")]
+    pub height: Option<i32>,
+}
+
+/// ```mediapipe.LocationData.RelativeBoundingBox```, a rectangle normalized to [0, 1] by the image size.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RelativeBoundingBox {
+    #[prost(float, optional, tag = "1")]
+    pub xmin: Option<f32>,
+    #[prost(float, optional, tag = "2")]
+    pub ymin: Option<f32>,
+    #[prost(float, optional, tag = "3")]
+    pub width: Option<f32>,
+    #[prost(float, optional, tag = "4")]
+    pub height: Option<f32>,
+}
+
+/// ```mediapipe.LocationData.RelativeKeypoint```, a keypoint normalized to [0, 1] by the image size.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RelativeKeypoint {
+    #[prost(float, optional, tag = "1")]
+    pub x: Option<f32>,
+    #[prost(float, optional, tag = "2")]
+    pub y: Option<f32>,
+    #[prost(string, optional, tag = "3")]
+    pub keypoint_label: Option<String>,
+    #[prost(float, optional, tag = "4")]
+    pub score: Option<f32>,
+}
+
+/// ```mediapipe.Detection``` in ```mediapipe/framework/formats/detection.proto```
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Detection {
+    /// The labels of categories, which have the same length as ```score``` if not empty.
+    #[prost(string, repeated, tag = "1")]
+    pub label: Vec<String>,
+    /// The indices of categories, which have the same length as ```score``` if not empty.
+    #[prost(int32, repeated, packed = "true", tag = "2")]
+    pub label_id: Vec<i32>,
+    #[prost(float, repeated, packed = "true", tag = "3")]
+    pub score: Vec<f32>,
+    #[prost(message, optional, tag = "4")]
+    pub location_data: Option<LocationData>,
+    #[prost(string, optional, tag = "5")]
+    pub feature_tag: Option<String>,
+    #[prost(string, optional, tag = "6")]
+    pub track_id: Option<String>,
+    #[prost(int64, optional, tag = "7")]
+    pub detection_id: Option<i64>,
+    #[prost(message, repeated, tag = "8")]
+    pub associated_detections: Vec<AssociatedDetection>,
+    /// The human-readable names of categories, which have the same length as ```score``` if not empty.
+    #[prost(string, repeated, tag = "9")]
+    pub display_name: Vec<String>,
+    #[prost(int64, optional, tag = "10")]
+    pub timestamp_usec: Option<i64>,
+}
+
+/// ```mediapipe.Detection.AssociatedDetection```, which associates a detection with other detections.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AssociatedDetection {
+    #[prost(int32, optional, tag = "1")]
+    pub id: Option<i32>,
+    #[prost(float, optional, tag = "2")]
+    pub confidence: Option<f32>,
+}
+
+/// ```mediapipe.DetectionList``` in ```mediapipe/framework/formats/detection.proto```
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DetectionList {
+    #[prost(message, repeated, tag = "1")]
+    pub detection: Vec<Detection>,
+}
+
+impl From<&crate::postprocess::Detection> for Detection {
+    fn from(d: &crate::postprocess::Detection) -> Self {
+        // mediapipe uses empty strings for the missing names
+        let names = |f: fn(&Category) -> &Option<String>| -> Vec<String> {
+            if d.categories.iter().all(|c| f(c).is_none()) {
+                return vec![];
+            }
+            d.categories
+                .iter()
+                .map(|c| f(c).clone().unwrap_or_default())
+                .collect()
+        };
+        let b = &d.bounding_box;
+        Self {
+            label: names(|c| &c.category_name),
+            label_id: d.categories.iter().map(|c| c.index as i32).collect(),
+            score: d.categories.iter().map(|c| c.score).collect(),
+            location_data: Some(LocationData {
+                format: Some(LocationDataFormat::RelativeBoundingBox as i32),
+                bounding_box: None,
+                relative_bounding_box: Some(RelativeBoundingBox {
+                    xmin: Some(b.left),
+                    ymin: Some(b.top),
+                    width: Some(b.right - b.left),
+                    height: Some(b.bottom - b.top),
+                }),
+                relative_keypoints: d
+                    .key_points
+                    .iter()
+                    .flatten()
+                    .map(|k| RelativeKeypoint {
+                        x: Some(k.x),
+                        y: Some(k.y),
+                        keypoint_label: k.label.clone(),
+                        score: k.score,
+                    })
+                    .collect(),
+            }),
+            display_name: names(|c| &c.display_name),
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<&Detection> for crate::postprocess::Detection {
+    type Error = Error;
+
+    /// Only the relative bounding box is supported, because the image size is unknown.
+    fn try_from(d: &Detection) -> Result<Self, Self::Error> {
+        let location_data = d.location_data.as_ref();
+        let b = location_data
+            .and_then(|l| l.relative_bounding_box.as_ref())
+            .ok_or_else(|| {
+                Error::ArgumentError("The detection has no relative bounding box".into())
+            })?;
+        for (name, len) in [
+            ("label", d.label.len()),
+            ("label_id", d.label_id.len()),
+            ("display_name", d.display_name.len()),
+        ] {
+            if len != 0 && len != d.score.len() {
+                return Err(Error::ArgumentError(format!(
+                    "The detection has `{}` scores, but has `{}` {}",
+                    d.score.len(),
+                    len,
+                    name
+                )));
+            }
+        }
+        let name = |names: &Vec<String>, i: usize| names.get(i).filter(|n| !n.is_empty()).cloned();
+        let categories = (0..d.score.len())
+            .map(|i| Category {
+                index: d.label_id.get(i).cloned().unwrap_or(0).max(0) as u32,
+                score: d.score[i],
+                category_name: name(&d.label, i),
+                display_name: name(&d.display_name, i),
+            })
+            .collect();
+        let (left, top) = (b.xmin.unwrap_or(0.), b.ymin.unwrap_or(0.));
+        let key_points = location_data
+            .map(|l| &l.relative_keypoints)
+            .filter(|k| !k.is_empty())
+            .map(|ks| {
+                ks.iter()
+                    .map(|k| NormalizedKeypoint {
+                        x: k.x.unwrap_or(0.),
+                        y: k.y.unwrap_or(0.),
+                        label: k.keypoint_label.clone(),
+                        score: k.score,
+                    })
+                    .collect()
+            });
+        Ok(Self {
+            categories,
+            bounding_box: Rect {
+                left,
+                top,
+                right: left + b.width.unwrap_or(0.),
+                bottom: top + b.height.unwrap_or(0.),
+            },
+            key_points,
+        })
+    }
+}
+
+impl From<&DetectionResult> for DetectionList {
+    fn from(r: &DetectionResult) -> Self {
+        Self {
+            detection: r.detections.iter().map(Detection::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<&DetectionList> for DetectionResult {
+    type Error = Error;
+
+    fn try_from(l: &DetectionList) -> Result<Self, Self::Error> {
+        Ok(Self {
+            detections: l
+                .detection
+                .iter()
+                .map(crate::postprocess::Detection::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use prost::Message;
+
+    #[test]
+    fn test_detection_proto() {
+        let result = DetectionResult {
+            detections: vec![crate::postprocess::Detection {
+                categories: vec![
+                    Category {
+                        index: 3,
+                        score: 0.75,
+                        category_name: Some("cat".into()),
+                        display_name: None,
+                    },
+                    Category {
+                        index: 4,
+                        score: 0.25,
+                        category_name: None,
+                        display_name: None,
+                    },
+                ],
+                bounding_box: Rect {
+                    left: 0.25,
+                    top: 0.5,
+                    right: 0.5,
+                    bottom: 1.,
+                },
+                key_points: Some(vec![NormalizedKeypoint {
+                    x: 0.25,
+                    y: 0.75,
+                    label: None,
+                    score: Some(0.5),
+                }]),
+            }],
+        };
+        let list = DetectionList::from(&result);
+        let d = &list.detection[0];
+        assert_eq!(d.label, vec!["cat".to_string(), String::new()]);
+        assert_eq!(d.label_id, vec![3, 4]);
+        assert!(d.display_name.is_empty());
+        let relative_box = d
+            .location_data
+            .as_ref()
+            .unwrap()
+            .relative_bounding_box
+            .as_ref()
+            .unwrap();
+        assert_eq!(relative_box.width, Some(0.25));
+
+        let decoded = DetectionList::decode(list.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, list);
+        let r = DetectionResult::try_from(&decoded).unwrap();
+        let d = &r.detections[0];
+        assert_eq!(d.bounding_box, result.detections[0].bounding_box);
+        assert_eq!(d.categories[0].category_name.as_deref(), Some("cat"));
+        assert_eq!(d.categories[1].category_name, None);
+        assert_eq!(d.categories[1].index, 4);
+        assert_eq!(d.key_points.as_ref().unwrap()[0].score, Some(0.5));
+
+        // absolute bounding box and inconsistent lengths are not supported
+        let mut d = list.detection[0].clone();
+        d.label_id.pop();
+        assert!(crate::postprocess::Detection::try_from(&d).is_err());
+        d.location_data.as_mut().unwrap().relative_bounding_box = None;
+        assert!(crate::postprocess::Detection::try_from(&d).is_err());
+    }
+}
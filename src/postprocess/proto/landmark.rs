@@ -0,0 +1,108 @@
+use crate::postprocess::Landmarks;
+
+macro_rules! landmark_proto_impl {
+    ( $landmark:ident, $landmark_list:ident, $proto_name:literal ) => {
+        #[doc = concat!("```mediapipe.", $proto_name, "``` in ```mediapipe/framework/formats/landmark.proto```")]
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct $landmark {
+            #[prost(float, optional, tag = "1")]
+            pub x: Option<f32>,
+            #[prost(float, optional, tag = "2")]
+            pub y: Option<f32>,
+            #[prost(float, optional, tag = "3")]
+            pub z: Option<f32>,
+            #[prost(float, optional, tag = "4")]
+            pub visibility: Option<f32>,
+            #[prost(float, optional, tag = "5")]
+            pub presence: Option<f32>,
+        }
+
+        #[doc = concat!("```mediapipe.", $proto_name, "List``` in ```mediapipe/framework/formats/landmark.proto```")]
+        #[derive(Clone, PartialEq, prost::Message)]
+        pub struct $landmark_list {
+            #[prost(message, repeated, tag = "1")]
+            pub landmark: Vec<$landmark>,
+        }
+
+        impl From<&crate::postprocess::Landmark> for $landmark {
+            /// The landmark name will be dropped, because the protobuf message has no name.
+            fn from(l: &crate::postprocess::Landmark) -> Self {
+                Self {
+                    x: Some(l.x),
+                    y: Some(l.y),
+                    z: Some(l.z),
+                    visibility: l.visibility,
+                    presence: l.presence,
+                }
+            }
+        }
+
+        impl From<&$landmark> for crate::postprocess::Landmark {
+            fn from(l: &$landmark) -> Self {
+                Self {
+                    x: l.x.unwrap_or(0.),
+                    y: l.y.unwrap_or(0.),
+                    z: l.z.unwrap_or(0.),
+                    visibility: l.visibility,
+                    presence: l.presence,
+                    name: None,
+                }
+            }
+        }
+
+        impl From<&Landmarks> for $landmark_list {
+            fn from(l: &Landmarks) -> Self {
+                Self {
+                    landmark: l.iter().map($landmark::from).collect(),
+                }
+            }
+        }
+
+        impl From<&$landmark_list> for Landmarks {
+            fn from(l: &$landmark_list) -> Self {
+                Self(
+                    l.landmark
+                        .iter()
+                        .map(crate::postprocess::Landmark::from)
+                        .collect(),
+                )
+            }
+        }
+    };
+}
+
+landmark_proto_impl!(Landmark, LandmarkList, "Landmark");
+landmark_proto_impl!(
+    NormalizedLandmark,
+    NormalizedLandmarkList,
+    "NormalizedLandmark"
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use prost::Message;
+
+    #[test]
+    fn test_landmark_proto() {
+        let landmarks = Landmarks(vec![crate::postprocess::Landmark {
+            x: 0.5,
+            y: 0.25,
+            z: -1.,
+            visibility: None,
+            presence: Some(0.75),
+            name: Some("wrist".into()),
+        }]);
+        let list = NormalizedLandmarkList::from(&landmarks);
+        assert_eq!(list.landmark[0].visibility, None);
+        let buf = list.encode_to_vec();
+        // the wire format is the same as the world landmarks
+        let world = LandmarkList::decode(buf.as_slice()).unwrap();
+        assert_eq!(world.encode_to_vec(), buf);
+        let l = Landmarks::from(&NormalizedLandmarkList::decode(buf.as_slice()).unwrap());
+        assert_eq!(l.len(), 1);
+        assert_eq!(l[0], landmarks[0]);
+        assert_eq!(l[0].presence, Some(0.75));
+        assert_eq!(l[0].name, None);
+    }
+}
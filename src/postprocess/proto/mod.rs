@@ -0,0 +1,19 @@
+//! Protobuf messages which are compatible with google mediapipe [1], and the conversions between them and
+//! the result containers. The messages can be encoded and decoded by [`Message`].
+//!
+//! [1]: https://github.com/google/mediapipe/tree/master/mediapipe/framework/formats
+
+mod classification;
+pub use classification::*;
+
+#[cfg(feature = "vision")]
+mod detection;
+#[cfg(feature = "vision")]
+pub use detection::*;
+
+#[cfg(feature = "vision")]
+mod landmark;
+#[cfg(feature = "vision")]
+pub use landmark::*;
+
+pub use prost::Message;
@@ -0,0 +1,102 @@
+/// Dot product of float vectors with the same length.
+/// It uses wasm ```simd128``` instructions if the target feature is enabled, otherwise the loop is written
+/// with independent lanes so that the compiler can vectorize it.
+#[inline]
+pub(super) fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+    // the simd loads of `b` rely on the same length
+    assert_eq!(a.len(), b.len());
+
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    {
+        use core::arch::wasm32::*;
+        let chunks = a.len() / 4;
+        let mut sum = f32x4_splat(0.);
+        for i in 0..chunks {
+            // safety: the 4 elements are in bounds, and v128_load does not require alignment
+            let (x, y) = unsafe {
+                (
+                    v128_load(a.as_ptr().add(i * 4) as *const v128),
+                    v128_load(b.as_ptr().add(i * 4) as *const v128),
+                )
+            };
+            sum = f32x4_add(sum, f32x4_mul(x, y));
+        }
+        let mut s = f32x4_extract_lane::<0>(sum)
+            + f32x4_extract_lane::<1>(sum)
+            + f32x4_extract_lane::<2>(sum)
+            + f32x4_extract_lane::<3>(sum);
+        for i in chunks * 4..a.len() {
+            s += a[i] * b[i];
+        }
+        s
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+    {
+        let mut lanes = [0f32; 8];
+        let mut chunks_a = a.chunks_exact(8);
+        let mut chunks_b = b.chunks_exact(8);
+        for (x, y) in (&mut chunks_a).zip(&mut chunks_b) {
+            for ((l, x), y) in lanes.iter_mut().zip(x).zip(y) {
+                *l += x * y;
+            }
+        }
+        let mut s: f32 = lanes.iter().sum();
+        for (x, y) in chunks_a.remainder().iter().zip(chunks_b.remainder()) {
+            s += x * y;
+        }
+        s
+    }
+}
+
+/// Dot product of quantized vectors with the same length, see [`dot_f32`].
+#[inline]
+pub(super) fn dot_i8(a: &[i8], b: &[i8]) -> i32 {
+    // the simd loads of `b` rely on the same length
+    assert_eq!(a.len(), b.len());
+
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    {
+        use core::arch::wasm32::*;
+        let chunks = a.len() / 16;
+        let mut sum = i32x4_splat(0);
+        for i in 0..chunks {
+            // safety: the 16 elements are in bounds, and v128_load does not require alignment
+            let (x, y) = unsafe {
+                (
+                    v128_load(a.as_ptr().add(i * 16) as *const v128),
+                    v128_load(b.as_ptr().add(i * 16) as *const v128),
+                )
+            };
+            // the products of i8 always fit in i16
+            let low = i32x4_extadd_pairwise_i16x8(i16x8_extmul_low_i8x16(x, y));
+            let high = i32x4_extadd_pairwise_i16x8(i16x8_extmul_high_i8x16(x, y));
+            sum = i32x4_add(sum, i32x4_add(low, high));
+        }
+        let mut s = i32x4_extract_lane::<0>(sum)
+            + i32x4_extract_lane::<1>(sum)
+            + i32x4_extract_lane::<2>(sum)
+            + i32x4_extract_lane::<3>(sum);
+        for i in chunks * 16..a.len() {
+            s += a[i] as i32 * b[i] as i32;
+        }
+        s
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+    {
+        let mut lanes = [0i32; 16];
+        let mut chunks_a = a.chunks_exact(16);
+        let mut chunks_b = b.chunks_exact(16);
+        for (x, y) in (&mut chunks_a).zip(&mut chunks_b) {
+            for ((l, x), y) in lanes.iter_mut().zip(x).zip(y) {
+                *l += *x as i32 * *y as i32;
+            }
+        }
+        let mut s: i32 = lanes.iter().sum();
+        for (x, y) in chunks_a.remainder().iter().zip(chunks_b.remainder()) {
+            s += *x as i32 * *y as i32;
+        }
+        s
+    }
+}
@@ -0,0 +1,262 @@
+use super::{read_u32, read_u64, write_u32, write_u64, HnswOptions};
+use crate::Error;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+use std::io::{Read, Write};
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Candidate {
+    pub(super) similarity: f32,
+    pub(super) node: u32,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.similarity
+            .total_cmp(&other.similarity)
+            .then(other.node.cmp(&self.node))
+    }
+}
+
+/// The Hierarchical Navigable Small World graph [1] of the embedding index.
+/// The nodes are the positions of embeddings in the index, and the similarities are computed by the index.
+///
+/// [1]: https://arxiv.org/abs/1603.09320
+pub(super) struct Hnsw {
+    pub(super) options: HnswOptions,
+    /// ```neighbors[node][layer]```
+    neighbors: Vec<Vec<Vec<u32>>>,
+    entry_point: Option<u32>,
+    /// The state of random number generator for the node levels, which makes the graph reproducible.
+    rng_state: u64,
+}
+
+impl Hnsw {
+    const FILE_NO_ENTRY_POINT: u32 = u32::MAX;
+
+    #[inline(always)]
+    pub(super) fn new(options: HnswOptions) -> Self {
+        Self {
+            options,
+            neighbors: Vec::new(),
+            entry_point: None,
+            rng_state: 0x2545f4914f6cdd1d,
+        }
+    }
+
+    /// Draw the top layer of a new node, with the level multiplier ```1 / ln(m)```.
+    fn random_level(&mut self) -> usize {
+        // splitmix64
+        self.rng_state = self.rng_state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+        let uniform = ((z >> 11) as f64 + 1.) / (1u64 << 53) as f64;
+        (-uniform.ln() / (self.options.m.max(2) as f64).ln()) as usize
+    }
+
+    #[inline(always)]
+    fn max_level(&self, entry_point: u32) -> usize {
+        self.neighbors[entry_point as usize].len() - 1
+    }
+
+    fn search_layer(
+        &self,
+        entry_points: &[Candidate],
+        ef: usize,
+        layer: usize,
+        similarity: &impl Fn(u32) -> f32,
+    ) -> Vec<Candidate> {
+        let mut visited: HashSet<u32> = entry_points.iter().map(|c| c.node).collect();
+        let mut candidates: BinaryHeap<Candidate> = entry_points.iter().cloned().collect();
+        let mut results: BinaryHeap<Reverse<Candidate>> =
+            entry_points.iter().cloned().map(Reverse).collect();
+
+        while let Some(c) = candidates.pop() {
+            let worst = results.peek().unwrap().0;
+            if results.len() >= ef && c < worst {
+                break;
+            }
+            for n in self.neighbors[c.node as usize][layer].iter() {
+                if !visited.insert(*n) {
+                    continue;
+                }
+                let candidate = Candidate {
+                    similarity: similarity(*n),
+                    node: *n,
+                };
+                if results.len() < ef || candidate > results.peek().unwrap().0 {
+                    candidates.push(candidate);
+                    results.push(Reverse(candidate));
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        let mut results: Vec<Candidate> = results.into_iter().map(|r| r.0).collect();
+        results.sort_unstable_by(|a, b| b.cmp(a));
+        results
+    }
+
+    /// Insert the node, which must be the next position of the index.
+    /// ```similarity(a, b)``` computes the similarity between the nodes.
+    pub(super) fn insert(&mut self, node: u32, similarity: impl Fn(u32, u32) -> f32) {
+        debug_assert_eq!(node as usize, self.neighbors.len());
+        let level = self.random_level();
+        self.neighbors.push(vec![Vec::new(); level + 1]);
+        let Some(entry_point) = self.entry_point else {
+            self.entry_point = Some(node);
+            return;
+        };
+
+        let max_level = self.max_level(entry_point);
+        let to_node = |n: u32| similarity(node, n);
+        let mut entry_points = vec![Candidate {
+            similarity: to_node(entry_point),
+            node: entry_point,
+        }];
+        for layer in (level + 1..=max_level).rev() {
+            entry_points = self.search_layer(&entry_points, 1, layer, &to_node);
+        }
+
+        let m = self.options.m.max(2);
+        for layer in (0..=level.min(max_level)).rev() {
+            entry_points =
+                self.search_layer(&entry_points, self.options.ef_construction, layer, &to_node);
+            let max_connections = if layer == 0 { m * 2 } else { m };
+            let selected: Vec<u32> = entry_points.iter().take(m).map(|c| c.node).collect();
+            for n in selected.iter() {
+                let list = &mut self.neighbors[*n as usize][layer];
+                list.push(node);
+                if list.len() > max_connections {
+                    // keep the most similar neighbors
+                    let mut scored: Vec<Candidate> = list
+                        .iter()
+                        .map(|x| Candidate {
+                            similarity: similarity(*n, *x),
+                            node: *x,
+                        })
+                        .collect();
+                    scored.sort_unstable_by(|a, b| b.cmp(a));
+                    *list = scored
+                        .into_iter()
+                        .take(max_connections)
+                        .map(|c| c.node)
+                        .collect();
+                }
+            }
+            self.neighbors[node as usize][layer] = selected;
+        }
+
+        if level > max_level {
+            self.entry_point = Some(node);
+        }
+    }
+
+    /// Search the ```ef``` most similar nodes (at least), sorted by descending similarity.
+    /// ```similarity(n)``` computes the similarity between the query and the node.
+    pub(super) fn search(&self, ef: usize, similarity: impl Fn(u32) -> f32) -> Vec<Candidate> {
+        let Some(entry_point) = self.entry_point else {
+            return Vec::new();
+        };
+        let mut entry_points = vec![Candidate {
+            similarity: similarity(entry_point),
+            node: entry_point,
+        }];
+        for layer in (1..=self.max_level(entry_point)).rev() {
+            entry_points = self.search_layer(&entry_points, 1, layer, &similarity);
+        }
+        self.search_layer(&entry_points, ef, 0, &similarity)
+    }
+
+    pub(super) fn write_to(&self, writer: &mut impl Write) -> Result<(), Error> {
+        write_u32(writer, self.options.m as u32)?;
+        write_u32(writer, self.options.ef_construction as u32)?;
+        write_u32(writer, self.options.ef_search as u32)?;
+        write_u64(writer, self.rng_state)?;
+        write_u32(
+            writer,
+            self.entry_point.unwrap_or(Self::FILE_NO_ENTRY_POINT),
+        )?;
+        for layers in self.neighbors.iter() {
+            write_u32(writer, layers.len() as u32)?;
+            for list in layers.iter() {
+                write_u32(writer, list.len() as u32)?;
+                for n in list.iter() {
+                    write_u32(writer, *n)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn read_from(reader: &mut impl Read, num_nodes: usize) -> Result<Self, Error> {
+        let invalid = || Error::ArgumentError("Invalid HNSW graph in embedding index".into());
+        let options = HnswOptions {
+            m: read_u32(reader)? as usize,
+            ef_construction: read_u32(reader)? as usize,
+            ef_search: read_u32(reader)? as usize,
+        };
+        let rng_state = read_u64(reader)?;
+        let entry_point = match read_u32(reader)? {
+            Self::FILE_NO_ENTRY_POINT => None,
+            n if (n as usize) < num_nodes => Some(n),
+            _ => return Err(invalid()),
+        };
+        let mut neighbors = Vec::with_capacity(num_nodes.min(1 << 16));
+        for _ in 0..num_nodes {
+            let num_layers = read_u32(reader)? as usize;
+            if num_layers == 0 {
+                return Err(invalid());
+            }
+            let mut layers = Vec::with_capacity(num_layers.min(64));
+            for _ in 0..num_layers {
+                let len = read_u32(reader)? as usize;
+                let mut list = Vec::with_capacity(len.min(num_nodes));
+                for _ in 0..len {
+                    let n = read_u32(reader)?;
+                    if n as usize >= num_nodes {
+                        return Err(invalid());
+                    }
+                    list.push(n);
+                }
+                layers.push(list);
+            }
+            neighbors.push(layers);
+        }
+        // the neighbors of each layer must have the layer
+        for layers in neighbors.iter() {
+            for (layer, list) in layers.iter().enumerate() {
+                if list.iter().any(|n| neighbors[*n as usize].len() <= layer) {
+                    return Err(invalid());
+                }
+            }
+        }
+        if entry_point.is_none() != neighbors.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            options,
+            neighbors,
+            entry_point,
+            rng_state,
+        })
+    }
+}
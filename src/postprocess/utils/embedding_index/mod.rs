@@ -0,0 +1,633 @@
+mod dot;
+mod hnsw;
+
+use crate::postprocess::Embedding;
+use crate::Error;
+use hnsw::Hnsw;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Options of the approximate nearest neighbor search by HNSW (Hierarchical Navigable Small World) graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HnswOptions {
+    /// The max number of neighbors of each node in upper layers, the layer 0 has ```2 * m``` neighbors.
+    /// Default is ```16```.
+    pub m: usize,
+    /// The size of dynamic candidate list when inserting embeddings, default is ```200```.
+    pub ef_construction: usize,
+    /// The size of dynamic candidate list when searching, larger is more accurate but slower.
+    /// Default is ```64```, and it is at least the ```k``` of search.
+    pub ef_search: usize,
+}
+
+impl Default for HnswOptions {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            m: 16,
+            ef_construction: 200,
+            ef_search: 64,
+        }
+    }
+}
+
+/// A search result of [`EmbeddingIndex`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddingSearchResult {
+    /// The ID of the embedding in index.
+    pub id: u64,
+    /// The cosine similarity between the query and the embedding.
+    pub similarity: f64,
+}
+
+enum Vectors {
+    Float(Vec<f32>),
+    Quantized(Vec<i8>),
+}
+
+enum Query<'a> {
+    Float(&'a [f32]),
+    Quantized(&'a [i8]),
+}
+
+/// An index of embeddings with IDs, which can search the most similar embeddings by cosine similarity.
+///
+/// All embeddings in the index must have the same type (float or quantized) and dimension, which are
+/// decided by the first added embedding. The search is exact brute-force by default, and is approximate
+/// if the HNSW graph is enabled by [`EmbeddingIndex::hnsw`].
+///
+/// ```rust,no_run
+/// use mediapipe_rs::postprocess::utils::{EmbeddingIndex, HnswOptions};
+/// # fn f(embeddings: Vec<mediapipe_rs::postprocess::Embedding>, query: mediapipe_rs::postprocess::Embedding) -> Result<(), mediapipe_rs::Error> {
+///
+/// let mut index = EmbeddingIndex::new().hnsw(HnswOptions::default());
+/// for (id, embedding) in embeddings.iter().enumerate() {
+///     index.add(id as u64, embedding)?;
+/// }
+/// index.save("index.bin")?;
+///
+/// let index = EmbeddingIndex::load("index.bin")?;
+/// for r in index.search(&query, 5)? {
+///     println!("{}: {}", r.id, r.similarity);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct EmbeddingIndex {
+    dimension: usize,
+    vectors: Option<Vectors>,
+    inv_norms: Vec<f32>,
+    ids: Vec<u64>,
+    positions: HashMap<u64, u32>,
+    deleted: Vec<bool>,
+    num_deleted: usize,
+    hnsw: Option<Hnsw>,
+}
+
+impl Default for EmbeddingIndex {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EmbeddingIndex {
+    const FILE_MAGIC: &'static [u8; 4] = b"MPEI";
+    const FILE_VERSION: u32 = 1;
+
+    /// Create a new empty index with exact search.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            dimension: 0,
+            vectors: None,
+            inv_norms: Vec::new(),
+            ids: Vec::new(),
+            positions: HashMap::new(),
+            deleted: Vec::new(),
+            num_deleted: 0,
+            hnsw: None,
+        }
+    }
+
+    /// Enable the approximate search by HNSW graph. The embeddings already in the index are inserted to the graph.
+    pub fn hnsw(mut self, options: HnswOptions) -> Self {
+        let mut hnsw = Hnsw::new(options);
+        for node in 0..self.ids.len() as u32 {
+            hnsw.insert(node, |a, b| self.node_similarity(a, b));
+        }
+        self.hnsw = Some(hnsw);
+        self
+    }
+
+    /// Set the ```ef_search``` of HNSW options, it has no effect on exact search.
+    #[inline(always)]
+    pub fn set_ef_search(&mut self, ef_search: usize) {
+        if let Some(ref mut hnsw) = self.hnsw {
+            hnsw.options.ef_search = ef_search;
+        }
+    }
+
+    /// Get the HNSW options if the approximate search is enabled.
+    #[inline(always)]
+    pub fn hnsw_options(&self) -> Option<&HnswOptions> {
+        self.hnsw.as_ref().map(|h| &h.options)
+    }
+
+    /// The number of embeddings in the index.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.ids.len() - self.num_deleted
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The dimension of embeddings, ```None``` if no embedding has been added.
+    #[inline(always)]
+    pub fn dimension(&self) -> Option<usize> {
+        self.vectors.as_ref().map(|_| self.dimension)
+    }
+
+    /// Whether the index stores quantized embeddings, ```None``` if no embedding has been added.
+    #[inline(always)]
+    pub fn is_quantized(&self) -> Option<bool> {
+        self.vectors
+            .as_ref()
+            .map(|v| matches!(v, Vectors::Quantized(_)))
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, id: u64) -> bool {
+        self.positions.contains_key(&id)
+    }
+
+    /// Get the query vector of the embedding, which must have the same type and dimension as the index.
+    fn query<'a>(&self, embedding: &'a Embedding) -> Result<(Query<'a>, f32), Error> {
+        let (query, len, norm_square) = match self.vectors {
+            Some(Vectors::Quantized(_)) => {
+                let v = embedding.quantized_embedding.as_slice();
+                (Query::Quantized(v), v.len(), dot::dot_i8(v, v) as f32)
+            }
+            Some(Vectors::Float(_)) => {
+                let v = embedding.float_embedding.as_slice();
+                (Query::Float(v), v.len(), dot::dot_f32(v, v))
+            }
+            None => {
+                if !embedding.float_embedding.is_empty() {
+                    let v = embedding.float_embedding.as_slice();
+                    (Query::Float(v), v.len(), dot::dot_f32(v, v))
+                } else {
+                    let v = embedding.quantized_embedding.as_slice();
+                    (Query::Quantized(v), v.len(), dot::dot_i8(v, v) as f32)
+                }
+            }
+        };
+        if len == 0 {
+            return Err(Error::ArgumentError(format!(
+                "The embedding has no {} values",
+                if matches!(query, Query::Float(_)) {
+                    "float"
+                } else {
+                    "quantized"
+                }
+            )));
+        }
+        if self.vectors.is_some() && len != self.dimension {
+            return Err(Error::ArgumentError(format!(
+                "The embedding dimension `{}` is not equal to the index dimension `{}`",
+                len, self.dimension
+            )));
+        }
+        if norm_square <= 0. || !norm_square.is_finite() {
+            return Err(Error::ArgumentError(
+                "Cannot compute cosine similarity on embedding with 0 norm".into(),
+            ));
+        }
+        Ok((query, 1. / norm_square.sqrt()))
+    }
+
+    #[inline(always)]
+    fn dot(&self, query: &Query, node: u32) -> f32 {
+        let start = node as usize * self.dimension;
+        let end = start + self.dimension;
+        match (self.vectors.as_ref(), query) {
+            (Some(Vectors::Float(v)), Query::Float(q)) => dot::dot_f32(&v[start..end], q),
+            (Some(Vectors::Quantized(v)), Query::Quantized(q)) => {
+                dot::dot_i8(&v[start..end], q) as f32
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[inline(always)]
+    fn node_query(&self, node: u32) -> Query<'_> {
+        let start = node as usize * self.dimension;
+        let end = start + self.dimension;
+        match self.vectors.as_ref().unwrap() {
+            Vectors::Float(v) => Query::Float(&v[start..end]),
+            Vectors::Quantized(v) => Query::Quantized(&v[start..end]),
+        }
+    }
+
+    #[inline(always)]
+    fn node_similarity(&self, a: u32, b: u32) -> f32 {
+        self.dot(&self.node_query(a), b) * self.inv_norms[a as usize] * self.inv_norms[b as usize]
+    }
+
+    /// Add the embedding with the ID. The float embedding is used if it is not empty, otherwise the quantized one.
+    pub fn add(&mut self, id: u64, embedding: &Embedding) -> Result<(), Error> {
+        if self.positions.contains_key(&id) {
+            return Err(Error::ArgumentError(format!(
+                "The ID `{}` is already in the index",
+                id
+            )));
+        }
+        let (query, inv_norm) = self.query(embedding)?;
+        let node = self.ids.len() as u32;
+        match (&mut self.vectors, query) {
+            (Some(Vectors::Float(v)), Query::Float(q)) => v.extend_from_slice(q),
+            (Some(Vectors::Quantized(v)), Query::Quantized(q)) => v.extend_from_slice(q),
+            (None, Query::Float(q)) => {
+                self.dimension = q.len();
+                self.vectors = Some(Vectors::Float(q.to_vec()));
+            }
+            (None, Query::Quantized(q)) => {
+                self.dimension = q.len();
+                self.vectors = Some(Vectors::Quantized(q.to_vec()));
+            }
+            _ => unreachable!(),
+        }
+        self.inv_norms.push(inv_norm);
+        self.ids.push(id);
+        self.positions.insert(id, node);
+        self.deleted.push(false);
+
+        if let Some(mut hnsw) = self.hnsw.take() {
+            hnsw.insert(node, |a, b| self.node_similarity(a, b));
+            self.hnsw = Some(hnsw);
+        }
+        Ok(())
+    }
+
+    /// Remove the embedding by ID, return whether it was in the index.
+    /// The removed embeddings are skipped in search results, and their storage is kept until the index is rebuilt.
+    pub fn remove(&mut self, id: u64) -> bool {
+        match self.positions.remove(&id) {
+            Some(node) => {
+                self.deleted[node as usize] = true;
+                self.num_deleted += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Search the top-k most similar embeddings, sorted by descending cosine similarity.
+    /// The search is approximate if the HNSW graph is enabled, otherwise it is the same as
+    /// [`EmbeddingIndex::search_exact`].
+    pub fn search(&self, query: &Embedding, k: usize) -> Result<Vec<EmbeddingSearchResult>, Error> {
+        let Some(ref hnsw) = self.hnsw else {
+            return self.search_exact(query, k);
+        };
+        if self.is_empty() || k == 0 {
+            return Ok(Vec::new());
+        }
+        let (query, inv_norm) = self.query(query)?;
+        // the removed nodes are still in the graph
+        let ef = hnsw.options.ef_search.max(k) + self.num_deleted;
+        Ok(hnsw
+            .search(ef, |n| {
+                self.dot(&query, n) * inv_norm * self.inv_norms[n as usize]
+            })
+            .into_iter()
+            .filter(|c| !self.deleted[c.node as usize])
+            .take(k)
+            .map(|c| self.search_result(c.node, c.similarity))
+            .collect())
+    }
+
+    /// Search the top-k most similar embeddings by comparing with all embeddings, sorted by descending
+    /// cosine similarity.
+    pub fn search_exact(
+        &self,
+        query: &Embedding,
+        k: usize,
+    ) -> Result<Vec<EmbeddingSearchResult>, Error> {
+        if self.is_empty() || k == 0 {
+            return Ok(Vec::new());
+        }
+        let (query, inv_norm) = self.query(query)?;
+        let mut candidates: Vec<hnsw::Candidate> = (0..self.ids.len() as u32)
+            .filter(|n| !self.deleted[*n as usize])
+            .map(|n| hnsw::Candidate {
+                similarity: self.dot(&query, n) * inv_norm * self.inv_norms[n as usize],
+                node: n,
+            })
+            .collect();
+        if candidates.len() > k {
+            candidates.select_nth_unstable_by(k - 1, |a, b| b.cmp(a));
+            candidates.truncate(k);
+        }
+        candidates.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(candidates
+            .into_iter()
+            .map(|c| self.search_result(c.node, c.similarity))
+            .collect())
+    }
+
+    #[inline(always)]
+    fn search_result(&self, node: u32, similarity: f32) -> EmbeddingSearchResult {
+        EmbeddingSearchResult {
+            id: self.ids[node as usize],
+            similarity: similarity as f64,
+        }
+    }
+
+    /// Save the index (with the HNSW graph if enabled) to the file.
+    #[inline]
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Load the index saved by [`EmbeddingIndex::save`].
+    #[inline]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::read_from(&mut std::io::BufReader::new(std::fs::File::open(path)?))
+    }
+
+    /// Write the index in a little-endian binary format.
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<(), Error> {
+        writer.write_all(Self::FILE_MAGIC)?;
+        write_u32(writer, Self::FILE_VERSION)?;
+        let vector_type = match self.vectors {
+            None => 0u8,
+            Some(Vectors::Float(_)) => 1,
+            Some(Vectors::Quantized(_)) => 2,
+        };
+        writer.write_all(&[vector_type, self.hnsw.is_some() as u8])?;
+        write_u32(writer, self.dimension as u32)?;
+        write_u32(writer, self.ids.len() as u32)?;
+        for (id, deleted) in self.ids.iter().zip(self.deleted.iter()) {
+            write_u64(writer, *id)?;
+            writer.write_all(&[*deleted as u8])?;
+        }
+        match self.vectors {
+            Some(Vectors::Float(ref v)) => {
+                for f in v.iter() {
+                    writer.write_all(&f.to_le_bytes())?;
+                }
+            }
+            Some(Vectors::Quantized(ref v)) => {
+                let bytes: Vec<u8> = v.iter().map(|i| *i as u8).collect();
+                writer.write_all(&bytes)?;
+            }
+            None => {}
+        }
+        if let Some(ref hnsw) = self.hnsw {
+            hnsw.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Read the index written by [`EmbeddingIndex::write_to`].
+    pub fn read_from(reader: &mut impl Read) -> Result<Self, Error> {
+        let invalid = |msg: &str| Error::ArgumentError(format!("Invalid embedding index: {}", msg));
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != Self::FILE_MAGIC {
+            return Err(invalid("magic mismatch"));
+        }
+        let version = read_u32(reader)?;
+        if version != Self::FILE_VERSION {
+            return Err(invalid(&format!("unsupported version `{}`", version)));
+        }
+        let mut flags = [0u8; 2];
+        reader.read_exact(&mut flags)?;
+        let dimension = read_u32(reader)? as usize;
+        let count = read_u32(reader)? as usize;
+        if (flags[0] == 0) != (count == 0) || (count != 0 && dimension == 0) {
+            return Err(invalid("inconsistent size"));
+        }
+
+        let mut index = Self::new();
+        index.dimension = dimension;
+        for node in 0..count {
+            let id = read_u64(reader)?;
+            let mut deleted = [0u8];
+            reader.read_exact(&mut deleted)?;
+            index.ids.push(id);
+            index.deleted.push(deleted[0] != 0);
+            if deleted[0] != 0 {
+                index.num_deleted += 1;
+            } else if index.positions.insert(id, node as u32).is_some() {
+                return Err(invalid("duplicate id"));
+            }
+        }
+
+        let elem_size = match flags[0] {
+            0 => 0,
+            1 => 4,
+            2 => 1,
+            t => return Err(invalid(&format!("unknown embedding type `{}`", t))),
+        };
+        let len = dimension
+            .checked_mul(count)
+            .and_then(|l| l.checked_mul(elem_size))
+            .ok_or_else(|| invalid("size overflow"))?;
+        // read incrementally, so that a wrong size in header cannot allocate too much memory.
+        let mut bytes = Vec::new();
+        reader.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(invalid("unexpected end of embeddings"));
+        }
+        index.vectors = match flags[0] {
+            1 => Some(Vectors::Float(
+                bytes
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect(),
+            )),
+            2 => Some(Vectors::Quantized(
+                bytes.into_iter().map(|b| b as i8).collect(),
+            )),
+            _ => None,
+        };
+        for node in 0..count as u32 {
+            let q = index.node_query(node);
+            let norm_square = match q {
+                Query::Float(v) => dot::dot_f32(v, v),
+                Query::Quantized(v) => dot::dot_i8(v, v) as f32,
+            };
+            index.inv_norms.push(1. / norm_square.sqrt());
+        }
+
+        if flags[1] != 0 {
+            index.hnsw = Some(Hnsw::read_from(reader, count)?);
+        }
+        Ok(index)
+    }
+}
+
+#[inline(always)]
+fn write_u32(writer: &mut impl Write, value: u32) -> Result<(), Error> {
+    Ok(writer.write_all(&value.to_le_bytes())?)
+}
+
+#[inline(always)]
+fn write_u64(writer: &mut impl Write, value: u64) -> Result<(), Error> {
+    Ok(writer.write_all(&value.to_le_bytes())?)
+}
+
+#[inline(always)]
+fn read_u32(reader: &mut impl Read) -> Result<u32, Error> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+#[inline(always)]
+fn read_u64(reader: &mut impl Read) -> Result<u64, Error> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn float_embedding(v: Vec<f32>) -> Embedding {
+        Embedding {
+            head_index: 0,
+            head_name: None,
+            float_embedding: v,
+            quantized_embedding: vec![],
+        }
+    }
+
+    fn random_vectors(count: usize, dimension: usize) -> Vec<Vec<f32>> {
+        let mut state = 1u64;
+        (0..count)
+            .map(|_| {
+                (0..dimension)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        (state >> 40) as f32 / (1u64 << 24) as f32 - 0.5
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_embedding_index() {
+        let mut index = EmbeddingIndex::new();
+        index.add(1, &float_embedding(vec![1., 0., 0.])).unwrap();
+        index.add(2, &float_embedding(vec![1., 1., 0.])).unwrap();
+        index.add(3, &float_embedding(vec![0., 0., 2.])).unwrap();
+        assert!(index.add(3, &float_embedding(vec![0., 0., 1.])).is_err());
+        assert!(index.add(4, &float_embedding(vec![0., 1.])).is_err());
+        assert!(index.add(4, &float_embedding(vec![0., 0., 0.])).is_err());
+        let quantized = Embedding {
+            head_index: 0,
+            head_name: None,
+            float_embedding: vec![],
+            quantized_embedding: vec![1, 2, 3],
+        };
+        assert!(index.add(4, &quantized).is_err());
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.dimension(), Some(3));
+        assert_eq!(index.is_quantized(), Some(false));
+
+        let r = index.search(&float_embedding(vec![2., 0., 0.]), 2).unwrap();
+        assert_eq!(r.len(), 2);
+        assert_eq!(r[0].id, 1);
+        assert!((r[0].similarity - 1.).abs() < 1e-6);
+        assert_eq!(r[1].id, 2);
+        assert!((r[1].similarity - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+
+        assert!(index.remove(1));
+        assert!(!index.remove(1));
+        let r = index.search(&float_embedding(vec![2., 0., 0.]), 5).unwrap();
+        assert_eq!(r.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2, 3]);
+
+        let mut quantized_index = EmbeddingIndex::new();
+        quantized_index.add(7, &quantized).unwrap();
+        let r = quantized_index.search(&quantized, 1).unwrap();
+        assert_eq!(r[0].id, 7);
+        assert!((r[0].similarity - 1.).abs() < 1e-6);
+        assert!(quantized_index
+            .search(&float_embedding(vec![1.]), 1)
+            .is_err());
+
+        // save and load
+        let mut bytes = Vec::new();
+        index.write_to(&mut bytes).unwrap();
+        let loaded = EmbeddingIndex::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert!(!loaded.contains(1) && loaded.contains(3));
+        assert_eq!(
+            loaded
+                .search(&float_embedding(vec![0., 1., 0.]), 1)
+                .unwrap(),
+            index.search(&float_embedding(vec![0., 1., 0.]), 1).unwrap()
+        );
+        assert!(EmbeddingIndex::read_from(&mut &bytes[..bytes.len() - 1]).is_err());
+        assert!(EmbeddingIndex::read_from(&mut &b"MPEX"[..]).is_err());
+        // the dimension in header is too large
+        let mut corrupted = bytes.clone();
+        corrupted[10..14].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(EmbeddingIndex::read_from(&mut corrupted.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_embedding_index_hnsw() {
+        let vectors = random_vectors(1000, 16);
+        let mut index = EmbeddingIndex::new().hnsw(HnswOptions::default());
+        for (i, v) in vectors.iter().enumerate() {
+            index.add(i as u64, &float_embedding(v.clone())).unwrap();
+        }
+
+        let queries = random_vectors(1050, 16).split_off(1000);
+        let mut num_found = 0;
+        for q in queries.iter() {
+            let q = float_embedding(q.clone());
+            let exact = index.search_exact(&q, 10).unwrap();
+            let approximate = index.search(&q, 10).unwrap();
+            assert_eq!(approximate.len(), 10);
+            num_found += approximate.iter().filter(|a| exact.contains(a)).count();
+        }
+        // recall@10
+        assert!(num_found as f32 / 500. > 0.9);
+
+        let mut bytes = Vec::new();
+        index.write_to(&mut bytes).unwrap();
+        let loaded = EmbeddingIndex::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded.hnsw_options(), Some(&HnswOptions::default()));
+        let q = float_embedding(queries[0].clone());
+        assert_eq!(
+            loaded.search(&q, 10).unwrap(),
+            index.search(&q, 10).unwrap()
+        );
+
+        // enable the graph after adding
+        let mut exact = EmbeddingIndex::new();
+        for (i, v) in vectors.iter().take(100).enumerate() {
+            exact.add(i as u64, &float_embedding(v.clone())).unwrap();
+        }
+        let approximate = exact.hnsw(HnswOptions::default());
+        assert_eq!(
+            approximate.search(&q, 1).unwrap(),
+            approximate.search_exact(&q, 1).unwrap()
+        );
+    }
+}
@@ -1,3 +1,7 @@
+mod embedding_index;
+
+pub use embedding_index::*;
+
 #[cfg(feature = "vision")]
 mod vision;
 
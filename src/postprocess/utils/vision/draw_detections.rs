@@ -1,5 +1,6 @@
+use super::bitmap_font::{contrast_color, draw_text_mut, text_size};
 use super::DefaultPixel;
-use crate::postprocess::DetectionResult;
+use crate::postprocess::{Category, DetectionResult};
 use image::{GenericImage, Pixel};
 use imageproc::drawing;
 
@@ -7,10 +8,26 @@ use imageproc::drawing;
 #[derive(Debug)]
 pub struct DrawDetectionsOptions<P: Pixel> {
     pub border_color: P,
+    /// The line thickness of bounding box borders, in pixels.
+    pub border_thickness: u32,
+    /// Colors for each category index, which override the `border_color` if not empty.
+    /// The color of category `i` is `category_colors[i % category_colors.len()]`.
+    pub category_colors: Vec<P>,
     pub draw_keypoint: bool,
     pub keypoint_color: P,
     pub keypoint_radius: i32,
-    // todo: add other options
+    /// Whether draw the index of keypoints beside them.
+    pub draw_keypoint_index: bool,
+    /// Whether draw the category name of the top category above the bounding box.
+    pub draw_label: bool,
+    /// Whether draw the category score after the label.
+    pub draw_score: bool,
+    /// Whether fill the label background with the bounding box color.
+    pub draw_label_background: bool,
+    /// The label text color, `None` means black or white according to the label background.
+    pub label_color: Option<P>,
+    /// The scale of the embedded 5x7 bitmap font.
+    pub font_scale: u32,
 }
 
 impl<P: Pixel> DrawDetectionsOptions<P> {
@@ -18,9 +35,82 @@ impl<P: Pixel> DrawDetectionsOptions<P> {
     pub fn new(border_color: P, keypoint_color: P) -> Self {
         Self {
             border_color,
+            border_thickness: 1,
+            category_colors: vec![],
             draw_keypoint: false,
             keypoint_color,
             keypoint_radius: 5,
+            draw_keypoint_index: false,
+            draw_label: true,
+            draw_score: true,
+            draw_label_background: true,
+            label_color: None,
+            font_scale: 2,
+        }
+    }
+
+    #[inline(always)]
+    pub fn border_thickness(mut self, border_thickness: u32) -> Self {
+        self.border_thickness = border_thickness;
+        self
+    }
+
+    #[inline(always)]
+    pub fn category_colors(mut self, category_colors: Vec<P>) -> Self {
+        self.category_colors = category_colors;
+        self
+    }
+
+    #[inline(always)]
+    pub fn draw_keypoint(mut self, draw_keypoint: bool) -> Self {
+        self.draw_keypoint = draw_keypoint;
+        self
+    }
+
+    #[inline(always)]
+    pub fn draw_keypoint_index(mut self, draw_keypoint_index: bool) -> Self {
+        self.draw_keypoint_index = draw_keypoint_index;
+        self
+    }
+
+    #[inline(always)]
+    pub fn draw_label(mut self, draw_label: bool) -> Self {
+        self.draw_label = draw_label;
+        self
+    }
+
+    #[inline(always)]
+    pub fn draw_score(mut self, draw_score: bool) -> Self {
+        self.draw_score = draw_score;
+        self
+    }
+
+    #[inline(always)]
+    pub fn draw_label_background(mut self, draw_label_background: bool) -> Self {
+        self.draw_label_background = draw_label_background;
+        self
+    }
+
+    #[inline(always)]
+    pub fn label_color(mut self, label_color: P) -> Self {
+        self.label_color = Some(label_color);
+        self
+    }
+
+    #[inline(always)]
+    pub fn font_scale(mut self, font_scale: u32) -> Self {
+        self.font_scale = font_scale;
+        self
+    }
+
+    /// Get the color for the category index.
+    #[inline(always)]
+    fn color(&self, category_index: Option<u32>) -> P {
+        match category_index {
+            Some(i) if !self.category_colors.is_empty() => {
+                self.category_colors[i as usize % self.category_colors.len()]
+            }
+            _ => self.border_color,
         }
     }
 }
@@ -28,12 +118,21 @@ impl<P: Pixel> DrawDetectionsOptions<P> {
 impl<P: Pixel + DefaultPixel> Default for DrawDetectionsOptions<P> {
     #[inline(always)]
     fn default() -> Self {
-        Self {
-            border_color: DefaultPixel::default(),
-            draw_keypoint: false,
-            keypoint_color: DefaultPixel::default(),
-            keypoint_radius: 5,
-        }
+        Self::new(DefaultPixel::default(), DefaultPixel::default())
+    }
+}
+
+/// Get the label text for category: display name, category name or index, and the score.
+fn label_text(category: &Category, draw_score: bool) -> String {
+    let name = match (&category.display_name, &category.category_name) {
+        (Some(n), _) if !n.is_empty() => n.clone(),
+        (_, Some(n)) if !n.is_empty() => n.clone(),
+        _ => category.index.to_string(),
+    };
+    if draw_score {
+        format!("{} {:.2}", name, category.score)
+    } else {
+        name
     }
 }
 
@@ -58,30 +157,138 @@ pub fn draw_detection_with_options<I>(
 {
     let img_w = img.width() as f32;
     let img_h = img.height() as f32;
+    let font_scale = options.font_scale.max(1);
     for d in detection_result.detections.iter().rev() {
-        let left = d.bounding_box.left * img_w;
-        let right = d.bounding_box.right * img_w;
-        let top = d.bounding_box.top * img_h;
-        let bottom = d.bounding_box.bottom * img_h;
-        let rect = imageproc::rect::Rect::at(left as i32, top as i32)
-            .of_size((right - left) as u32, (bottom - top) as u32);
-        drawing::draw_hollow_rect_mut(img, rect, options.border_color);
+        let category = d.categories.first();
+        let color = options.color(category.map(|c| c.index));
+
+        let left = (d.bounding_box.left * img_w) as i32;
+        let right = (d.bounding_box.right * img_w) as i32;
+        let top = (d.bounding_box.top * img_h) as i32;
+        let bottom = (d.bounding_box.bottom * img_h) as i32;
+        // the borders grow inside the bounding box
+        for t in 0..options.border_thickness as i32 {
+            let (w, h) = (right - left - 2 * t, bottom - top - 2 * t);
+            if w <= 0 || h <= 0 {
+                break;
+            }
+            let rect = imageproc::rect::Rect::at(left + t, top + t).of_size(w as u32, h as u32);
+            drawing::draw_hollow_rect_mut(img, rect, color);
+        }
 
         if options.draw_keypoint {
             if let Some(ref ks) = d.key_points {
-                for k in ks {
-                    let x = k.x * img_w;
-                    let y = k.y * img_h;
+                for (i, k) in ks.iter().enumerate() {
+                    let x = (k.x * img_w) as i32;
+                    let y = (k.y * img_h) as i32;
                     drawing::draw_filled_circle_mut(
                         img,
-                        (x as i32, y as i32),
+                        (x, y),
                         options.keypoint_radius,
                         options.keypoint_color,
                     );
+                    if options.draw_keypoint_index {
+                        draw_text_mut(
+                            img,
+                            &i.to_string(),
+                            x + options.keypoint_radius + 1,
+                            y - options.keypoint_radius,
+                            font_scale,
+                            options.keypoint_color,
+                        );
+                    }
+                }
+            }
+        }
+
+        if options.draw_label {
+            if let Some(category) = category {
+                let text = label_text(category, options.draw_score);
+                let (text_w, text_h) = text_size(&text, font_scale);
+                let padding = font_scale as i32;
+                let label_w = text_w as i32 + 2 * padding;
+                let label_h = text_h as i32 + 2 * padding;
+                // draw the label above the bounding box, or inside it if there is no space
+                let label_top = if top >= label_h { top - label_h } else { top };
+                if options.draw_label_background {
+                    let rect = imageproc::rect::Rect::at(left, label_top)
+                        .of_size(label_w as u32, label_h as u32);
+                    drawing::draw_filled_rect_mut(img, rect, color);
                 }
+                let text_color = match options.label_color {
+                    Some(c) => c,
+                    None if options.draw_label_background => contrast_color(&color),
+                    None => color,
+                };
+                draw_text_mut(
+                    img,
+                    &text,
+                    left + padding,
+                    label_top + padding,
+                    font_scale,
+                    text_color,
+                );
             }
         }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::postprocess::{Detection, Rect};
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn test_draw_detection() {
+        let category = |index: u32, name: Option<&str>| Category {
+            index,
+            score: 0.875,
+            display_name: None,
+            category_name: name.map(|n| n.to_string()),
+        };
+        assert_eq!(label_text(&category(3, Some("cat")), true), "cat 0.88");
+        assert_eq!(label_text(&category(3, None), false), "3");
+
+        let red = Rgb([255, 0, 0]);
+        let green = Rgb([0, 255, 0]);
+        let black = Rgb([0, 0, 0]);
+        let result = DetectionResult {
+            detections: vec![Detection {
+                categories: vec![category(1, Some("a"))],
+                bounding_box: Rect {
+                    left: 0.25,
+                    top: 0.5,
+                    right: 0.75,
+                    bottom: 1.,
+                },
+                key_points: None,
+            }],
+        };
+        let options = DrawDetectionsOptions::new(red, red)
+            .border_thickness(2)
+            .category_colors(vec![red, green])
+            .draw_score(false)
+            .font_scale(1);
+
+        let mut img = RgbImage::new(40, 40);
+        draw_detection_with_options(&mut img, &result, &options);
+        // borders
+        assert_eq!(img.get_pixel(10, 39), &green);
+        assert_eq!(img.get_pixel(11, 38), &green);
+        assert_eq!(img.get_pixel(12, 37), &black);
+        // label background is above the box: 5 + 2 pixels width, 7 + 2 pixels height
+        assert_eq!(img.get_pixel(10, 11), &green);
+        assert_eq!(img.get_pixel(16, 19), &green);
+        assert_eq!(img.get_pixel(17, 15), &black);
+        // text 'a' is black on green
+        assert_eq!(img.get_pixel(12, 14), &black);
+        assert_eq!(img.get_pixel(11, 14), &green);
 
-        // todo: draw label
+        // no space above the box, the label is drawn inside it
+        let mut img = RgbImage::new(40, 16);
+        draw_detection_with_options(&mut img, &result, &options.draw_label_background(false));
+        assert_eq!(img.get_pixel(13, 11), &green);
+        assert_eq!(img.get_pixel(15, 11), &black);
     }
 }
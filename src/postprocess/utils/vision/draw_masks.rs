@@ -0,0 +1,308 @@
+use crate::postprocess::{ImageCategoryMask, ImageConfidenceMask};
+use image::{GenericImage, GenericImageView, ImageBuffer, Pixel, Rgb, RgbImage};
+
+/// draw segmentation masks options
+#[derive(Debug)]
+pub struct DrawMasksOptions {
+    /// Colors for each category, the color of category `i` is `palette[i % palette.len()]`.
+    /// Empty means using the default palette (the PASCAL VOC colormap).
+    pub palette: Vec<Rgb<u8>>,
+    /// The opacity of mask colors, in the range [0, 1].
+    pub alpha: f32,
+    /// The category which will not be drawn, usually the background.
+    pub background_category: Option<u8>,
+}
+
+impl DrawMasksOptions {
+    #[inline(always)]
+    pub fn new(palette: Vec<Rgb<u8>>) -> Self {
+        Self {
+            palette,
+            alpha: 0.5,
+            background_category: Some(0),
+        }
+    }
+
+    #[inline(always)]
+    pub fn alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    #[inline(always)]
+    pub fn background_category(mut self, background_category: Option<u8>) -> Self {
+        self.background_category = background_category;
+        self
+    }
+}
+
+impl Default for DrawMasksOptions {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+
+/// Get the default color of category, using the PASCAL VOC colormap.
+pub fn default_category_color(category: u8) -> Rgb<u8> {
+    let mut color = [0u8; 3];
+    let mut c = category;
+    for j in 0..8 {
+        for (i, channel) in color.iter_mut().enumerate() {
+            *channel |= ((c >> i) & 1) << (7 - j);
+        }
+        c >>= 3;
+    }
+    Rgb(color)
+}
+
+#[inline(always)]
+fn category_color(palette: &[Rgb<u8>], category: u8) -> Rgb<u8> {
+    if palette.is_empty() {
+        default_category_color(category)
+    } else {
+        palette[category as usize % palette.len()]
+    }
+}
+
+/// Get the heatmap color (the jet colormap) of confidence, which is clamped to [0, 1].
+pub fn confidence_heatmap_color(confidence: f32) -> Rgb<u8> {
+    let v = if confidence.is_nan() {
+        0.
+    } else {
+        confidence.clamp(0., 1.) * 4.
+    };
+    let channel = |center: f32| ((1.5 - (v - center).abs()).clamp(0., 1.) * 255.).round() as u8;
+    Rgb([channel(3.), channel(2.), channel(1.)])
+}
+
+/// Colorize the category mask with the palette, empty palette means using the default palette.
+pub fn colorize_category_mask(mask: &ImageCategoryMask, palette: &[Rgb<u8>]) -> RgbImage {
+    RgbImage::from_fn(mask.width(), mask.height(), |x, y| {
+        category_color(palette, mask.get_pixel(x, y).0[0])
+    })
+}
+
+/// Render the confidence mask as a heatmap image, from blue (0) to red (1).
+pub fn confidence_mask_heatmap(mask: &ImageConfidenceMask) -> RgbImage {
+    RgbImage::from_fn(mask.width(), mask.height(), |x, y| {
+        confidence_heatmap_color(mask.get_pixel(x, y).0[0])
+    })
+}
+
+#[inline(always)]
+fn blend_channel(dst: u8, src: u8, alpha: f32) -> u8 {
+    (dst as f32 + (src as f32 - dst as f32) * alpha).round() as u8
+}
+
+/// Blend the color to pixel, the alpha channel of pixel will not be changed.
+#[inline]
+fn blend_color<P: Pixel<Subpixel = u8>>(pixel: &mut P, color: Rgb<u8>, alpha: f32) {
+    let channels = pixel.channels_mut();
+    if P::CHANNEL_COUNT >= 3 {
+        for (c, src) in channels.iter_mut().zip(color.0) {
+            *c = blend_channel(*c, src, alpha);
+        }
+    } else {
+        channels[0] = blend_channel(channels[0], color.to_luma().0[0], alpha);
+    }
+}
+
+/// Get the mask pixel for image pixel using the nearest neighbor, the mask can have a different size from image.
+#[inline(always)]
+fn nearest(mask_size: u32, img_size: u32, i: u32) -> u32 {
+    ((i as u64 * mask_size as u64) / img_size as u64) as u32
+}
+
+/// Get the confidence for image pixel using the bilinear interpolation.
+#[inline]
+fn sample_confidence(mask: &ImageConfidenceMask, img_w: u32, img_h: u32, x: u32, y: u32) -> f32 {
+    let axis = |mask_size: u32, img_size: u32, i: u32| {
+        let p = ((i as f32 + 0.5) * mask_size as f32 / img_size as f32 - 0.5)
+            .clamp(0., (mask_size - 1) as f32);
+        let p0 = p.floor() as u32;
+        (p0, (p0 + 1).min(mask_size - 1), p - p0 as f32)
+    };
+    let (x0, x1, fx) = axis(mask.width(), img_w, x);
+    let (y0, y1, fy) = axis(mask.height(), img_h, y);
+    let v = |x, y| mask.get_pixel(x, y).0[0];
+    let top = v(x0, y0) + (v(x1, y0) - v(x0, y0)) * fx;
+    let bottom = v(x0, y1) + (v(x1, y1) - v(x0, y1)) * fx;
+    let c = top + (bottom - top) * fy;
+    if c.is_nan() {
+        0.
+    } else {
+        c.clamp(0., 1.)
+    }
+}
+
+/// draw category mask to image with default options
+#[inline(always)]
+pub fn draw_category_mask<I>(img: &mut I, mask: &ImageCategoryMask)
+where
+    I: GenericImage,
+    I::Pixel: Pixel<Subpixel = u8>,
+{
+    draw_category_mask_with_options(img, mask, &Default::default())
+}
+
+/// draw category mask to image with options, the mask will be scaled to the image size.
+pub fn draw_category_mask_with_options<I>(
+    img: &mut I,
+    mask: &ImageCategoryMask,
+    options: &DrawMasksOptions,
+) where
+    I: GenericImage,
+    I::Pixel: Pixel<Subpixel = u8>,
+{
+    let (img_w, img_h) = img.dimensions();
+    if mask.width() == 0 || mask.height() == 0 {
+        return;
+    }
+    let alpha = options.alpha.clamp(0., 1.);
+    for y in 0..img_h {
+        let mask_y = nearest(mask.height(), img_h, y);
+        for x in 0..img_w {
+            let category = mask.get_pixel(nearest(mask.width(), img_w, x), mask_y).0[0];
+            if options.background_category == Some(category) {
+                continue;
+            }
+            let mut pixel = img.get_pixel(x, y);
+            blend_color(
+                &mut pixel,
+                category_color(&options.palette, category),
+                alpha,
+            );
+            img.put_pixel(x, y, pixel);
+        }
+    }
+}
+
+/// draw confidence mask to image as a heatmap, the mask will be scaled to the image size.
+pub fn draw_confidence_mask<I>(img: &mut I, mask: &ImageConfidenceMask, alpha: f32)
+where
+    I: GenericImage,
+    I::Pixel: Pixel<Subpixel = u8>,
+{
+    let (img_w, img_h) = img.dimensions();
+    if mask.width() == 0 || mask.height() == 0 {
+        return;
+    }
+    let alpha = alpha.clamp(0., 1.);
+    for y in 0..img_h {
+        for x in 0..img_w {
+            let confidence = sample_confidence(mask, img_w, img_h, x, y);
+            let mut pixel = img.get_pixel(x, y);
+            blend_color(&mut pixel, confidence_heatmap_color(confidence), alpha);
+            img.put_pixel(x, y, pixel);
+        }
+    }
+}
+
+/// Composite the foreground of image over the background image.
+/// The mask is the foreground confidence (e.g. the person mask of selfie segmentation),
+/// and the background will be scaled to the image size.
+pub fn replace_background<I, B>(img: &mut I, mask: &ImageConfidenceMask, background: &B)
+where
+    I: GenericImage,
+    I::Pixel: Pixel<Subpixel = u8>,
+    B: GenericImageView<Pixel = I::Pixel>,
+{
+    let (img_w, img_h) = img.dimensions();
+    let (bg_w, bg_h) = background.dimensions();
+    if mask.width() == 0 || mask.height() == 0 || bg_w == 0 || bg_h == 0 {
+        return;
+    }
+    for y in 0..img_h {
+        let bg_y = nearest(bg_h, img_h, y);
+        for x in 0..img_w {
+            let confidence = sample_confidence(mask, img_w, img_h, x, y);
+            let bg = background.get_pixel(nearest(bg_w, img_w, x), bg_y);
+            let pixel = img
+                .get_pixel(x, y)
+                .map2(&bg, |f, b| blend_channel(b, f, confidence));
+            img.put_pixel(x, y, pixel);
+        }
+    }
+}
+
+/// Blur the background of image with the gaussian blur, and keep the foreground.
+/// The mask is the foreground confidence (e.g. the person mask of selfie segmentation).
+pub fn blur_background<I>(img: &mut I, mask: &ImageConfidenceMask, sigma: f32)
+where
+    I: GenericImage,
+    I::Pixel: Pixel<Subpixel = u8> + 'static,
+{
+    if sigma.is_nan() || sigma <= 0. {
+        return;
+    }
+    let (img_w, img_h) = img.dimensions();
+    let src: ImageBuffer<I::Pixel, Vec<u8>> =
+        ImageBuffer::from_fn(img_w, img_h, |x, y| img.get_pixel(x, y));
+    let blurred = imageproc::filter::gaussian_blur_f32(&src, sigma);
+    replace_background(img, mask, &blurred);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use image::{GrayImage, Luma, Rgba, RgbaImage};
+
+    #[test]
+    fn test_colorize_masks() {
+        assert_eq!(default_category_color(0), Rgb([0, 0, 0]));
+        assert_eq!(default_category_color(1), Rgb([128, 0, 0]));
+        assert_eq!(default_category_color(15), Rgb([192, 128, 128]));
+        assert_eq!(confidence_heatmap_color(0.), Rgb([0, 0, 128]));
+        assert_eq!(confidence_heatmap_color(0.5), Rgb([128, 255, 128]));
+        assert_eq!(confidence_heatmap_color(2.), Rgb([128, 0, 0]));
+
+        let mask = ImageCategoryMask::from_raw(2, 1, vec![0, 3]).unwrap();
+        let palette = [Rgb([1, 2, 3]), Rgb([4, 5, 6])];
+        let colors = colorize_category_mask(&mask, &palette);
+        assert_eq!(colors.get_pixel(0, 0), &palette[0]);
+        assert_eq!(colors.get_pixel(1, 0), &palette[1]);
+        let heatmap = confidence_mask_heatmap(&ImageConfidenceMask::new(1, 1));
+        assert_eq!(heatmap.get_pixel(0, 0), &Rgb([0, 0, 128]));
+
+        // the mask is scaled to the image size, and the background is not drawn
+        let mut img = RgbaImage::from_pixel(4, 2, Rgba([100, 100, 100, 255]));
+        draw_category_mask_with_options(
+            &mut img,
+            &mask,
+            &DrawMasksOptions::new(vec![Rgb([200, 0, 100])]).alpha(0.5),
+        );
+        assert_eq!(img.get_pixel(1, 1), &Rgba([100, 100, 100, 255]));
+        assert_eq!(img.get_pixel(2, 0), &Rgba([150, 50, 100, 255]));
+        let mut img = GrayImage::new(4, 2);
+        draw_confidence_mask(&mut img, &ImageConfidenceMask::new(2, 2), 1.);
+        assert_eq!(
+            img.get_pixel(3, 1),
+            &Luma([Rgb([0u8, 0, 128]).to_luma().0[0]])
+        );
+    }
+
+    #[test]
+    fn test_composite_background() {
+        let mask = ImageConfidenceMask::from_raw(2, 1, vec![1., 0.]).unwrap();
+        let background = RgbImage::from_pixel(1, 1, Rgb([0, 0, 0]));
+        let mut img = RgbImage::from_pixel(4, 1, Rgb([200, 100, 0]));
+        replace_background(&mut img, &mask, &background);
+        assert_eq!(img.get_pixel(0, 0), &Rgb([200, 100, 0]));
+        assert_eq!(img.get_pixel(1, 0), &Rgb([150, 75, 0]));
+        assert_eq!(img.get_pixel(2, 0), &Rgb([50, 25, 0]));
+        assert_eq!(img.get_pixel(3, 0), &Rgb([0, 0, 0]));
+
+        // the foreground is kept
+        let mut img = RgbImage::from_fn(4, 1, |x, _| Rgb([x as u8 * 80, 0, 0]));
+        let origin = img.clone();
+        blur_background(
+            &mut img,
+            &ImageConfidenceMask::from_pixel(1, 1, Luma([1.])),
+            2.,
+        );
+        assert_eq!(img, origin);
+        blur_background(&mut img, &ImageConfidenceMask::new(1, 1), 2.);
+        assert_ne!(img, origin);
+    }
+}
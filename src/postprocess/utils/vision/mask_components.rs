@@ -0,0 +1,207 @@
+use super::MaskRle;
+use crate::postprocess::{ImageCategoryMask, ImageConfidenceMask, Rect};
+use image::{GrayImage, Luma};
+use imageproc::contours::BorderType;
+use imageproc::point::Point;
+
+/// A 8-connected region of pixels which have the same category in a category mask.
+#[derive(Debug, Clone)]
+pub struct MaskComponent {
+    /// The category of pixels in the component.
+    pub category: u8,
+    /// The number of pixels in the component.
+    pub area: u32,
+    /// The bounding box in pixels, the right and bottom are exclusive.
+    pub bounding_box: Rect<u32>,
+    /// The binary mask in the bounding box, the pixels of component are 255 and others are 0.
+    pub mask: GrayImage,
+}
+
+/// A contour of mask component, the points are pixel coordinates ```(x, y)``` in the category mask.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaskContour {
+    pub points: Vec<(u32, u32)>,
+    /// Whether this is the border of a hole in the component, otherwise it is the outer border.
+    pub is_hole: bool,
+}
+
+impl MaskComponent {
+    /// Get the outer contour and the hole contours (in this order) of the component.
+    /// The contours are simplified by the Douglas-Peucker algorithm with ```epsilon``` in pixels,
+    /// ```0``` means not simplifying.
+    pub fn contours(&self, epsilon: f64) -> Vec<MaskContour> {
+        // pad the mask to make the border following work at the edges of bounding box
+        let (w, h) = self.mask.dimensions();
+        let mut padded = GrayImage::new(w + 2, h + 2);
+        image::imageops::replace(&mut padded, &self.mask, 1, 1);
+
+        let mut contours = imageproc::contours::find_contours::<i32>(&padded);
+        contours.sort_by_key(|c| c.border_type == BorderType::Hole);
+        contours
+            .into_iter()
+            .map(|c| {
+                let points = if epsilon > 0. && c.points.len() > 2 {
+                    imageproc::geometry::approximate_polygon_dp(&c.points, epsilon, true)
+                } else {
+                    c.points
+                };
+                MaskContour {
+                    points: points
+                        .into_iter()
+                        .map(|p: Point<i32>| {
+                            (
+                                (p.x - 1) as u32 + self.bounding_box.left,
+                                (p.y - 1) as u32 + self.bounding_box.top,
+                            )
+                        })
+                        .collect(),
+                    is_hole: c.border_type == BorderType::Hole,
+                }
+            })
+            .collect()
+    }
+
+    /// Get the simplified outer contour of the component as a polygon, see [`MaskComponent::contours`].
+    #[inline]
+    pub fn polygon(&self, epsilon: f64) -> Vec<(u32, u32)> {
+        self.contours(epsilon)
+            .into_iter()
+            .next()
+            .map(|c| c.points)
+            .unwrap_or_default()
+    }
+
+    /// Get the run-length encoding of the component in the full mask, which has the size ```(width, height)```.
+    pub fn to_rle(&self, width: u32, height: u32) -> MaskRle {
+        let b = &self.bounding_box;
+        MaskRle::encode(width, height, |x, y| {
+            x >= b.left
+                && x < b.right
+                && y >= b.top
+                && y < b.bottom
+                && self.mask.get_pixel(x - b.left, y - b.top).0[0] != 0
+        })
+    }
+}
+
+/// Convert the confidence mask to a binary category mask, the pixels whose confidence is greater than or equal to
+/// the threshold are 1, and others are 0.
+pub fn threshold_confidence_mask(mask: &ImageConfidenceMask, threshold: f32) -> ImageCategoryMask {
+    ImageCategoryMask::from_fn(mask.width(), mask.height(), |x, y| {
+        Luma([(mask.get_pixel(x, y).0[0] >= threshold) as u8])
+    })
+}
+
+/// Find the 8-connected components of each category in the category mask, in the scan order.
+/// The pixels of ```background``` category are ignored.
+pub fn mask_components(mask: &ImageCategoryMask, background: Option<u8>) -> Vec<MaskComponent> {
+    let (width, height) = mask.dimensions();
+    let mut visited = vec![false; width as usize * height as usize];
+    let mut components = Vec::new();
+    let mut stack = Vec::new();
+    let mut pixels = Vec::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            let index = (y * width + x) as usize;
+            let category = mask.get_pixel(x, y).0[0];
+            if visited[index] || background == Some(category) {
+                continue;
+            }
+
+            // flood fill
+            visited[index] = true;
+            stack.push((x, y));
+            pixels.clear();
+            let mut b = Rect {
+                left: x,
+                top: y,
+                right: x + 1,
+                bottom: y + 1,
+            };
+            while let Some((px, py)) = stack.pop() {
+                pixels.push((px, py));
+                b.left = b.left.min(px);
+                b.top = b.top.min(py);
+                b.right = b.right.max(px + 1);
+                b.bottom = b.bottom.max(py + 1);
+                for ny in py.saturating_sub(1)..(py + 2).min(height) {
+                    for nx in px.saturating_sub(1)..(px + 2).min(width) {
+                        let n = (ny * width + nx) as usize;
+                        if !visited[n] && mask.get_pixel(nx, ny).0[0] == category {
+                            visited[n] = true;
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+            }
+
+            let mut component_mask = GrayImage::new(b.right - b.left, b.bottom - b.top);
+            for (px, py) in pixels.iter() {
+                component_mask.put_pixel(px - b.left, py - b.top, Luma([255]));
+            }
+            components.push(MaskComponent {
+                category,
+                area: pixels.len() as u32,
+                bounding_box: b,
+                mask: component_mask,
+            });
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mask_components() {
+        #[rustfmt::skip]
+        let mask = ImageCategoryMask::from_raw(6, 5, vec![
+            0, 1, 1, 1, 0, 2,
+            0, 1, 0, 1, 0, 0,
+            0, 1, 1, 1, 0, 2,
+            0, 0, 0, 0, 2, 0,
+            1, 0, 0, 0, 0, 0,
+        ]).unwrap();
+        let components = mask_components(&mask, Some(0));
+        assert_eq!(components.len(), 4);
+        let ring = &components[0];
+        assert_eq!(ring.category, 1);
+        assert_eq!(ring.area, 8);
+        assert_eq!(
+            ring.bounding_box,
+            Rect {
+                left: 1,
+                top: 0,
+                right: 4,
+                bottom: 3
+            }
+        );
+        // the diagonal pixels are connected
+        assert_eq!(components[2].category, 2);
+        assert_eq!(components[2].area, 2);
+        assert_eq!(components[3].bounding_box.top, 4);
+        assert_eq!(mask_components(&mask, None).len(), 6);
+
+        let contours = ring.contours(0.);
+        assert_eq!(contours.len(), 2);
+        assert!(!contours[0].is_hole && contours[1].is_hole);
+        let mut polygon = ring.polygon(0.5);
+        polygon.sort();
+        assert_eq!(polygon, vec![(1, 0), (1, 2), (3, 0), (3, 2)]);
+
+        let rle = ring.to_rle(6, 5);
+        assert_eq!(rle.area(), 8);
+        assert_eq!(rle, MaskRle::encode(6, 5, |x, y| ring_pixel(&mask, x, y)));
+
+        let confidence = ImageConfidenceMask::from_raw(2, 1, vec![0.2, 0.7]).unwrap();
+        let binary = threshold_confidence_mask(&confidence, 0.5);
+        assert_eq!(binary.as_raw(), &vec![0, 1]);
+    }
+
+    fn ring_pixel(mask: &ImageCategoryMask, x: u32, y: u32) -> bool {
+        x < 4 && y < 3 && mask.get_pixel(x, y).0[0] == 1
+    }
+}
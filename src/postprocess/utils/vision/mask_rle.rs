@@ -0,0 +1,169 @@
+use crate::postprocess::ImageCategoryMask;
+use crate::Error;
+
+/// The run-length encoding of a binary mask, which is compatible with the COCO dataset format.
+///
+/// The pixels are visited in column-major order (from top to bottom, then from left to right),
+/// and the counts are the lengths of runs alternating between background and foreground,
+/// starting with background (so the first count may be 0).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaskRle {
+    pub width: u32,
+    pub height: u32,
+    pub counts: Vec<u32>,
+}
+
+impl MaskRle {
+    /// Encode the binary mask, ```is_foreground(x, y)``` returns whether the pixel is foreground.
+    pub fn encode(width: u32, height: u32, is_foreground: impl Fn(u32, u32) -> bool) -> Self {
+        let mut counts = Vec::new();
+        let mut current = false;
+        let mut run = 0;
+        for x in 0..width {
+            for y in 0..height {
+                if is_foreground(x, y) != current {
+                    counts.push(run);
+                    run = 0;
+                    current = !current;
+                }
+                run += 1;
+            }
+        }
+        counts.push(run);
+        Self {
+            width,
+            height,
+            counts,
+        }
+    }
+
+    /// Encode the pixels of the category in the category mask.
+    #[inline]
+    pub fn from_category_mask(mask: &ImageCategoryMask, category: u8) -> Self {
+        Self::encode(mask.width(), mask.height(), |x, y| {
+            mask.get_pixel(x, y).0[0] == category
+        })
+    }
+
+    /// Get the number of foreground pixels.
+    #[inline]
+    pub fn area(&self) -> u32 {
+        self.counts.iter().skip(1).step_by(2).sum()
+    }
+
+    /// Decode to a mask, the foreground pixels are 1 and the background pixels are 0.
+    pub fn decode(&self) -> Result<ImageCategoryMask, Error> {
+        let total = self.width as u64 * self.height as u64;
+        if self.counts.iter().map(|c| *c as u64).sum::<u64>() != total {
+            return Err(Error::ArgumentError(format!(
+                "The sum of RLE counts is not equal to the number of pixels `{}`",
+                total
+            )));
+        }
+        let mut mask = ImageCategoryMask::new(self.width, self.height);
+        let mut index = 0;
+        for (i, count) in self.counts.iter().enumerate() {
+            if i % 2 == 1 {
+                for p in index..index + *count {
+                    mask.put_pixel(p / self.height, p % self.height, image::Luma([1]));
+                }
+            }
+            index += *count;
+        }
+        Ok(mask)
+    }
+
+    /// Get the compressed string of counts, which is the same as ```pycocotools```.
+    pub fn to_coco_string(&self) -> String {
+        let mut s = String::new();
+        for i in 0..self.counts.len() {
+            let mut x = self.counts[i] as i64;
+            if i > 2 {
+                x -= self.counts[i - 2] as i64;
+            }
+            loop {
+                let mut c = (x & 0x1f) as u8;
+                x >>= 5;
+                let more = if c & 0x10 != 0 { x != -1 } else { x != 0 };
+                if more {
+                    c |= 0x20;
+                }
+                s.push((c + 48) as char);
+                if !more {
+                    break;
+                }
+            }
+        }
+        s
+    }
+
+    /// Parse the compressed string of counts, which is the same as ```pycocotools```.
+    pub fn from_coco_string(width: u32, height: u32, s: &str) -> Result<Self, Error> {
+        let invalid = || Error::ArgumentError(format!("Invalid COCO RLE string `{}`", s));
+        let mut counts: Vec<u32> = Vec::new();
+        let mut bytes = s.bytes();
+        while let Some(first) = bytes.next() {
+            let mut x = 0i64;
+            let mut k = 0;
+            let mut b = first;
+            loop {
+                if !(48..48 + 64).contains(&b) || k >= 12 {
+                    return Err(invalid());
+                }
+                let c = (b - 48) as i64;
+                x |= (c & 0x1f) << (5 * k);
+                k += 1;
+                if c & 0x20 == 0 {
+                    if c & 0x10 != 0 {
+                        x |= -1 << (5 * k);
+                    }
+                    break;
+                }
+                b = bytes.next().ok_or_else(invalid)?;
+            }
+            if counts.len() > 2 {
+                x += counts[counts.len() - 2] as i64;
+            }
+            counts.push(u32::try_from(x).map_err(|_| invalid())?);
+        }
+        Ok(Self {
+            width,
+            height,
+            counts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mask_rle() {
+        // column-major: [0, 1, 1, 1, 0, 0]
+        let mask = ImageCategoryMask::from_raw(3, 2, vec![0, 1, 0, 1, 1, 0]).unwrap();
+        let rle = MaskRle::from_category_mask(&mask, 1);
+        assert_eq!(rle.counts, vec![1, 3, 2]);
+        assert_eq!(rle.area(), 3);
+        assert_eq!(rle.decode().unwrap(), mask);
+        let rle = MaskRle::from_category_mask(&mask, 0);
+        assert_eq!(rle.counts, vec![0, 1, 3, 2]);
+
+        // compressed string is the same as pycocotools
+        let rle = MaskRle {
+            width: 100,
+            height: 1,
+            counts: vec![5, 1, 1, 0, 40, 53],
+        };
+        assert_eq!(rle.to_coco_string(), "511OW1e1");
+        assert_eq!(MaskRle::from_coco_string(100, 1, "511OW1e1").unwrap(), rle);
+        assert!(MaskRle::from_coco_string(1, 1, "5 ").is_err());
+        assert!(MaskRle::from_coco_string(1, 1, "X").is_err());
+        assert!(rle.decode().is_ok());
+        assert!(MaskRle::from_coco_string(2, 2, "5")
+            .unwrap()
+            .decode()
+            .is_err());
+    }
+}
@@ -1,7 +1,18 @@
+mod bitmap_font;
 mod default_pixel;
 mod draw_detections;
 mod draw_landmarks;
+mod draw_masks;
+mod mask_components;
+mod mask_rle;
+#[cfg(feature = "ffmpeg")]
+mod video_writer;
 
 pub use default_pixel::*;
 pub use draw_detections::*;
 pub use draw_landmarks::*;
+pub use draw_masks::*;
+pub use mask_components::*;
+pub use mask_rle::*;
+#[cfg(feature = "ffmpeg")]
+pub use video_writer::*;
@@ -0,0 +1,442 @@
+use crate::preprocess::vision::{
+    ImageColorSpaceType, ImageDataLayout, ImageLikeTensorShape, ImageToTensor, ImageToTensorInfo,
+    VideoData,
+};
+use crate::tasks::vision::TaskSession;
+use crate::{Error, TensorType};
+use image::RgbImage;
+use std::path::Path;
+
+/// Video codecs supported by [`FFMpegVideoWriter`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum VideoCodec {
+    H264,
+    VP9,
+    MJPEG,
+}
+
+impl VideoCodec {
+    /// The default codec for the container format: ```webm``` uses VP9, ```avi``` uses MJPEG,
+    /// and others (such as ```mp4```, ```mkv```) use H.264.
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("webm") => VideoCodec::VP9,
+            Some("avi") => VideoCodec::MJPEG,
+            _ => VideoCodec::H264,
+        }
+    }
+
+    #[inline(always)]
+    fn codec_id(self) -> ffmpeg_next::codec::Id {
+        match self {
+            VideoCodec::H264 => ffmpeg_next::codec::Id::H264,
+            VideoCodec::VP9 => ffmpeg_next::codec::Id::VP9,
+            VideoCodec::MJPEG => ffmpeg_next::codec::Id::MJPEG,
+        }
+    }
+
+    #[inline(always)]
+    fn pixel_format(self) -> ffmpeg_next::format::Pixel {
+        match self {
+            VideoCodec::H264 | VideoCodec::VP9 => ffmpeg_next::format::Pixel::YUV420P,
+            // full range yuv for jpeg
+            VideoCodec::MJPEG => ffmpeg_next::format::Pixel::YUVJ420P,
+        }
+    }
+}
+
+/// Options for [`FFMpegVideoWriter`].
+#[derive(Debug, Clone)]
+pub struct FFMpegVideoWriterOptions {
+    /// The video codec, `None` means choosing by the output file extension, see [`VideoCodec::from_path`].
+    pub codec: Option<VideoCodec>,
+    /// The nominal frame rate, the real frame timing is decided by frame timestamps.
+    pub frame_rate: f64,
+    /// The target bit rate (bits/s), `None` means using the encoder default.
+    pub bit_rate: Option<usize>,
+}
+
+impl FFMpegVideoWriterOptions {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline(always)]
+    pub fn codec(mut self, codec: VideoCodec) -> Self {
+        self.codec = Some(codec);
+        self
+    }
+
+    #[inline(always)]
+    pub fn frame_rate(mut self, frame_rate: f64) -> Self {
+        self.frame_rate = frame_rate;
+        self
+    }
+
+    #[inline(always)]
+    pub fn bit_rate(mut self, bit_rate: usize) -> Self {
+        self.bit_rate = Some(bit_rate);
+        self
+    }
+}
+
+impl Default for FFMpegVideoWriterOptions {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            codec: None,
+            frame_rate: 30.,
+            bit_rate: None,
+        }
+    }
+}
+
+/// Write [`RgbImage`] frames to a video file using FFMpeg, such as H.264 in MP4, VP9 in WebM, MJPEG in AVI.
+///
+/// The frame timestamps must be strictly increasing. The output file is completed when calling
+/// [`FFMpegVideoWriter::finish`] or dropping the writer, and ```finish``` should be used to get the errors.
+///
+/// ```rust,no_run
+/// use mediapipe_rs::postprocess::utils::FFMpegVideoWriter;
+///
+/// let mut writer = FFMpegVideoWriter::new("output.mp4", 640, 480)?;
+/// for i in 0..30 {
+///     writer.write_frame(&image::RgbImage::new(640, 480), i * 40)?;
+/// }
+/// writer.finish()?;
+/// # Ok::<(), mediapipe_rs::Error>(())
+/// ```
+pub struct FFMpegVideoWriter {
+    output: ffmpeg_next::format::context::Output,
+    encoder: ffmpeg_next::encoder::video::Encoder,
+    scaler: ffmpeg_next::software::scaling::Context,
+    rgb_frame: ffmpeg_next::frame::Video,
+    width: u32,
+    height: u32,
+    stream_index: usize,
+    stream_time_base: ffmpeg_next::Rational,
+    last_timestamp_ms: Option<u64>,
+    num_frames: usize,
+    is_finished: bool,
+}
+
+impl FFMpegVideoWriter {
+    /// The encoder uses timestamps in milliseconds.
+    const ENCODER_TIME_BASE: ffmpeg_next::Rational = ffmpeg_next::Rational(1, 1000);
+
+    const RESOURCE_TEMPORARILY_UNAVAILABLE: ffmpeg_next::Error = ffmpeg_next::Error::Other {
+        errno: ffmpeg_next::util::error::EAGAIN,
+    };
+
+    /// Create a video file with default options, the container format is guessed by the file extension.
+    #[inline(always)]
+    pub fn new(path: impl AsRef<Path>, width: u32, height: u32) -> Result<Self, Error> {
+        Self::with_options(path, width, height, Default::default())
+    }
+
+    /// Create a video file with options, the container format is guessed by the file extension.
+    pub fn with_options(
+        path: impl AsRef<Path>,
+        width: u32,
+        height: u32,
+        options: FFMpegVideoWriterOptions,
+    ) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let codec = options.codec.unwrap_or_else(|| VideoCodec::from_path(path));
+        if width == 0 || height == 0 {
+            return Err(Error::ArgumentError(format!(
+                "Invalid video size `{}x{}`",
+                width, height
+            )));
+        }
+        // yuv420p needs even size
+        if codec != VideoCodec::MJPEG && (width % 2 != 0 || height % 2 != 0) {
+            return Err(Error::ArgumentError(format!(
+                "The video size must be even for `{:?}`, but got `{}x{}`",
+                codec, width, height
+            )));
+        }
+        if options.frame_rate.is_nan()
+            || options.frame_rate <= 0.
+            || options.frame_rate.is_infinite()
+        {
+            return Err(Error::ArgumentError(format!(
+                "Invalid frame rate `{}`",
+                options.frame_rate
+            )));
+        }
+
+        let mut output = ffmpeg_next::format::output(&path)?;
+        let global_header = output
+            .format()
+            .flags()
+            .contains(ffmpeg_next::format::Flags::GLOBAL_HEADER);
+        let encoder_codec = ffmpeg_next::encoder::find(codec.codec_id())
+            .ok_or(ffmpeg_next::Error::EncoderNotFound)?;
+
+        let mut stream = output.add_stream(encoder_codec)?;
+        let stream_index = stream.index();
+        let mut encoder = ffmpeg_next::codec::Context::new_with_codec(encoder_codec)
+            .encoder()
+            .video()?;
+        encoder.set_width(width);
+        encoder.set_height(height);
+        encoder.set_format(codec.pixel_format());
+        encoder.set_time_base(Self::ENCODER_TIME_BASE);
+        encoder.set_frame_rate(Some(options.frame_rate));
+        if let Some(bit_rate) = options.bit_rate {
+            encoder.set_bit_rate(bit_rate);
+        }
+        if global_header {
+            encoder.set_flags(ffmpeg_next::codec::Flags::GLOBAL_HEADER);
+        }
+        let encoder = encoder.open_as(encoder_codec)?;
+        stream.set_parameters(&encoder);
+        stream.set_time_base(Self::ENCODER_TIME_BASE);
+
+        // the muxer may change the stream time base when writing header
+        output.write_header()?;
+        let stream_time_base = output
+            .stream(stream_index)
+            .map(|s| s.time_base())
+            .unwrap_or(Self::ENCODER_TIME_BASE);
+
+        let scaler = ffmpeg_next::software::scaling::Context::get(
+            ffmpeg_next::format::Pixel::RGB24,
+            width,
+            height,
+            codec.pixel_format(),
+            width,
+            height,
+            ffmpeg_next::software::scaling::Flags::BILINEAR,
+        )?;
+
+        Ok(Self {
+            output,
+            encoder,
+            scaler,
+            rgb_frame: ffmpeg_next::frame::Video::new(
+                ffmpeg_next::format::Pixel::RGB24,
+                width,
+                height,
+            ),
+            width,
+            height,
+            stream_index,
+            stream_time_base,
+            last_timestamp_ms: None,
+            num_frames: 0,
+            is_finished: false,
+        })
+    }
+
+    /// Get the video size: (width, height)
+    #[inline(always)]
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Get the number of frames written.
+    #[inline(always)]
+    pub fn num_frames(&self) -> usize {
+        self.num_frames
+    }
+
+    /// Encode a frame, the image size must be the video size,
+    /// and the timestamp (ms) must be greater than the previous frame.
+    pub fn write_frame(&mut self, image: &RgbImage, timestamp_ms: u64) -> Result<(), Error> {
+        if self.is_finished {
+            return Err(Error::ArgumentError(
+                "The video writer has been finished".into(),
+            ));
+        }
+        if image.dimensions() != (self.width, self.height) {
+            return Err(Error::ArgumentError(format!(
+                "Expect frame size `{}x{}`, but got `{}x{}`",
+                self.width,
+                self.height,
+                image.width(),
+                image.height()
+            )));
+        }
+        if let Some(last_timestamp_ms) = self.last_timestamp_ms {
+            if timestamp_ms <= last_timestamp_ms {
+                return Err(Error::ArgumentError(format!(
+                    "Frame timestamps must be increasing, but got `{}` after `{}`",
+                    timestamp_ms, last_timestamp_ms
+                )));
+            }
+        }
+
+        // the frame lines may have padding
+        let row_len = self.width as usize * 3;
+        let stride = self.rgb_frame.stride(0);
+        let data = self.rgb_frame.data_mut(0);
+        for (y, row) in image.as_raw().chunks_exact(row_len).enumerate() {
+            data[y * stride..y * stride + row_len].copy_from_slice(row);
+        }
+
+        // the encoder may keep a reference of the sent frame, so use a new frame every time
+        let mut yuv_frame = ffmpeg_next::frame::Video::empty();
+        self.scaler.run(&self.rgb_frame, &mut yuv_frame)?;
+        yuv_frame.set_pts(Some(timestamp_ms as i64));
+        self.encoder.send_frame(&yuv_frame)?;
+        self.last_timestamp_ms = Some(timestamp_ms);
+        self.num_frames += 1;
+        self.write_packets()
+    }
+
+    /// Flush the encoder and write the file trailer. Calling it again does nothing.
+    pub fn finish(&mut self) -> Result<(), Error> {
+        if self.is_finished {
+            return Ok(());
+        }
+        self.is_finished = true;
+        self.encoder.send_eof()?;
+        self.write_packets()?;
+        self.output.write_trailer()?;
+        Ok(())
+    }
+
+    fn write_packets(&mut self) -> Result<(), Error> {
+        let mut packet = ffmpeg_next::Packet::empty();
+        loop {
+            match self.encoder.receive_packet(&mut packet) {
+                Ok(()) => {
+                    packet.set_stream(self.stream_index);
+                    packet.rescale_ts(Self::ENCODER_TIME_BASE, self.stream_time_base);
+                    packet.write_interleaved(&mut self.output)?;
+                }
+                Err(ffmpeg_next::Error::Eof) => return Ok(()),
+                Err(err) if err == Self::RESOURCE_TEMPORARILY_UNAVAILABLE => return Ok(()),
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+}
+
+impl Drop for FFMpegVideoWriter {
+    fn drop(&mut self) {
+        // the errors are ignored, use `finish` to check them
+        let _ = self.finish();
+    }
+}
+
+/// Process the video using the task session, draw every result on its frame, and write the annotated
+/// frames to a video file. Return the number of frames written.
+///
+/// The video size is the size of the first frame, and no file is created if the video has no frame.
+///
+/// ```rust,no_run
+/// use mediapipe_rs::postprocess::utils::{annotate_video, draw_detection, FFMpegVideoWriterOptions};
+/// use mediapipe_rs::preprocess::vision::FFMpegVideoData;
+/// use mediapipe_rs::tasks::vision::ObjectDetectorBuilder;
+///
+/// let detector = ObjectDetectorBuilder::new()
+///     .model_asset_path("efficientdet_lite0.tflite")
+///     .finalize()?;
+/// let mut session = detector.new_session()?;
+/// let video = FFMpegVideoData::new(ffmpeg_next::format::input(&"input.mp4")?)?;
+/// annotate_video(
+///     &mut session,
+///     video,
+///     "output.mp4",
+///     FFMpegVideoWriterOptions::new(),
+///     |img, result| draw_detection(img, result),
+/// )?;
+/// # Ok::<(), mediapipe_rs::Error>(())
+/// ```
+pub fn annotate_video<Session, InputVideoData, Draw>(
+    session: &mut Session,
+    video_data: InputVideoData,
+    output_path: impl AsRef<Path>,
+    options: FFMpegVideoWriterOptions,
+    mut draw: Draw,
+) -> Result<usize, Error>
+where
+    Session: TaskSession,
+    InputVideoData: VideoData,
+    Draw: FnMut(&mut RgbImage, &Session::Result),
+{
+    let mut video_data = CaptureFrameVideoData {
+        video_data,
+        frame: None,
+    };
+    let mut writer: Option<FFMpegVideoWriter> = None;
+    while let Some(result) = session.process_next(&Default::default(), &mut video_data)? {
+        let (mut image, timestamp_ms) = match video_data.frame.take() {
+            Some(frame) => frame,
+            None => continue,
+        };
+        draw(&mut image, &result);
+        let writer = match writer {
+            Some(ref mut writer) => writer,
+            None => writer.insert(FFMpegVideoWriter::with_options(
+                output_path.as_ref(),
+                image.width(),
+                image.height(),
+                options.clone(),
+            )?),
+        };
+        writer.write_frame(&image, timestamp_ms)?;
+    }
+
+    match writer {
+        Some(mut writer) => {
+            writer.finish()?;
+            Ok(writer.num_frames())
+        }
+        None => Ok(0),
+    }
+}
+
+/// Keep a RGB copy of the frame which is passed to the task session.
+struct CaptureFrameVideoData<InputVideoData: VideoData> {
+    video_data: InputVideoData,
+    frame: Option<(RgbImage, u64)>,
+}
+
+impl<InputVideoData: VideoData> VideoData for CaptureFrameVideoData<InputVideoData> {
+    type Frame<'frame>
+        = InputVideoData::Frame<'frame>
+    where
+        Self: 'frame;
+
+    fn next_frame(&mut self) -> Result<Option<Self::Frame<'_>>, Error> {
+        let frame = match self.video_data.next_frame()? {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+        let timestamp_ms = frame
+            .timestamp_ms()
+            .ok_or_else(|| Error::ArgumentError("Video frames must have timestamps".into()))?;
+
+        // convert to the rgb8 image with origin size
+        let (width, height) = frame.image_size();
+        let info = ImageToTensorInfo {
+            image_data_layout: ImageDataLayout::NHWC,
+            color_space: ImageColorSpaceType::RGB,
+            tensor_type: TensorType::U8,
+            tensor_shape: ImageLikeTensorShape {
+                batch: 1,
+                width: width as usize,
+                height: height as usize,
+                channels: 3,
+            },
+            stats_min: vec![],
+            stats_max: vec![],
+            normalization_options: (vec![], vec![]),
+        };
+        let mut buffer = vec![0u8; info.tensor_shape.elem_size()];
+        frame.to_tensor(&info, &Default::default(), &mut buffer)?;
+        let image = RgbImage::from_raw(width, height, buffer).unwrap();
+        self.frame = Some((image, timestamp_ms));
+        Ok(Some(frame))
+    }
+}
@@ -12,6 +12,8 @@ where
     input: Input,
     pub decoder: Decoder,
     pub frame: Frame,
+    /// The time base of the input stream, which is also the time base of decoded frames' timestamps.
+    pub time_base: ffmpeg_next::Rational,
     input_stream_index: usize,
     decoder_has_sent_eof: bool,
 }
@@ -29,6 +31,7 @@ macro_rules! impl_new_func {
                         ffmpeg_next::media::Type::$stream_type
                     )))?;
                 let input_stream_index = input_stream.index();
+                let time_base = input_stream.time_base();
                 let context = Context::from_parameters(input_stream.parameters())?;
                 let mut decoder = context.decoder().$decode_func()?;
                 decoder.set_parameters(input_stream.parameters())?;
@@ -36,6 +39,7 @@ macro_rules! impl_new_func {
                     input,
                     decoder,
                     frame: ffmpeg_next::frame::$frame::empty(),
+                    time_base,
                     input_stream_index,
                     decoder_has_sent_eof: false,
                 })
@@ -88,4 +92,15 @@ where
 
         Ok(true)
     }
+
+    /// Seek to the key frame at or before the timestamp (ms), and flush the decoder.
+    /// The frames before the timestamp may still be decoded.
+    #[inline(always)]
+    pub fn seek(&mut self, timestamp_ms: u64) -> Result<(), Error> {
+        let ts = timestamp_ms as i64 * ffmpeg_next::ffi::AV_TIME_BASE as i64 / 1000;
+        self.input.seek(ts, ..ts)?;
+        self.decoder.flush();
+        self.decoder_has_sent_eof = false;
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 mod bert_tensor;
 mod regex_to_tensor;
+mod string_tensor;
 
 use super::*;
 use regex::Regex;
@@ -14,6 +15,17 @@ pub trait TextToTensors {
         to_tensor_info: &TextToTensorInfo,
         output_buffers: &mut T,
     ) -> Result<(), Error>;
+
+    /// Like [`TextToTensors::to_tensors`], but the output buffers can be resized,
+    /// which is necessary for the variable-size tensors such as string tensors.
+    #[inline(always)]
+    fn to_resizable_tensors<T: AsMut<[Vec<u8>]>>(
+        &self,
+        to_tensor_info: &TextToTensorInfo,
+        output_buffers: &mut T,
+    ) -> Result<(), Error> {
+        self.to_tensors(to_tensor_info, output_buffers)
+    }
 }
 
 /// Necessary information for the text to tensors.
@@ -43,6 +55,7 @@ pub enum TextToTensorInfo<'buf> {
         pad_id: i32,
     },
     /// A model taking a string tensor input.
+    /// The string is serialized as TfLite string tensor format and fed as bytes.
     StringModel,
     /// A UniversalSentenceEncoder-based model.
     UseModel,
@@ -157,11 +170,41 @@ impl TextToTensors for &str {
                     *pad_id,
                 );
             }
-            TextToTensorInfo::StringModel | TextToTensorInfo::UseModel => {
-                todo!("Text String model")
+            TextToTensorInfo::StringModel => {
+                debug_assert_eq!(output_buffers.as_mut().len(), 1);
+                // the fixed size buffer must have the same size as the string tensor
+                let mut buffer = Vec::new();
+                string_tensor::to_string_tensor(self, &mut buffer)?;
+                let output_buffer = output_buffers.as_mut()[0].as_mut();
+                if output_buffer.len() != buffer.len() {
+                    return Err(Error::ArgumentError(format!(
+                        "String tensor needs `{}` bytes, but the buffer size is `{}`, use `to_resizable_tensors` instead",
+                        buffer.len(),
+                        output_buffer.len()
+                    )));
+                }
+                output_buffer.copy_from_slice(&buffer);
+                Ok(())
+            }
+            TextToTensorInfo::UseModel => {
+                todo!("Text UniversalSentenceEncoder model")
             }
         }
     }
+
+    fn to_resizable_tensors<T: AsMut<[Vec<u8>]>>(
+        &self,
+        to_tensor_info: &TextToTensorInfo,
+        output_buffers: &mut T,
+    ) -> Result<(), Error> {
+        match to_tensor_info {
+            TextToTensorInfo::StringModel => {
+                debug_assert_eq!(output_buffers.as_mut().len(), 1);
+                string_tensor::to_string_tensor(self, &mut output_buffers.as_mut()[0])
+            }
+            _ => self.to_tensors(to_tensor_info, output_buffers),
+        }
+    }
 }
 
 impl TextToTensors for String {
@@ -173,6 +216,16 @@ impl TextToTensors for String {
     ) -> Result<(), Error> {
         self.as_str().to_tensors(to_tensor_info, output_buffers)
     }
+
+    #[inline(always)]
+    fn to_resizable_tensors<T: AsMut<[Vec<u8>]>>(
+        &self,
+        to_tensor_info: &TextToTensorInfo,
+        output_buffers: &mut T,
+    ) -> Result<(), Error> {
+        self.as_str()
+            .to_resizable_tensors(to_tensor_info, output_buffers)
+    }
 }
 
 impl<'a> TextToTensors for Cow<'a, str> {
@@ -187,4 +240,44 @@ impl<'a> TextToTensors for Cow<'a, str> {
             Cow::Owned(s) => s.to_tensors(to_tensor_info, output_buffers),
         }
     }
+
+    #[inline(always)]
+    fn to_resizable_tensors<T: AsMut<[Vec<u8>]>>(
+        &self,
+        to_tensor_info: &TextToTensorInfo,
+        output_buffers: &mut T,
+    ) -> Result<(), Error> {
+        match self {
+            Cow::Borrowed(s) => (*s).to_resizable_tensors(to_tensor_info, output_buffers),
+            Cow::Owned(s) => s.to_resizable_tensors(to_tensor_info, output_buffers),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_string_tensor() {
+        let info = TextToTensorInfo::StringModel;
+        let expected = [
+            1i32.to_le_bytes().as_slice(),
+            12i32.to_le_bytes().as_slice(),
+            14i32.to_le_bytes().as_slice(),
+            b"hi",
+        ]
+        .concat();
+
+        let mut buffers = vec![Vec::new()];
+        "hi".to_resizable_tensors(&info, &mut buffers).unwrap();
+        assert_eq!(buffers[0], expected);
+
+        // fixed size buffers
+        let mut buffers = [[0u8; 14]];
+        "hi".to_tensors(&info, &mut buffers).unwrap();
+        assert_eq!(buffers[0].as_slice(), expected.as_slice());
+        let mut buffers = [[0u8; 16]];
+        assert!("hi".to_tensors(&info, &mut buffers).is_err());
+    }
 }
@@ -0,0 +1,24 @@
+use super::*;
+
+/// Serialize the string to TfLite string tensor format:
+/// ```[num_strings: i32][offsets: i32 * (num_strings + 1)][string bytes]```
+/// All offsets are the byte offset from the start of the buffer.
+pub(super) fn to_string_tensor(s: &str, output_buffer: &mut Vec<u8>) -> Result<(), Error> {
+    const NUM_STRINGS: usize = 1;
+    let header_size = std::mem::size_of::<i32>() * (NUM_STRINGS + 2);
+    let total_size = header_size + s.len();
+    if total_size > i32::MAX as usize {
+        return Err(Error::ArgumentError(format!(
+            "Input text is too long, bytes size is `{}`",
+            s.len()
+        )));
+    }
+
+    output_buffer.clear();
+    output_buffer.reserve(total_size);
+    output_buffer.extend_from_slice(&(NUM_STRINGS as i32).to_le_bytes());
+    output_buffer.extend_from_slice(&(header_size as i32).to_le_bytes());
+    output_buffer.extend_from_slice(&(total_size as i32).to_le_bytes());
+    output_buffer.extend_from_slice(s.as_bytes());
+    Ok(())
+}
@@ -7,6 +7,10 @@ type FFMpegVideoInput =
     common::ffmpeg_input::FFMpegInput<ffmpeg_next::decoder::Video, ffmpeg_next::frame::Video>;
 
 /// FFMpeg Video Data, which can be used as vision tasks input.
+///
+/// The frames can be sampled by [`FFMpegVideoData::every_nth_frame`], [`FFMpegVideoData::target_fps`],
+/// [`FFMpegVideoData::start_ms`], [`FFMpegVideoData::end_ms`] and [`FFMpegVideoData::max_frames`],
+/// the skipped frames are not converted to tensors, so long videos can be processed faster.
 pub struct FFMpegVideoData {
     source: FFMpegVideoInput,
 
@@ -17,6 +21,11 @@ pub struct FFMpegVideoData {
     // mutable caches
     scales: RefCell<HashMap<ScaleKey, ffmpeg_next::software::scaling::Context>>,
     scale_frame_buffer: RefCell<ffmpeg_next::frame::Video>,
+
+    // frame sampling
+    sampler: FrameSampler,
+    has_seeked: bool,
+    is_finished: bool,
 }
 
 impl FFMpegVideoData {
@@ -24,9 +33,9 @@ impl FFMpegVideoData {
     #[inline(always)]
     pub fn new(input: ffmpeg_next::format::context::Input) -> Result<Self, Error> {
         let source = FFMpegVideoInput::new(input)?;
-        let convert_to_ms = source.decoder.time_base().numerator() as f64
-            / source.decoder.time_base().denominator() as f64
-            * 1000.;
+        // the packets are not rescaled, so the frames' timestamps are in the stream time base
+        let convert_to_ms =
+            source.time_base.numerator() as f64 / source.time_base.denominator() as f64 * 1000.;
         // todo: fix the timebase is 0
         let time_base_num = if source.decoder.time_base().numerator() == 0 {
             1
@@ -49,17 +58,97 @@ impl FFMpegVideoData {
             convert_to_ms,
             scales: RefCell::new(Default::default()),
             scale_frame_buffer: RefCell::new(ffmpeg_next::frame::Video::empty()),
+            sampler: FrameSampler::default(),
+            has_seeked: false,
+            is_finished: false,
         })
     }
+
+    /// Only output every ```n```th frame (after the start timestamp), default is ```1``` (every frame).
+    /// It can be used with [`FFMpegVideoData::target_fps`], the frames are sampled by ```n``` first.
+    #[inline(always)]
+    pub fn every_nth_frame(mut self, n: usize) -> Self {
+        self.sampler.every_nth_frame = n.max(1);
+        self
+    }
+
+    /// Sample the frames to the target frame rate by timestamps, the frames which are closer than ```1000 / fps```
+    /// ms to the last output frame are skipped. It has no effect if the video frame rate is lower than the target.
+    #[inline(always)]
+    pub fn target_fps(mut self, fps: f64) -> Self {
+        self.sampler.target_fps = if fps > 0. && fps.is_finite() {
+            Some(fps)
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Start at the timestamp (ms). The input is seeked to the key frame before the timestamp when getting the
+    /// first frame, and the frames before the timestamp are decoded but skipped.
+    #[inline(always)]
+    pub fn start_ms(mut self, start_ms: u64) -> Self {
+        self.sampler.start_ms = Some(start_ms);
+        self
+    }
+
+    /// End at the timestamp (ms), the frames after the timestamp are not decoded.
+    #[inline(always)]
+    pub fn end_ms(mut self, end_ms: u64) -> Self {
+        self.sampler.end_ms = Some(end_ms);
+        self
+    }
+
+    /// Output at most ```max_frames``` frames.
+    #[inline(always)]
+    pub fn max_frames(mut self, max_frames: usize) -> Self {
+        self.sampler.max_frames = Some(max_frames);
+        self
+    }
+
+    /// The number of frames which have been output.
+    #[inline(always)]
+    pub fn num_output_frames(&self) -> usize {
+        self.sampler.num_output_frames()
+    }
+
+    /// Get the timestamp (ms) of current decoded frame.
+    #[inline(always)]
+    fn frame_timestamp_ms(&self) -> Option<f64> {
+        self.source
+            .frame
+            .timestamp()
+            .map(|t| t as f64 * self.convert_to_ms)
+    }
 }
 
 impl VideoData for FFMpegVideoData {
     type Frame<'frame> = FFMpegFrame<'frame>;
 
-    #[inline(always)]
     fn next_frame(&mut self) -> Result<Option<Self::Frame<'_>>, Error> {
-        if !self.source.receive_frame()? {
-            return Ok(None);
+        if !self.has_seeked {
+            self.has_seeked = true;
+            if let Some(start_ms) = self.sampler.start_ms.filter(|s| *s > 0) {
+                self.source.seek(start_ms)?;
+            }
+        }
+
+        loop {
+            if self.is_finished || self.sampler.is_full() {
+                return Ok(None);
+            }
+            if !self.source.receive_frame()? {
+                self.is_finished = true;
+                return Ok(None);
+            }
+            match self.sampler.sample(self.frame_timestamp_ms()) {
+                Some(true) => break,
+                Some(false) => continue,
+                None => {
+                    self.is_finished = true;
+                    return Ok(None);
+                }
+            }
         }
         Ok(Some(FFMpegFrame(self)))
     }
@@ -181,11 +270,7 @@ impl<'a> ImageToTensor for FFMpegFrame<'a> {
 
     /// return the current timestamp (ms)
     fn timestamp_ms(&self) -> Option<u64> {
-        self.0
-            .source
-            .frame
-            .timestamp()
-            .map(|t| (t as f64 * self.0.convert_to_ms) as u64)
+        self.0.frame_timestamp_ms().map(|t| t as u64)
     }
 }
 
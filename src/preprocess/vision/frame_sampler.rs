@@ -0,0 +1,260 @@
+/// Decide which decoded video frames are output, by the frame sampling options of the video data.
+#[derive(Debug, Clone)]
+pub(crate) struct FrameSampler {
+    // frame sampling options
+    pub every_nth_frame: usize,
+    pub target_fps: Option<f64>,
+    pub start_ms: Option<u64>,
+    pub end_ms: Option<u64>,
+    pub max_frames: Option<usize>,
+
+    // frame sampling states
+    has_started: bool,
+    num_candidate_frames: usize,
+    num_output_frames: usize,
+    next_output_ms: Option<f64>,
+}
+
+impl Default for FrameSampler {
+    fn default() -> Self {
+        Self {
+            every_nth_frame: 1,
+            target_fps: None,
+            start_ms: None,
+            end_ms: None,
+            max_frames: None,
+            has_started: false,
+            num_candidate_frames: 0,
+            num_output_frames: 0,
+            next_output_ms: None,
+        }
+    }
+}
+
+impl FrameSampler {
+    /// The number of frames which have been output.
+    #[inline(always)]
+    pub fn num_output_frames(&self) -> usize {
+        self.num_output_frames
+    }
+
+    /// Whether ```max_frames``` frames have been output.
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.max_frames.is_some_and(|m| self.num_output_frames >= m)
+    }
+
+    /// Whether the frame with the timestamp (ms) should be output.
+    /// Return ```None``` if the end is reached.
+    ///
+    /// The frames without timestamp are skipped until a timestamped frame reaches the start timestamp,
+    /// because the frames before the start cannot be told apart after seeking.
+    pub fn sample(&mut self, timestamp_ms: Option<f64>) -> Option<bool> {
+        if self.is_full() {
+            return None;
+        }
+
+        if !self.has_started {
+            match (self.start_ms.filter(|s| *s > 0), timestamp_ms) {
+                (None, _) => {}
+                (Some(_), None) => return Some(false),
+                (Some(s), Some(t)) if t < s as f64 => return Some(false),
+                (Some(_), Some(_)) => {}
+            }
+            self.has_started = true;
+        }
+        if let Some(t) = timestamp_ms {
+            if self.end_ms.is_some_and(|e| t > e as f64) {
+                return None;
+            }
+        }
+
+        let index = self.num_candidate_frames;
+        self.num_candidate_frames += 1;
+        if index % self.every_nth_frame != 0 {
+            return Some(false);
+        }
+
+        if let (Some(fps), Some(t)) = (self.target_fps, timestamp_ms) {
+            let interval = 1000. / fps;
+            // tolerate the rounding of timestamps
+            const TOLERANCE_MS: f64 = 1.;
+            match self.next_output_ms {
+                Some(next) if t + TOLERANCE_MS < next => return Some(false),
+                // keep the sampling grid, unless the frames are far behind
+                Some(next) if t < next + interval => self.next_output_ms = Some(next + interval),
+                _ => self.next_output_ms = Some(t + interval),
+            }
+        }
+
+        self.num_output_frames += 1;
+        Some(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // run the sampler until the end, return the timestamps of output frames
+    fn sample_all(
+        sampler: &mut FrameSampler,
+        timestamps: impl IntoIterator<Item = Option<f64>>,
+    ) -> Vec<Option<f64>> {
+        let mut outputs = Vec::new();
+        for t in timestamps {
+            match sampler.sample(t) {
+                Some(true) => outputs.push(t),
+                Some(false) => {}
+                None => break,
+            }
+        }
+        outputs
+    }
+
+    // timestamps of a 30 fps video, rounded to ms
+    fn timestamps_30fps(num: usize) -> impl Iterator<Item = Option<f64>> {
+        (0..num).map(|i| Some((i as f64 * 1000. / 30.).round()))
+    }
+
+    #[test]
+    fn test_default() {
+        let mut sampler = FrameSampler::default();
+        let outputs = sample_all(&mut sampler, timestamps_30fps(10));
+        assert_eq!(outputs.len(), 10);
+        assert_eq!(sampler.num_output_frames(), 10);
+
+        let mut sampler = FrameSampler::default();
+        let outputs = sample_all(&mut sampler, [None, None, None]);
+        assert_eq!(outputs, vec![None, None, None]);
+    }
+
+    #[test]
+    fn test_every_nth_frame() {
+        let mut sampler = FrameSampler {
+            every_nth_frame: 3,
+            ..Default::default()
+        };
+        let outputs = sample_all(&mut sampler, timestamps_30fps(10));
+        assert_eq!(outputs, vec![Some(0.), Some(100.), Some(200.), Some(300.)]);
+    }
+
+    #[test]
+    fn test_target_fps() {
+        let mut sampler = FrameSampler {
+            target_fps: Some(10.),
+            ..Default::default()
+        };
+        // the rounded timestamps 33, 67, 100 ... are kept on the 100 ms grid
+        let outputs = sample_all(&mut sampler, timestamps_30fps(10));
+        assert_eq!(outputs, vec![Some(0.), Some(100.), Some(200.), Some(300.)]);
+
+        // tolerate the timestamps a little earlier than the grid
+        let mut sampler = FrameSampler {
+            target_fps: Some(10.),
+            ..Default::default()
+        };
+        let outputs = sample_all(&mut sampler, [0., 50., 99.5, 150., 199., 250.].map(Some));
+        assert_eq!(outputs, vec![Some(0.), Some(99.5), Some(199.)]);
+
+        // restart the grid if the frames are far behind
+        let mut sampler = FrameSampler {
+            target_fps: Some(10.),
+            ..Default::default()
+        };
+        let outputs = sample_all(&mut sampler, [0., 350., 420., 450.].map(Some));
+        assert_eq!(outputs, vec![Some(0.), Some(350.), Some(450.)]);
+
+        // the target is higher than the video frame rate
+        let mut sampler = FrameSampler {
+            target_fps: Some(60.),
+            ..Default::default()
+        };
+        let outputs = sample_all(&mut sampler, timestamps_30fps(10));
+        assert_eq!(outputs.len(), 10);
+
+        // sampled by every nth frame first
+        let mut sampler = FrameSampler {
+            every_nth_frame: 2,
+            target_fps: Some(10.),
+            ..Default::default()
+        };
+        let outputs = sample_all(&mut sampler, timestamps_30fps(10));
+        assert_eq!(outputs, vec![Some(0.), Some(133.), Some(200.)]);
+    }
+
+    #[test]
+    fn test_start_end() {
+        let mut sampler = FrameSampler {
+            start_ms: Some(100),
+            end_ms: Some(200),
+            ..Default::default()
+        };
+        let outputs = sample_all(&mut sampler, timestamps_30fps(10));
+        assert_eq!(
+            outputs,
+            vec![Some(100.), Some(133.), Some(167.), Some(200.)]
+        );
+
+        // every nth frame counts from the start
+        let mut sampler = FrameSampler {
+            start_ms: Some(100),
+            every_nth_frame: 2,
+            ..Default::default()
+        };
+        let outputs = sample_all(&mut sampler, timestamps_30fps(10));
+        assert_eq!(
+            outputs,
+            vec![Some(100.), Some(167.), Some(233.), Some(300.)]
+        );
+    }
+
+    #[test]
+    fn test_start_without_timestamps() {
+        // skip the frames without timestamp until a timestamped frame reaches the start
+        let mut sampler = FrameSampler {
+            start_ms: Some(100),
+            ..Default::default()
+        };
+        let outputs = sample_all(
+            &mut sampler,
+            [None, Some(67.), None, Some(100.), None, Some(133.)],
+        );
+        assert_eq!(outputs, vec![Some(100.), None, Some(133.)]);
+
+        let mut sampler = FrameSampler {
+            start_ms: Some(100),
+            ..Default::default()
+        };
+        let outputs = sample_all(&mut sampler, [None, None, None]);
+        assert!(outputs.is_empty());
+
+        // start at 0 does not need timestamps
+        let mut sampler = FrameSampler {
+            start_ms: Some(0),
+            ..Default::default()
+        };
+        let outputs = sample_all(&mut sampler, [None, None]);
+        assert_eq!(outputs, vec![None, None]);
+    }
+
+    #[test]
+    fn test_max_frames() {
+        let mut sampler = FrameSampler {
+            max_frames: Some(3),
+            every_nth_frame: 2,
+            ..Default::default()
+        };
+        let outputs = sample_all(&mut sampler, timestamps_30fps(10));
+        assert_eq!(outputs, vec![Some(0.), Some(67.), Some(133.)]);
+        assert!(sampler.is_full());
+        assert_eq!(sampler.sample(Some(200.)), None);
+
+        let mut sampler = FrameSampler {
+            max_frames: Some(0),
+            ..Default::default()
+        };
+        assert!(sampler.is_full());
+        assert_eq!(sampler.sample(Some(0.)), None);
+    }
+}
@@ -13,8 +13,8 @@ macro_rules! get_rgb_mean_std_from_info {
         let r_std = $info.normalization_options.1.get(0).unwrap();
         let g_mean = $info.normalization_options.0.get(1).unwrap_or(r_mean);
         let g_std = $info.normalization_options.1.get(1).unwrap_or(r_std);
-        let b_mean = $info.normalization_options.0.get(1).unwrap_or(r_mean);
-        let b_std = $info.normalization_options.1.get(1).unwrap_or(r_std);
+        let b_mean = $info.normalization_options.0.get(2).unwrap_or(r_mean);
+        let b_std = $info.normalization_options.1.get(2).unwrap_or(r_std);
         (r_mean, r_std, g_mean, g_std, b_mean, b_std)
     }};
 }
@@ -140,7 +140,6 @@ where
             let bytes = img.as_bytes();
             debug_assert_eq!(res.len(), bytes.len() * std::mem::size_of::<f32>());
 
-            let hw = (img.width() * img.height()) as usize;
             return match data_layout {
                 ImageDataLayout::NHWC => {
                     let mut i = 0;
@@ -159,10 +158,12 @@ where
                     Ok(())
                 }
                 ImageDataLayout::NCHW | ImageDataLayout::CHWN => {
-                    for start in 0..3 {
-                        let mut i = start as usize;
-                        while i < hw {
-                            let f = ((bytes[i] as f32) - r_mean) / r_std;
+                    for (start, mean, std) in
+                        [(0, r_mean, r_std), (1, g_mean, g_std), (2, b_mean, b_std)]
+                    {
+                        let mut i = start;
+                        while i < bytes.len() {
+                            let f = ((bytes[i] as f32) - mean) / std;
                             res[res_index..res_index + 4].copy_from_slice(&f.to_ne_bytes());
                             res_index += 4;
                             i += 3;
@@ -183,13 +184,12 @@ where
                 }
                 // batch is always 1 now
                 ImageDataLayout::NCHW | ImageDataLayout::CHWN => {
-                    let hw = (img.width() * img.height()) as usize;
                     for c in 0..3 {
-                        let mut i = c as usize;
-                        while i < hw {
+                        let mut i = c;
+                        while i < bytes.len() {
                             res[res_index] = bytes[i];
                             res_index += 1;
-                            i += c;
+                            i += 3;
                         }
                     }
                     Ok(())
@@ -275,3 +275,37 @@ mod ops_inner {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_nchw_to_tensor() {
+        // 2x1 image: (10, 20, 30), (40, 50, 60)
+        let img =
+            ImageBuffer::<Rgb<u8>, Vec<u8>>::from_raw(2, 1, vec![10, 20, 30, 40, 50, 60]).unwrap();
+        let mut info = ImageToTensorInfo {
+            image_data_layout: ImageDataLayout::NCHW,
+            color_space: ImageColorSpaceType::RGB,
+            tensor_type: TensorType::U8,
+            tensor_shape: ImageLikeTensorShape::parse(ImageDataLayout::NCHW, &[1, 3, 1, 2])
+                .unwrap(),
+            stats_min: vec![],
+            stats_max: vec![],
+            normalization_options: (vec![0., 10., 20.], vec![1., 2., 4.]),
+        };
+        let mut buf = vec![0u8; 6];
+        rgb8_image_buffer_to_tensor(&img, &info, &mut buf).unwrap();
+        assert_eq!(buf, vec![10, 40, 20, 50, 30, 60]);
+
+        info.tensor_type = TensorType::F32;
+        let mut buf = vec![0u8; 24];
+        rgb8_image_buffer_to_tensor(&img, &info, &mut buf).unwrap();
+        let res: Vec<f32> = buf
+            .chunks(4)
+            .map(|c| f32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        assert_eq!(res, vec![10., 40., 5., 20., 2.5, 10.]);
+    }
+}
@@ -0,0 +1,314 @@
+use super::*;
+use ::image::DynamicImage;
+use std::path::{Path, PathBuf};
+
+enum FrameSource {
+    Paths(std::vec::IntoIter<PathBuf>),
+    Images(std::vec::IntoIter<DynamicImage>),
+    #[cfg(feature = "animation")]
+    Animation(::image::Frames<'static>),
+}
+
+enum Timestamps {
+    FrameRate(f64),
+    List(Vec<u64>),
+    /// Use the delays of animation frames.
+    #[cfg(feature = "animation")]
+    FrameDelays,
+}
+
+/// Image sequence video data, which can be used as vision tasks input without FFMpeg.
+///
+/// The frames can be image files (such as all images in a directory), in-memory images,
+/// or the frames of animated GIF and APNG (feature ```animation```).
+/// The timestamps are derived from the frame rate (default is 30 fps), or provided by caller,
+/// and the animation frames use their delays by default.
+///
+/// The image files are decoded by the ```image``` crate, so the image formats must be enabled,
+/// e.g. the ```jpeg``` and ```png``` features of ```image```.
+pub struct ImageSequenceVideoData {
+    source: FrameSource,
+    timestamps: Timestamps,
+    frame: Option<DynamicImage>,
+    frame_index: usize,
+    // the timestamp of next animation frame
+    delay_timestamp_ms: f64,
+}
+
+impl ImageSequenceVideoData {
+    const DEFAULT_FRAME_RATE: f64 = 30.;
+
+    #[inline(always)]
+    fn new(source: FrameSource, timestamps: Timestamps) -> Self {
+        Self {
+            source,
+            timestamps,
+            frame: None,
+            frame_index: 0,
+            delay_timestamp_ms: 0.,
+        }
+    }
+
+    /// Create a new instance from in-memory images.
+    #[inline(always)]
+    pub fn from_images(images: impl IntoIterator<Item = DynamicImage>) -> Self {
+        Self::new(
+            FrameSource::Images(images.into_iter().collect::<Vec<_>>().into_iter()),
+            Timestamps::FrameRate(Self::DEFAULT_FRAME_RATE),
+        )
+    }
+
+    /// Create a new instance from image files, the files are decoded when getting frames.
+    #[inline(always)]
+    pub fn from_paths<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> Self {
+        Self::new(
+            FrameSource::Paths(
+                paths
+                    .into_iter()
+                    .map(|p| p.as_ref().to_path_buf())
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ),
+            Timestamps::FrameRate(Self::DEFAULT_FRAME_RATE),
+        )
+    }
+
+    /// Create a new instance from the image files in the directory, sorted by file name.
+    /// The files whose extension is not an image format are skipped.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && ::image::ImageFormat::from_path(&path).is_ok() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        Ok(Self::from_paths(paths))
+    }
+
+    /// Create a new instance from the frames of animated GIF.
+    #[cfg(feature = "animation")]
+    #[inline(always)]
+    pub fn from_gif(
+        reader: impl std::io::BufRead + std::io::Seek + 'static,
+    ) -> Result<Self, Error> {
+        use ::image::AnimationDecoder;
+        let frames = ::image::codecs::gif::GifDecoder::new(reader)?.into_frames();
+        Ok(Self::new(
+            FrameSource::Animation(frames),
+            Timestamps::FrameDelays,
+        ))
+    }
+
+    /// Create a new instance from the frames of APNG. The PNG without animation has one frame.
+    #[cfg(feature = "animation")]
+    pub fn from_apng(
+        reader: impl std::io::BufRead + std::io::Seek + 'static,
+    ) -> Result<Self, Error> {
+        use ::image::AnimationDecoder;
+        let decoder = ::image::codecs::png::PngDecoder::new(reader)?;
+        if !decoder.is_apng()? {
+            return Ok(Self::new(
+                FrameSource::Images(vec![DynamicImage::from_decoder(decoder)?].into_iter()),
+                Timestamps::FrameDelays,
+            ));
+        }
+        Ok(Self::new(
+            FrameSource::Animation(decoder.apng()?.into_frames()),
+            Timestamps::FrameDelays,
+        ))
+    }
+
+    /// Open the animated GIF or APNG file by the file extension.
+    #[cfg(feature = "animation")]
+    pub fn open_animation(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+        match ::image::ImageFormat::from_path(path)? {
+            ::image::ImageFormat::Gif => Self::from_gif(reader),
+            ::image::ImageFormat::Png => Self::from_apng(reader),
+            f => Err(Error::ArgumentError(format!(
+                "Unsupported animation format `{:?}`",
+                f
+            ))),
+        }
+    }
+
+    /// Set the frame rate to derive the timestamps: ```timestamp_ms = frame_index * 1000 / frame_rate```.
+    #[inline(always)]
+    pub fn frame_rate(mut self, frame_rate: f64) -> Self {
+        self.timestamps = Timestamps::FrameRate(frame_rate);
+        self
+    }
+
+    /// Set the timestamps (ms) of frames, the number of timestamps must not be less than the number of frames.
+    #[inline(always)]
+    pub fn timestamps_ms(mut self, timestamps_ms: Vec<u64>) -> Self {
+        self.timestamps = Timestamps::List(timestamps_ms);
+        self
+    }
+}
+
+impl VideoData for ImageSequenceVideoData {
+    type Frame<'frame> = ImageSequenceFrame<'frame>;
+
+    fn next_frame(&mut self) -> Result<Option<Self::Frame<'_>>, Error> {
+        let (image, delay_ms) = match self.source {
+            FrameSource::Paths(ref mut paths) => match paths.next() {
+                Some(path) => (::image::open(path)?, None),
+                None => return Ok(None),
+            },
+            FrameSource::Images(ref mut images) => match images.next() {
+                Some(image) => (image, None),
+                None => return Ok(None),
+            },
+            #[cfg(feature = "animation")]
+            FrameSource::Animation(ref mut frames) => match frames.next() {
+                Some(frame) => {
+                    let frame = frame?;
+                    let (numer, denom) = frame.delay().numer_denom_ms();
+                    (
+                        DynamicImage::ImageRgba8(frame.into_buffer()),
+                        Some(numer as f64 / denom.max(1) as f64),
+                    )
+                }
+                None => return Ok(None),
+            },
+        };
+
+        let timestamp_ms = match self.timestamps {
+            Timestamps::FrameRate(frame_rate) => {
+                if frame_rate.is_nan() || frame_rate <= 0. || frame_rate.is_infinite() {
+                    return Err(Error::ArgumentError(format!(
+                        "Invalid frame rate `{}`",
+                        frame_rate
+                    )));
+                }
+                (self.frame_index as f64 * 1000. / frame_rate).round() as u64
+            }
+            Timestamps::List(ref timestamps) => {
+                *timestamps.get(self.frame_index).ok_or_else(|| {
+                    Error::ArgumentError(format!(
+                        "No timestamp for frame `{}`, only `{}` timestamps are provided",
+                        self.frame_index,
+                        timestamps.len()
+                    ))
+                })?
+            }
+            #[cfg(feature = "animation")]
+            Timestamps::FrameDelays => self.delay_timestamp_ms.round() as u64,
+        };
+        self.delay_timestamp_ms += delay_ms.unwrap_or(0.);
+        self.frame_index += 1;
+        Ok(Some(ImageSequenceFrame {
+            image: self.frame.insert(image),
+            timestamp_ms,
+        }))
+    }
+}
+
+/// A frame of [`ImageSequenceVideoData`].
+pub struct ImageSequenceFrame<'a> {
+    image: &'a DynamicImage,
+    timestamp_ms: u64,
+}
+
+impl<'a> ImageSequenceFrame<'a> {
+    /// Get the frame image.
+    #[inline(always)]
+    pub fn image(&self) -> &DynamicImage {
+        self.image
+    }
+}
+
+impl<'a> ImageToTensor for ImageSequenceFrame<'a> {
+    #[inline(always)]
+    fn to_tensor<T: AsMut<[u8]>>(
+        &self,
+        to_tensor_info: &ImageToTensorInfo,
+        process_options: &ImageProcessingOptions,
+        output_buffers: &mut T,
+    ) -> Result<(), Error> {
+        self.image
+            .to_tensor(to_tensor_info, process_options, output_buffers)
+    }
+
+    /// return image size: (weight, height)
+    #[inline(always)]
+    fn image_size(&self) -> (u32, u32) {
+        self.image.image_size()
+    }
+
+    /// return the current timestamp (ms)
+    #[inline(always)]
+    fn timestamp_ms(&self) -> Option<u64> {
+        Some(self.timestamp_ms)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn collect(mut video: ImageSequenceVideoData) -> Result<Vec<(u32, u64)>, Error> {
+        let mut frames = Vec::new();
+        while let Some(frame) = video.next_frame()? {
+            frames.push((frame.image_size().0, frame.timestamp_ms().unwrap()));
+        }
+        Ok(frames)
+    }
+
+    #[test]
+    fn test_image_sequence() {
+        let images = || (1..4).map(|w| DynamicImage::new_rgb8(w, 2));
+        assert_eq!(
+            collect(ImageSequenceVideoData::from_images(images())).unwrap(),
+            vec![(1, 0), (2, 33), (3, 67)]
+        );
+        assert_eq!(
+            collect(ImageSequenceVideoData::from_images(images()).frame_rate(10.)).unwrap(),
+            vec![(1, 0), (2, 100), (3, 200)]
+        );
+        let video = ImageSequenceVideoData::from_images(images()).timestamps_ms(vec![5, 7, 9]);
+        assert_eq!(collect(video).unwrap(), vec![(1, 5), (2, 7), (3, 9)]);
+        let video = ImageSequenceVideoData::from_images(images()).timestamps_ms(vec![5, 7]);
+        assert!(collect(video).is_err());
+        assert!(collect(ImageSequenceVideoData::from_images(images()).frame_rate(0.)).is_err());
+
+        // directory
+        let dir = std::env::temp_dir().join(format!("mediapipe-rs-seq-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        DynamicImage::new_rgb8(4, 2)
+            .save(dir.join("b.jpg"))
+            .unwrap();
+        DynamicImage::new_rgb8(3, 2)
+            .save(dir.join("a.jpg"))
+            .unwrap();
+        std::fs::write(dir.join("c.txt"), b"").unwrap();
+        let frames = collect(ImageSequenceVideoData::from_dir(&dir).unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(frames.unwrap(), vec![(3, 0), (4, 33)]);
+    }
+
+    #[cfg(feature = "animation")]
+    #[test]
+    fn test_animation() {
+        use ::image::{codecs::gif::GifEncoder, Delay, Frame, RgbaImage};
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut bytes);
+            for delay in [40, 60, 100] {
+                let frame = Frame::from_parts(
+                    RgbaImage::new(5, 3),
+                    0,
+                    0,
+                    Delay::from_numer_denom_ms(delay, 1),
+                );
+                encoder.encode_frame(frame).unwrap();
+            }
+        }
+        let video = ImageSequenceVideoData::from_gif(std::io::Cursor::new(bytes)).unwrap();
+        assert_eq!(collect(video).unwrap(), vec![(5, 0), (5, 40), (5, 100)]);
+    }
+}
@@ -1,9 +1,15 @@
 mod image;
+mod image_sequence;
+pub use image_sequence::{ImageSequenceFrame, ImageSequenceVideoData};
 
 #[cfg(feature = "ffmpeg")]
 mod ffmpeg;
+#[cfg(any(feature = "ffmpeg", test))]
+mod frame_sampler;
 #[cfg(feature = "ffmpeg")]
 pub use ffmpeg::FFMpegVideoData;
+#[cfg(feature = "ffmpeg")]
+use frame_sampler::FrameSampler;
 
 use super::*;
 use crate::tasks::vision::ImageProcessingOptions;
@@ -31,7 +37,7 @@ pub trait ImageToTensor {
 }
 
 /// Used for video data. Every video data implement the [`VideoData`] can be used as vision tasks input.
-/// Now builtin impl: [`FFMpegVideoData`].
+/// Now builtin impl: [`ImageSequenceVideoData`], [`FFMpegVideoData`].
 ///
 /// Now rust stable cannot use [Generic Associated Types](https://rust-lang.github.io/rfcs/1598-generic_associated_types.html)
 pub trait VideoData {
@@ -50,6 +56,23 @@ pub enum ImageDataLayout {
     CHWN,
 }
 
+impl ImageDataLayout {
+    /// Detect the data layout of a 4-D image tensor shape by the position of the channels dimension.
+    #[inline]
+    pub(crate) fn detect(shape: &[usize]) -> Option<Self> {
+        if shape.len() != 4 {
+            return None;
+        }
+        if shape[1] == 3 || shape[1] == 1 {
+            Some(ImageDataLayout::NCHW)
+        } else if shape[3] == 3 || shape[3] == 1 {
+            Some(ImageDataLayout::NHWC)
+        } else {
+            None
+        }
+    }
+}
+
 /// Image Color Type.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum ImageColorSpaceType {
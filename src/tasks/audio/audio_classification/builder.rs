@@ -33,6 +33,8 @@ impl AudioClassifierBuilder {
 
     base_task_options_impl!();
 
+    model_metadata_override_impl!();
+
     classification_options_impl!();
 
     /// Use the build options to create a new task instance.
@@ -41,11 +43,15 @@ impl AudioClassifierBuilder {
         classification_options_check!(self, classification_options);
         let buf = base_task_options_check_and_get_buf!(self);
 
-        // change the lifetime to 'static, because the buf will move to graph and will not be released.
+        // change the lifetime to 'static, because the graph keeps the buf until the task is dropped.
         let model_resource_ref = crate::model::parse_model(buf.as_ref())?;
         let model_resource = unsafe {
             std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
         };
+        let model_resource = self
+            .base_task_options
+            .model_metadata_override
+            .apply(model_resource)?;
 
         // check model
         model_base_check_impl!(model_resource, 1, 1);
@@ -53,11 +59,9 @@ impl AudioClassifierBuilder {
         let input_tensor_type =
             model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.execution_target,
-        )
-        .build_from_shared_slices([buf])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.model_backend(), buf)?;
 
         return Ok(AudioClassifier {
             build_options: self,
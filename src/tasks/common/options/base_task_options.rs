@@ -7,6 +7,12 @@ pub(crate) struct BaseTaskOptions {
 
     /// The device to run the models.
     pub execution_target: crate::Device,
+
+    /// The inference backend to run the models.
+    pub inference_backend: std::sync::Arc<dyn crate::backend::InferenceBackend>,
+
+    /// The model metadata supplied by users, which overrides the model metadata.
+    pub model_metadata_override: crate::model::ModelMetadataOverride,
 }
 
 impl Default for BaseTaskOptions {
@@ -15,10 +21,29 @@ impl Default for BaseTaskOptions {
             model_asset_buffer: None,
             model_asset_path: None,
             execution_target: crate::Device::CPU,
+            inference_backend: crate::backend::default_backend(),
+            model_metadata_override: Default::default(),
         }
     }
 }
 
+impl BaseTaskOptions {
+    /// Use the inference backend to build a graph from the model buffer.
+    #[inline(always)]
+    pub fn build_graph(
+        &self,
+        encoding: crate::GraphEncoding,
+        model: crate::SharedSlice<u8>,
+    ) -> Result<crate::Graph, crate::Error> {
+        crate::Graph::new(
+            self.inference_backend.as_ref(),
+            encoding,
+            self.execution_target,
+            model,
+        )
+    }
+}
+
 macro_rules! base_task_options_impl {
     () => {
         /// Set model asset data use [`crate::SharedSlice`]
@@ -70,6 +95,69 @@ macro_rules! base_task_options_impl {
             self.base_task_options.execution_target = crate::Device::TPU;
             self
         }
+
+        /// Set the inference backend to run the models. Default is [`crate::backend::default_backend`].
+        #[inline(always)]
+        pub fn inference_backend(
+            mut self,
+            inference_backend: std::sync::Arc<dyn crate::backend::InferenceBackend>,
+        ) -> Self {
+            self.base_task_options.inference_backend = inference_backend;
+            self
+        }
+    };
+}
+
+macro_rules! model_metadata_override_impl {
+    () => {
+        /// Set the image normalization options `(input - mean) / std` of the model input,
+        /// which overrides the model metadata. The length can be 1 or the number of channels.
+        #[cfg(feature = "vision")]
+        #[inline(always)]
+        pub fn image_normalization(mut self, mean: Vec<f32>, std: Vec<f32>) -> Self {
+            self.base_task_options
+                .model_metadata_override
+                .image_normalization = Some((mean, std));
+            self
+        }
+
+        /// Set the image data layout of the model input, which overrides the model metadata.
+        #[cfg(feature = "vision")]
+        #[inline(always)]
+        pub fn image_layout(
+            mut self,
+            image_layout: crate::preprocess::vision::ImageDataLayout,
+        ) -> Self {
+            self.base_task_options.model_metadata_override.image_layout = Some(image_layout);
+            self
+        }
+
+        /// Set the audio sample rate and number of channels of the model input, which overrides the model metadata.
+        #[cfg(feature = "audio")]
+        #[inline(always)]
+        pub fn audio_properties(mut self, sample_rate: usize, num_channels: usize) -> Self {
+            self.base_task_options
+                .model_metadata_override
+                .audio_properties = Some((sample_rate, num_channels));
+            self
+        }
+
+        /// Set the labels of the model output, which override the labels and display names in model metadata.
+        #[inline(always)]
+        pub fn labels(mut self, labels: Vec<String>) -> Self {
+            self.base_task_options.model_metadata_override.labels =
+                Some(crate::model::LabelsOverride::Labels(labels));
+            self
+        }
+
+        /// Set the labels file (one label per line) of the model output,
+        /// which overrides the labels and display names in model metadata.
+        #[inline(always)]
+        pub fn labels_from_file(mut self, labels_file: impl Into<std::path::PathBuf>) -> Self {
+            self.base_task_options.model_metadata_override.labels =
+                Some(crate::model::LabelsOverride::File(labels_file.into()));
+            self
+        }
     };
 }
 
@@ -13,8 +13,14 @@ mod embedding_options;
 #[cfg(feature = "vision")]
 mod hand_landmark_options;
 
+#[macro_use]
+#[cfg(feature = "vision")]
+mod ssd_anchors_options;
+
 pub(crate) use base_task_options::BaseTaskOptions;
 pub(crate) use classification_options::ClassificationOptions;
 pub(crate) use embedding_options::EmbeddingOptions;
 #[cfg(feature = "vision")]
 pub(crate) use hand_landmark_options::HandLandmarkOptions;
+#[cfg(feature = "vision")]
+pub(crate) use ssd_anchors_options::SsdAnchorsOptions;
@@ -0,0 +1,240 @@
+use crate::model::ModelResourceTrait;
+use crate::postprocess::{Anchor, SsdAnchorsBuilder, SsdDecodingOptions};
+use crate::Error;
+
+/// Options to generate anchors for SSD detection models, which override the model metadata and task defaults.
+/// `None` means using the task default value.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SsdAnchorsOptions {
+    /// Number of output feature maps to generate the anchors on.
+    pub num_layers: Option<usize>,
+    /// Min and max scales for generating anchor boxes on feature maps.
+    pub min_scale: Option<f32>,
+    pub max_scale: Option<f32>,
+    /// The offset for the center of anchors, in the scale of stride.
+    pub anchor_offset: Option<(f32, f32)>,
+    /// Strides of each output feature maps.
+    pub strides: Option<Vec<i32>>,
+    /// List of different aspect ratio to generate anchors.
+    pub aspect_ratios: Option<Vec<f32>>,
+    /// The aspect ratio of the additional interpolated anchor, `0` means no additional anchor.
+    pub interpolated_scale_aspect_ratio: Option<f32>,
+    /// Whether use fixed width and height (1.0) for each anchor.
+    pub fixed_anchor_size: Option<bool>,
+    /// Whether the fixed 3 boxes per location is used in the lowest layer.
+    pub reduce_boxes_in_lowest_layer: Option<bool>,
+}
+
+impl SsdAnchorsOptions {
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        self.num_layers.is_none()
+            && self.min_scale.is_none()
+            && self.max_scale.is_none()
+            && self.anchor_offset.is_none()
+            && self.strides.is_none()
+            && self.aspect_ratios.is_none()
+            && self.interpolated_scale_aspect_ratio.is_none()
+            && self.fixed_anchor_size.is_none()
+            && self.reduce_boxes_in_lowest_layer.is_none()
+    }
+
+    /// Apply the options to the task default anchors builder.
+    fn apply(&self, mut builder: SsdAnchorsBuilder) -> SsdAnchorsBuilder {
+        if let Some(ref strides) = self.strides {
+            builder = builder
+                .num_layers(self.num_layers.unwrap_or(strides.len()))
+                .strides(strides.clone());
+        } else if let Some(num_layers) = self.num_layers {
+            builder = builder.num_layers(num_layers);
+        }
+        if let Some(min_scale) = self.min_scale {
+            builder = builder.min_scale(min_scale);
+        }
+        if let Some(max_scale) = self.max_scale {
+            builder = builder.max_scale(max_scale);
+        }
+        if let Some((x, y)) = self.anchor_offset {
+            builder = builder.anchor_offset_x(x).anchor_offset_y(y);
+        }
+        if let Some(ref aspect_ratios) = self.aspect_ratios {
+            builder = builder.aspect_ratios(aspect_ratios.clone());
+        }
+        if let Some(r) = self.interpolated_scale_aspect_ratio {
+            builder = builder.interpolated_scale_aspect_ratio(r);
+        }
+        if let Some(f) = self.fixed_anchor_size {
+            builder = builder.fixed_anchor_size(f);
+        }
+        if let Some(r) = self.reduce_boxes_in_lowest_layer {
+            builder = builder.reduce_boxes_in_lowest_layer(r);
+        }
+        builder
+    }
+
+    /// Get the anchors and decoding options for the SSD model.
+    /// The priority is: builder options > model metadata > task defaults.
+    /// The number of boxes, coords and classes are checked with the model output shapes.
+    pub(crate) fn anchors_and_decoding_options(
+        &self,
+        model_resource: &dyn ModelResourceTrait,
+        location_buf_index: usize,
+        score_buf_index: usize,
+        default_anchors: SsdAnchorsBuilder,
+        default_decoding_options: SsdDecodingOptions,
+    ) -> Result<(Vec<Anchor>, SsdDecodingOptions), Error> {
+        let location_shape = model_resource_check_and_get_impl!(
+            model_resource,
+            output_tensor_shape,
+            location_buf_index
+        );
+        let score_shape = model_resource_check_and_get_impl!(
+            model_resource,
+            output_tensor_shape,
+            score_buf_index
+        );
+        let num_coords = location_shape.last().cloned().unwrap_or(0);
+        let num_boxes = location_shape
+            .iter()
+            .product::<usize>()
+            .checked_div(num_coords)
+            .unwrap_or(0);
+        if num_boxes == 0 {
+            return Err(Error::ModelInconsistentError(format!(
+                "Invalid SSD location tensor shape `{:?}`",
+                location_shape
+            )));
+        }
+        let num_classes = score_shape.iter().product::<usize>() / num_boxes;
+
+        let metadata = model_resource.ssd_detection_metadata();
+        let mut decoding_options = match metadata.and_then(|m| m.decoding_options.as_ref()) {
+            Some(d) => {
+                let mut d = d.clone();
+                // missing scales in metadata
+                if d.x_scale == 0. || d.y_scale == 0. || d.w_scale == 0. || d.h_scale == 0. {
+                    d.x_scale = default_decoding_options.x_scale;
+                    d.y_scale = default_decoding_options.y_scale;
+                    d.w_scale = default_decoding_options.w_scale;
+                    d.h_scale = default_decoding_options.h_scale;
+                }
+                d
+            }
+            None => default_decoding_options,
+        };
+        if decoding_options.num_boxes != 0 && decoding_options.num_boxes != num_boxes {
+            return Err(Error::ModelInconsistentError(format!(
+                "Expect `{}` boxes, but the model outputs `{}` boxes",
+                decoding_options.num_boxes, num_boxes
+            )));
+        }
+        decoding_options.num_boxes = num_boxes;
+        if decoding_options.num_coords != num_coords {
+            return Err(Error::ModelInconsistentError(format!(
+                "Expect `{}` values per box, but the model outputs `{}` values per box",
+                decoding_options.num_coords, num_coords
+            )));
+        }
+        if decoding_options.num_classes != num_classes {
+            return Err(Error::ModelInconsistentError(format!(
+                "Expect `{}` classes, but the model outputs `{}` classes",
+                decoding_options.num_classes, num_classes
+            )));
+        }
+        if !decoding_options.is_valid() {
+            return Err(Error::ModelInconsistentError(format!(
+                "Invalid SSD decoding options: `{:?}`",
+                decoding_options
+            )));
+        }
+
+        let anchors = match metadata {
+            Some(m) if self.is_empty() && !m.anchors.is_empty() => m.anchors.clone(),
+            _ => {
+                let builder = self.apply(default_anchors);
+                builder.check()?;
+                builder.generate()
+            }
+        };
+        if anchors.len() != num_boxes {
+            return Err(Error::ModelInconsistentError(format!(
+                "The number of anchors `{}` is not equal to the number of boxes `{}`, please check the anchors options",
+                anchors.len(),
+                num_boxes
+            )));
+        }
+        Ok((anchors, decoding_options))
+    }
+}
+
+macro_rules! ssd_anchors_options_impl {
+    () => {
+        /// Set the number of output feature maps to generate the anchors on.
+        /// Default is the number of strides.
+        #[inline(always)]
+        pub fn anchors_num_layers(mut self, num_layers: usize) -> Self {
+            self.ssd_anchors_options.num_layers = Some(num_layers);
+            self
+        }
+
+        /// Set the min and max scales for generating anchor boxes on feature maps.
+        #[inline(always)]
+        pub fn anchors_scale_range(mut self, min_scale: f32, max_scale: f32) -> Self {
+            self.ssd_anchors_options.min_scale = Some(min_scale);
+            self.ssd_anchors_options.max_scale = Some(max_scale);
+            self
+        }
+
+        /// Set the offset for the center of anchors. The value is in the scale of stride.
+        /// E.g. 0.5 meaning 0.5 * |current_stride| in pixels.
+        #[inline(always)]
+        pub fn anchors_offset(mut self, anchor_offset_x: f32, anchor_offset_y: f32) -> Self {
+            self.ssd_anchors_options.anchor_offset = Some((anchor_offset_x, anchor_offset_y));
+            self
+        }
+
+        /// Set the strides of each output feature maps.
+        #[inline(always)]
+        pub fn anchors_strides(mut self, strides: Vec<i32>) -> Self {
+            self.ssd_anchors_options.strides = Some(strides);
+            self
+        }
+
+        /// Set the list of different aspect ratio to generate anchors.
+        #[inline(always)]
+        pub fn anchors_aspect_ratios(mut self, aspect_ratios: Vec<f32>) -> Self {
+            self.ssd_anchors_options.aspect_ratios = Some(aspect_ratios);
+            self
+        }
+
+        /// Set the aspect ratio of an additional anchor, which has a scale interpolated between the scale for a
+        /// layer and the scale for the next layer. This anchor is not included if this value is 0.
+        #[inline(always)]
+        pub fn anchors_interpolated_scale_aspect_ratio(
+            mut self,
+            interpolated_scale_aspect_ratio: f32,
+        ) -> Self {
+            self.ssd_anchors_options.interpolated_scale_aspect_ratio =
+                Some(interpolated_scale_aspect_ratio);
+            self
+        }
+
+        /// Set whether use fixed width and height (1.0) for each anchor.
+        #[inline(always)]
+        pub fn anchors_fixed_size(mut self, fixed_anchor_size: bool) -> Self {
+            self.ssd_anchors_options.fixed_anchor_size = Some(fixed_anchor_size);
+            self
+        }
+
+        /// Set whether the fixed 3 boxes per location is used in the lowest layer.
+        #[inline(always)]
+        pub fn anchors_reduce_boxes_in_lowest_layer(
+            mut self,
+            reduce_boxes_in_lowest_layer: bool,
+        ) -> Self {
+            self.ssd_anchors_options.reduce_boxes_in_lowest_layer =
+                Some(reduce_boxes_in_lowest_layer);
+            self
+        }
+    };
+}
@@ -0,0 +1,67 @@
+use super::CustomModel;
+use crate::model::ModelResourceTrait;
+use crate::tasks::common::BaseTaskOptions;
+
+/// Configure the build options of a new **Custom Model** task instance.
+///
+/// Methods can be chained on it in order to configure it.
+pub struct CustomModelBuilder {
+    pub(super) base_task_options: BaseTaskOptions,
+}
+
+impl Default for CustomModelBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            base_task_options: Default::default(),
+        }
+    }
+}
+
+impl CustomModelBuilder {
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    base_task_options_impl!();
+
+    model_metadata_override_impl!();
+
+    /// Use the build options to create a new task instance.
+    #[inline]
+    pub fn finalize(mut self) -> Result<CustomModel, crate::Error> {
+        let buf = base_task_options_check_and_get_buf!(self);
+
+        // change the lifetime to 'static, because the graph keeps the buf until the task is dropped.
+        let model_resource_ref = crate::model::parse_model(buf.as_ref())?;
+        let model_resource = unsafe {
+            std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
+        };
+        let model_resource = self
+            .base_task_options
+            .model_metadata_override
+            .apply(model_resource)?;
+
+        // check model: any number of inputs and outputs is accepted, but all of them must have information
+        for i in 0..model_resource.input_tensor_count() {
+            model_resource_check_and_get_impl!(model_resource, input_tensor_type, i);
+            model_resource_check_and_get_impl!(model_resource, input_tensor_shape, i);
+        }
+        for i in 0..model_resource.output_tensor_count() {
+            model_resource_check_and_get_impl!(model_resource, output_tensor_type, i);
+            model_resource_check_and_get_impl!(model_resource, output_tensor_shape, i);
+        }
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.model_backend(), buf)?;
+
+        return Ok(CustomModel {
+            build_options: self,
+            model_resource,
+            graph,
+        });
+    }
+}
@@ -0,0 +1,510 @@
+mod builder;
+pub use builder::CustomModelBuilder;
+
+use crate::model::ModelResourceTrait;
+#[cfg(feature = "audio")]
+use crate::postprocess::AudioResultsIter;
+#[cfg(feature = "vision")]
+use crate::postprocess::VideoResultsIter;
+use crate::postprocess::{OutputTensor, OutputTensors, QuantizationParameters};
+#[cfg(feature = "audio")]
+use crate::preprocess::audio::{AudioData, AudioDataToTensorIter};
+#[cfg(feature = "text")]
+use crate::preprocess::text::TextToTensors;
+#[cfg(feature = "vision")]
+use crate::preprocess::vision::{ImageToTensor, VideoData};
+#[cfg(feature = "vision")]
+use crate::tasks::vision::ImageProcessingOptions;
+use crate::{Error, Graph, GraphExecutionContext, TensorType};
+
+/// User-defined post-processing, which converts the model output tensors to task results.
+///
+/// ```rust
+/// use mediapipe_rs::postprocess::OutputTensors;
+/// use mediapipe_rs::tasks::custom::PostProcess;
+///
+/// struct ArgMax;
+///
+/// impl PostProcess for ArgMax {
+///     type Result = usize;
+///
+///     fn process(&mut self, outputs: &OutputTensors) -> Result<usize, mediapipe_rs::Error> {
+///         let scores = outputs.get(0).unwrap().data.as_f32().unwrap();
+///         Ok((0..scores.len()).max_by(|a, b| scores[*a].total_cmp(&scores[*b])).unwrap())
+///     }
+/// }
+/// ```
+pub trait PostProcess {
+    type Result;
+
+    /// Convert the output tensors of one inference to the result.
+    fn process(&mut self, outputs: &OutputTensors) -> Result<Self::Result, Error>;
+}
+
+/// The default post-processing, which returns the output tensors.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RawOutputs;
+
+impl PostProcess for RawOutputs {
+    type Result = OutputTensors;
+
+    #[inline(always)]
+    fn process(&mut self, outputs: &OutputTensors) -> Result<Self::Result, Error> {
+        Ok(outputs.clone())
+    }
+}
+
+/// Runs custom models which do not fit the builtin tasks.
+///
+/// The inputs can be raw tensors, or images, text and audio which are converted using the model metadata.
+/// The results are typed output tensors, or the results of a user-defined [`PostProcess`].
+pub struct CustomModel {
+    build_options: CustomModelBuilder,
+    model_resource: Box<dyn ModelResourceTrait>,
+    graph: Graph,
+}
+
+impl CustomModel {
+    base_task_options_get_impl!();
+
+    /// Get the number of model input tensors.
+    #[inline(always)]
+    pub fn input_tensor_count(&self) -> usize {
+        self.model_resource.input_tensor_count()
+    }
+
+    /// Get the number of model output tensors.
+    #[inline(always)]
+    pub fn output_tensor_count(&self) -> usize {
+        self.model_resource.output_tensor_count()
+    }
+
+    /// Get the shape of the input tensor at `index`.
+    #[inline(always)]
+    pub fn input_tensor_shape(&self, index: usize) -> Option<&[usize]> {
+        self.model_resource.input_tensor_shape(index)
+    }
+
+    /// Get the type of the input tensor at `index`.
+    #[inline(always)]
+    pub fn input_tensor_type(&self, index: usize) -> Option<TensorType> {
+        self.model_resource.input_tensor_type(index)
+    }
+
+    /// Get the shape of the output tensor at `index`.
+    #[inline(always)]
+    pub fn output_tensor_shape(&self, index: usize) -> Option<&[usize]> {
+        self.model_resource.output_tensor_shape(index)
+    }
+
+    /// Get the type of the output tensor at `index`, before dequantization.
+    #[inline(always)]
+    pub fn output_tensor_type(&self, index: usize) -> Option<TensorType> {
+        self.model_resource.output_tensor_type(index)
+    }
+
+    /// Get the name of the output tensor at `index`, if the model has it.
+    #[inline(always)]
+    pub fn output_tensor_name(&self, index: usize) -> Option<&str> {
+        self.model_resource.output_tensor_name(index)
+    }
+
+    /// Find the index of the output tensor by name.
+    #[inline(always)]
+    pub fn output_tensor_name_to_index(&self, name: &str) -> Option<usize> {
+        (0..self.output_tensor_count()).find(|i| self.output_tensor_name(*i) == Some(name))
+    }
+
+    /// Get the labels of the output tensor at `index`, from the model metadata or the builder options.
+    #[inline(always)]
+    pub fn output_tensor_labels(&self, index: usize) -> Result<Vec<&str>, Error> {
+        let (labels, _) = self.model_resource.output_tensor_labels_locale(index, "")?;
+        let labels = std::str::from_utf8(labels).map_err(|e| {
+            Error::ModelInconsistentError(format!("Labels are not valid utf-8: `{}`", e))
+        })?;
+        Ok(labels.lines().collect())
+    }
+
+    /// Create a new task session that contains processing buffers and can do inference.
+    /// The session returns the output tensors.
+    #[inline(always)]
+    pub fn new_session(&self) -> Result<CustomModelSession, Error> {
+        self.new_session_with(RawOutputs)
+    }
+
+    /// Create a new task session which uses `post_process` to convert output tensors to results.
+    pub fn new_session_with<P: PostProcess>(
+        &self,
+        post_process: P,
+    ) -> Result<CustomModelSession<P>, Error> {
+        let input_count = self.model_resource.input_tensor_count();
+        let mut input_tensor_shapes = Vec::with_capacity(input_count);
+        let mut input_tensor_types = Vec::with_capacity(input_count);
+        let mut input_tensor_bufs = Vec::with_capacity(input_count);
+        for i in 0..input_count {
+            let input_tensor_shape =
+                model_resource_check_and_get_impl!(self.model_resource, input_tensor_shape, i);
+            let input_tensor_type =
+                model_resource_check_and_get_impl!(self.model_resource, input_tensor_type, i);
+            input_tensor_bufs.push(vec![
+                0;
+                tensor_bytes!(input_tensor_type, input_tensor_shape)
+            ]);
+            input_tensor_shapes.push(input_tensor_shape);
+            input_tensor_types.push(input_tensor_type);
+        }
+
+        let output_count = self.model_resource.output_tensor_count();
+        let mut output_tensor_types = Vec::with_capacity(output_count);
+        let mut output_tensor_bufs = Vec::with_capacity(output_count);
+        let mut outputs = OutputTensors {
+            tensors: Vec::with_capacity(output_count),
+            timestamp_ms: None,
+        };
+        for i in 0..output_count {
+            let output_tensor_shape =
+                model_resource_check_and_get_impl!(self.model_resource, output_tensor_shape, i);
+            let output_tensor_type =
+                model_resource_check_and_get_impl!(self.model_resource, output_tensor_type, i);
+            // unsigned outputs without quantization parameters are kept as raw bytes
+            let quantization_parameters =
+                self.model_resource.output_tensor_quantization_parameters(i);
+            output_tensor_bufs.push(vec![
+                0;
+                tensor_bytes!(output_tensor_type, output_tensor_shape)
+            ]);
+            output_tensor_types.push((output_tensor_type, quantization_parameters));
+            outputs.tensors.push(OutputTensor {
+                name: self.model_resource.output_tensor_name(i).map(String::from),
+                shape: output_tensor_shape.to_vec(),
+                data: Default::default(),
+            });
+        }
+
+        let execution_ctx = self.graph.init_execution_context()?;
+        Ok(CustomModelSession {
+            model: self,
+            execution_ctx,
+            post_process,
+            input_tensor_shapes,
+            input_tensor_types,
+            input_tensor_bufs,
+            output_tensor_types,
+            output_tensor_bufs,
+            outputs,
+        })
+    }
+
+    /// Run the model with raw input tensors using a new session.
+    #[inline(always)]
+    pub fn process_tensors(&self, inputs: &[&[u8]]) -> Result<OutputTensors, Error> {
+        self.new_session()?.process_tensors(inputs)
+    }
+
+    /// Run the model with one image using a new session.
+    #[cfg(feature = "vision")]
+    #[inline(always)]
+    pub fn process_image(&self, input: &impl ImageToTensor) -> Result<OutputTensors, Error> {
+        self.new_session()?.process_image(input)
+    }
+
+    /// Run the model with video frames using a new session, and collect all results to [`Vec`].
+    #[cfg(feature = "vision")]
+    #[inline(always)]
+    pub fn process_video(&self, video_data: impl VideoData) -> Result<Vec<OutputTensors>, Error> {
+        self.new_session()?.process_video(video_data)?.to_vec()
+    }
+
+    /// Run the model with text using a new session.
+    #[cfg(feature = "text")]
+    #[inline(always)]
+    pub fn process_text(&self, input: &impl TextToTensors) -> Result<OutputTensors, Error> {
+        self.new_session()?.process_text(input)
+    }
+
+    /// Run the model with audio stream using a new session, and collect all results to [`Vec`].
+    #[cfg(feature = "audio")]
+    #[inline(always)]
+    pub fn process_audio(&self, input_stream: impl AudioData) -> Result<Vec<OutputTensors>, Error> {
+        self.new_session()?.process_audio(input_stream)?.to_vec()
+    }
+}
+
+/// Session to run inference.
+/// If process multiple inputs, reuse it can get better performance.
+///
+/// ```rust
+/// use mediapipe_rs::tasks::custom::CustomModelBuilder;
+///
+/// let model = CustomModelBuilder::new()
+///     .model_asset_path("model.onnx")
+///     .finalize()?;
+/// let index = model.output_tensor_name_to_index("scores").unwrap();
+/// let mut session = model.new_session()?;
+/// for input in inputs {
+///     let outputs = session.process_tensors(&[input])?;
+///     let scores = outputs.get(index).unwrap().data.as_f32().unwrap();
+/// }
+/// ```
+pub struct CustomModelSession<'model, P: PostProcess = RawOutputs> {
+    model: &'model CustomModel,
+    execution_ctx: GraphExecutionContext<'model>,
+    post_process: P,
+
+    input_tensor_shapes: Vec<&'model [usize]>,
+    input_tensor_types: Vec<TensorType>,
+    input_tensor_bufs: Vec<Vec<u8>>,
+
+    output_tensor_types: Vec<(TensorType, Option<QuantizationParameters>)>,
+    output_tensor_bufs: Vec<Vec<u8>>,
+    outputs: OutputTensors,
+}
+
+impl<'model, P: PostProcess> CustomModelSession<'model, P> {
+    /// Get the post-processing of this session.
+    #[inline(always)]
+    pub fn post_process(&self) -> &P {
+        &self.post_process
+    }
+
+    /// Get the mutable post-processing of this session.
+    #[inline(always)]
+    pub fn post_process_mut(&mut self) -> &mut P {
+        &mut self.post_process
+    }
+
+    /// Set the raw data (native endian) of the input tensor at `index`.
+    /// The input tensors are used by the next [`CustomModelSession::run`].
+    pub fn set_input_tensor(&mut self, index: usize, data: &[u8]) -> Result<(), Error> {
+        let buf = self.input_tensor_bufs.get_mut(index).ok_or_else(|| {
+            Error::ArgumentError(format!(
+                "Input tensor index `{}` out of range, model has `{}` inputs",
+                index,
+                self.input_tensor_shapes.len()
+            ))
+        })?;
+        if buf.len() != data.len() {
+            return Err(Error::ArgumentError(format!(
+                "Input tensor `{}` expect `{}` bytes, but got `{}`",
+                index,
+                buf.len(),
+                data.len()
+            )));
+        }
+        buf.copy_from_slice(data);
+        Ok(())
+    }
+
+    /// Run the model with the input tensors set by [`CustomModelSession::set_input_tensor`].
+    #[inline(always)]
+    pub fn run(&mut self) -> Result<P::Result, Error> {
+        self.compute(None)
+    }
+
+    /// Run the model with raw input tensors (native endian), the number of inputs must be equal to the model inputs.
+    pub fn process_tensors(&mut self, inputs: &[&[u8]]) -> Result<P::Result, Error> {
+        if inputs.len() != self.input_tensor_bufs.len() {
+            return Err(Error::ArgumentError(format!(
+                "Expect `{}` input tensors, but got `{}`",
+                self.input_tensor_bufs.len(),
+                inputs.len()
+            )));
+        }
+        for (index, input) in inputs.iter().enumerate() {
+            self.set_input_tensor(index, input)?;
+        }
+        self.compute(None)
+    }
+
+    /// Run the model with one image, which is converted to the input tensor `0`.
+    #[cfg(feature = "vision")]
+    #[inline(always)]
+    pub fn process_image(&mut self, input: &impl ImageToTensor) -> Result<P::Result, Error> {
+        self.process_image_with_options(input, &Default::default())
+    }
+
+    /// Run the model with one image and options to specify the region of interest.
+    #[cfg(feature = "vision")]
+    pub fn process_image_with_options(
+        &mut self,
+        input: &impl ImageToTensor,
+        process_options: &ImageProcessingOptions,
+    ) -> Result<P::Result, Error> {
+        let input_to_tensor_info =
+            model_resource_check_and_get_impl!(self.model.model_resource, to_tensor_info, 0)
+                .try_to_image()?;
+        let input_tensor_buf = self
+            .input_tensor_bufs
+            .get_mut(0)
+            .ok_or_else(|| Error::ModelInconsistentError("Model has no input tensors".into()))?;
+        input.to_tensor(input_to_tensor_info, process_options, input_tensor_buf)?;
+        self.compute(input.timestamp_ms())
+    }
+
+    /// Run the model with input video stream use this session.
+    /// Return a iterator for results, process input stream when poll next result.
+    #[cfg(feature = "vision")]
+    #[inline(always)]
+    pub fn process_video<InputVideoData: VideoData>(
+        &mut self,
+        video_data: InputVideoData,
+    ) -> Result<VideoResultsIter<Self, InputVideoData>, Error>
+    where
+        P::Result: 'static,
+    {
+        Ok(VideoResultsIter::new(self, video_data))
+    }
+
+    /// Run the model with text, which is converted to the input tensors.
+    #[cfg(feature = "text")]
+    pub fn process_text(&mut self, input: &impl TextToTensors) -> Result<P::Result, Error> {
+        let input_to_tensor_info =
+            model_resource_check_and_get_impl!(self.model.model_resource, to_tensor_info, 0)
+                .try_to_text()?;
+        input.to_resizable_tensors(input_to_tensor_info, &mut self.input_tensor_bufs)?;
+        self.compute(None)
+    }
+
+    /// Run the model with audio stream use this session.
+    /// Return a iterator for results, process input stream when poll next result.
+    #[cfg(feature = "audio")]
+    #[inline(always)]
+    pub fn process_audio<T: AudioData>(
+        &mut self,
+        input_stream: T,
+    ) -> Result<AudioResultsIter<Self, T>, Error>
+    where
+        P::Result: 'static,
+    {
+        let input_to_tensor_info =
+            model_resource_check_and_get_impl!(self.model.model_resource, to_tensor_info, 0)
+                .try_to_audio()?;
+        let audio_data = AudioDataToTensorIter::new(input_to_tensor_info, input_stream)?;
+        Ok(AudioResultsIter::new(self, audio_data))
+    }
+
+    fn compute(&mut self, timestamp_ms: Option<u64>) -> Result<P::Result, Error> {
+        for index in 0..self.input_tensor_bufs.len() {
+            self.execution_ctx.set_input(
+                index,
+                self.input_tensor_types[index],
+                self.input_tensor_shapes[index],
+                self.input_tensor_bufs[index].as_slice(),
+            )?;
+        }
+        self.execution_ctx.compute()?;
+
+        for index in 0..self.output_tensor_bufs.len() {
+            let output_buffer = self.output_tensor_bufs[index].as_mut_slice();
+            let output_size = self.execution_ctx.get_output(index, output_buffer)?;
+            if output_size != output_buffer.len() {
+                return Err(Error::ModelInconsistentError(format!(
+                    "Model output `{}` bytes size is `{}`, but got `{}`",
+                    index,
+                    output_buffer.len(),
+                    output_size
+                )));
+            }
+            let (tensor_type, quantization_parameters) = self.output_tensor_types[index];
+            self.outputs.tensors[index].data.copy_from_bytes(
+                output_buffer,
+                tensor_type,
+                quantization_parameters,
+            )?;
+        }
+        self.outputs.timestamp_ms = timestamp_ms;
+
+        self.post_process.process(&self.outputs)
+    }
+}
+
+#[cfg(feature = "vision")]
+impl<'model, P: PostProcess> crate::tasks::vision::TaskSession for CustomModelSession<'model, P>
+where
+    P::Result: 'static,
+{
+    type Result = P::Result;
+
+    #[inline]
+    fn process_next(
+        &mut self,
+        process_options: &ImageProcessingOptions,
+        video_data: &mut impl VideoData,
+    ) -> Result<Option<Self::Result>, Error> {
+        if let Some(frame) = video_data.next_frame()? {
+            return self
+                .process_image_with_options(&frame, process_options)
+                .map(Some);
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "audio")]
+impl<'model, P: PostProcess> crate::tasks::audio::TaskSession for CustomModelSession<'model, P>
+where
+    P::Result: 'static,
+{
+    type Result = P::Result;
+
+    #[inline]
+    fn process_next<Source: AudioData>(
+        &mut self,
+        input_stream: &mut AudioDataToTensorIter<Source>,
+    ) -> Result<Option<Self::Result>, Error> {
+        let input_tensor_buf = self
+            .input_tensor_bufs
+            .get_mut(0)
+            .ok_or_else(|| Error::ModelInconsistentError("Model has no input tensors".into()))?;
+        if let Some(timestamp_ms) = input_stream.poll_next_tensors(&mut [input_tensor_buf])? {
+            return self.compute(Some(timestamp_ms)).map(Some);
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(all(test, feature = "tract"))]
+mod test {
+    use super::*;
+    use crate::postprocess::TensorData;
+
+    struct Sum;
+
+    impl PostProcess for Sum {
+        type Result = f32;
+
+        fn process(&mut self, outputs: &OutputTensors) -> Result<f32, Error> {
+            Ok(outputs.get(0).unwrap().data.as_f32().unwrap().iter().sum())
+        }
+    }
+
+    #[test]
+    fn test_custom_model() {
+        let model = CustomModelBuilder::new()
+            .model_asset_path("assets/testdata/test_add.onnx")
+            .inference_backend(std::sync::Arc::new(crate::backend::TractBackend))
+            .finalize()
+            .unwrap();
+        assert_eq!(model.input_tensor_count(), 1);
+        assert_eq!(model.output_tensor_count(), 1);
+        assert_eq!(model.input_tensor_shape(0), Some([2].as_slice()));
+        assert_eq!(model.output_tensor_type(0), Some(TensorType::F32));
+        let name = model.output_tensor_name(0).unwrap().to_string();
+        assert_eq!(model.output_tensor_name_to_index(&name), Some(0));
+        assert_eq!(model.output_tensor_name_to_index("not_exist"), None);
+
+        let input: Vec<u8> = [1.5f32, -2.].iter().flat_map(|f| f.to_ne_bytes()).collect();
+        let outputs = model.process_tensors(&[&input]).unwrap();
+        let output = outputs.get_by_name(&name).unwrap();
+        assert_eq!(output.shape, vec![2]);
+        assert_eq!(output.data, TensorData::F32(vec![3., -4.]));
+        assert!(outputs.timestamp_ms.is_none());
+
+        let mut session = model.new_session_with(Sum).unwrap();
+        assert!(session.process_tensors(&[]).is_err());
+        assert!(session.set_input_tensor(0, &input[..4]).is_err());
+        assert!(session.set_input_tensor(1, &input).is_err());
+        session.set_input_tensor(0, &input).unwrap();
+        assert_eq!(session.run().unwrap(), -1.);
+    }
+}
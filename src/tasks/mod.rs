@@ -4,6 +4,9 @@ pub(crate) mod common;
 #[cfg(feature = "audio")]
 pub mod audio;
 
+/// Run custom models with user-defined post-processing.
+pub mod custom;
+
 #[cfg(feature = "text")]
 pub mod text;
 
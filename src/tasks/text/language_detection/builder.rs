@@ -0,0 +1,88 @@
+use super::LanguageDetector;
+use crate::model::ModelResourceTrait;
+use crate::tasks::common::{BaseTaskOptions, ClassificationOptions};
+use crate::Error;
+use wasi_nn_safe::TensorType;
+
+/// Configure the build options of a new **Language Detection** task instance.
+///
+/// Methods can be chained on it in order to configure it.
+pub struct LanguageDetectorBuilder {
+    pub(super) base_task_options: BaseTaskOptions,
+    pub(super) classification_options: ClassificationOptions,
+}
+
+impl Default for LanguageDetectorBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            classification_options: Default::default(),
+        }
+    }
+}
+
+impl LanguageDetectorBuilder {
+    /// Create a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            base_task_options: Default::default(),
+            classification_options: Default::default(),
+        }
+    }
+
+    base_task_options_impl!();
+
+    model_metadata_override_impl!();
+
+    classification_options_impl!();
+
+    /// Use the build options to create a new task instance.
+    #[inline]
+    pub fn finalize(mut self) -> Result<LanguageDetector, Error> {
+        classification_options_check!(self, classification_options);
+        let buf = base_task_options_check_and_get_buf!(self);
+
+        // change the lifetime to 'static, because the graph keeps the buf until the task is dropped.
+        let model_resource_ref = crate::model::parse_model(buf.as_ref())?;
+        let model_resource = unsafe {
+            std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
+        };
+        let model_resource = self
+            .base_task_options
+            .model_metadata_override
+            .apply(model_resource)?;
+
+        // check model
+        model_base_check_impl!(model_resource, 1);
+        model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_text()?;
+
+        let input_count = model_resource.input_tensor_count();
+        if input_count != 1 && input_count != 3 {
+            return Err(Error::ModelInconsistentError(format!(
+                "Expect model input tensor count `1` or `3`, but got `{}`",
+                input_count
+            )));
+        }
+        for i in 0..input_count {
+            let t = model_resource_check_and_get_impl!(model_resource, input_tensor_type, i);
+            // string tensor is fed as bytes
+            if t != TensorType::I32 && t != TensorType::U8 {
+                return Err(Error::ModelInconsistentError(
+                    "All input tensors should be int32 or string type".into(),
+                ));
+            }
+        }
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.model_backend(), buf)?;
+
+        return Ok(LanguageDetector {
+            build_options: self,
+            model_resource,
+            graph,
+        });
+    }
+}
@@ -0,0 +1,225 @@
+mod builder;
+pub use builder::LanguageDetectorBuilder;
+
+use crate::model::ModelResourceTrait;
+use crate::postprocess::{CategoriesFilter, LanguageDetectorResult, TensorsToClassification};
+use crate::preprocess::text::{TextToTensorInfo, TextToTensors};
+use crate::{Error, Graph, GraphExecutionContext, TensorType};
+
+/// Predicts the language of an input text.
+pub struct LanguageDetector {
+    build_options: LanguageDetectorBuilder,
+    model_resource: Box<dyn ModelResourceTrait>,
+    graph: Graph,
+}
+
+impl LanguageDetector {
+    base_task_options_get_impl!();
+
+    classification_options_get_impl!();
+
+    /// Create a new task session that contains processing buffers and can do inference.
+    #[inline(always)]
+    pub fn new_session(&self) -> Result<LanguageDetectorSession, Error> {
+        let input_to_tensor_info =
+            model_resource_check_and_get_impl!(self.model_resource, to_tensor_info, 0)
+                .try_to_text()?;
+        let input_count = self.model_resource.input_tensor_count();
+        let mut input_tensor_types = Vec::with_capacity(input_count);
+        let mut input_tensor_shapes = Vec::with_capacity(input_count);
+        let mut input_tensor_bufs = Vec::with_capacity(input_count);
+        for i in 0..input_count {
+            let input_tensor_type =
+                model_resource_check_and_get_impl!(self.model_resource, input_tensor_type, i);
+            let input_tensor_shape =
+                model_resource_check_and_get_impl!(self.model_resource, input_tensor_shape, i);
+            input_tensor_types.push(input_tensor_type);
+            input_tensor_shapes.push(input_tensor_shape);
+            input_tensor_bufs.push(vec![
+                0;
+                tensor_bytes!(input_tensor_type, input_tensor_shape)
+            ]);
+        }
+
+        let output_tensor_shape =
+            model_resource_check_and_get_impl!(self.model_resource, output_tensor_shape, 0);
+
+        let execution_ctx = self.graph.init_execution_context()?;
+        let labels = self.model_resource.output_tensor_labels_locale(
+            0,
+            self.build_options
+                .classification_options
+                .display_names_locale
+                .as_ref(),
+        )?;
+
+        let categories_filter = CategoriesFilter::new(
+            &self.build_options.classification_options,
+            labels.0,
+            labels.1,
+        );
+        let mut tensors_to_classification = TensorsToClassification::new();
+        tensors_to_classification.add_classification_options(
+            categories_filter,
+            self.build_options.classification_options.max_results,
+            get_type_and_quantization!(self.model_resource, 0),
+            output_tensor_shape,
+        );
+
+        Ok(LanguageDetectorSession {
+            execution_ctx,
+            tensors_to_classification,
+            input_to_tensor_info,
+            input_tensor_types,
+            input_tensor_shapes,
+            input_tensor_bufs,
+        })
+    }
+
+    /// Detect the language of input using a new session.
+    #[inline(always)]
+    pub fn detect(&self, input: &impl TextToTensors) -> Result<LanguageDetectorResult, Error> {
+        self.new_session()?.detect(input)
+    }
+}
+
+/// Session to run inference.
+/// If process multiple text, reuse it can get better performance.
+///
+/// ```rust
+/// use mediapipe_rs::tasks::text::LanguageDetector;
+///
+/// let language_detector: LanguageDetector;
+/// let mut session = language_detector.new_session()?;
+/// for text in texts {
+///     session.detect(text)?;
+/// }
+/// ```
+pub struct LanguageDetectorSession<'a> {
+    execution_ctx: GraphExecutionContext<'a>,
+    tensors_to_classification: TensorsToClassification<'a>,
+
+    input_to_tensor_info: &'a TextToTensorInfo<'a>,
+    input_tensor_types: Vec<TensorType>,
+    input_tensor_shapes: Vec<&'a [usize]>,
+    input_tensor_bufs: Vec<Vec<u8>>,
+}
+
+impl<'a> LanguageDetectorSession<'a> {
+    /// Detect the language of input using this session.
+    pub fn detect(&mut self, input: &impl TextToTensors) -> Result<LanguageDetectorResult, Error> {
+        input.to_resizable_tensors(self.input_to_tensor_info, &mut self.input_tensor_bufs)?;
+
+        for index in 0..self.input_tensor_bufs.len() {
+            let buf = self.input_tensor_bufs[index].as_slice();
+            // the serialized string tensor has variable bytes size, so the shape is the bytes size
+            let string_tensor_shape = [buf.len()];
+            let shape = match self.input_to_tensor_info {
+                TextToTensorInfo::StringModel => &string_tensor_shape[..],
+                _ => self.input_tensor_shapes[index],
+            };
+            self.execution_ctx
+                .set_input(index, self.input_tensor_types[index], shape, buf)?;
+        }
+        self.execution_ctx.compute()?;
+
+        let output_buffer = self.tensors_to_classification.output_buffer(0);
+        let output_size = self.execution_ctx.get_output(0, output_buffer)?;
+        if output_size != output_buffer.len() {
+            return Err(Error::ModelInconsistentError(format!(
+                "Model output bytes size is `{}`, but got `{}`",
+                output_buffer.len(),
+                output_size
+            )));
+        }
+
+        Ok(self.tensors_to_classification.result(None).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::{ExecutionContext, InferenceBackend, InferenceGraph};
+    use crate::{Device, GraphEncoding, SharedSlice};
+
+    // a fake backend which checks the string tensor input, and detects `fr` if the text starts with `Bonjour`
+    struct StringModelBackend;
+
+    struct StringModelContext(Vec<f32>);
+
+    impl InferenceBackend for StringModelBackend {
+        fn build_graph(
+            &self,
+            _: GraphEncoding,
+            _: Device,
+            _: SharedSlice<u8>,
+        ) -> Result<Box<dyn InferenceGraph>, Error> {
+            Ok(Box::new(StringModelBackend))
+        }
+    }
+
+    impl InferenceGraph for StringModelBackend {
+        fn init_execution_context(&self) -> Result<Box<dyn ExecutionContext + '_>, Error> {
+            Ok(Box::new(StringModelContext(Vec::new())))
+        }
+    }
+
+    impl ExecutionContext for StringModelContext {
+        fn set_input(
+            &mut self,
+            index: usize,
+            tensor_type: TensorType,
+            dimensions: &[usize],
+            data: &[u8],
+        ) -> Result<(), Error> {
+            assert_eq!(index, 0);
+            assert_eq!(tensor_type, TensorType::U8);
+            assert_eq!(dimensions, &[data.len()]);
+            let read_i32 =
+                |i: usize| i32::from_le_bytes(data[i * 4..i * 4 + 4].try_into().unwrap()) as usize;
+            assert_eq!(read_i32(0), 1);
+            assert_eq!(read_i32(2), data.len());
+            let text = std::str::from_utf8(&data[read_i32(1)..]).unwrap();
+            self.0 = if text.starts_with("Bonjour") {
+                vec![0.1, 0.8, 0.1]
+            } else {
+                vec![0.8, 0.1, 0.1]
+            };
+            Ok(())
+        }
+
+        fn compute(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn get_output(&mut self, _: usize, output: &mut [u8]) -> Result<usize, Error> {
+            for (o, s) in output.chunks_exact_mut(4).zip(self.0.iter()) {
+                o.copy_from_slice(&s.to_ne_bytes());
+            }
+            Ok(self.0.len() * 4)
+        }
+    }
+
+    #[test]
+    fn test_string_model() {
+        let model = crate::model::metadata_writer::test::bare_string_input_model(&[1, 3]);
+        let detector = LanguageDetectorBuilder::new()
+            .model_asset_buffer(model)
+            .inference_backend(std::sync::Arc::new(StringModelBackend))
+            .labels(vec!["en".into(), "fr".into(), "zh".into()])
+            .max_results(1)
+            .finalize()
+            .unwrap();
+        let mut session = detector.new_session().unwrap();
+        for (text, language_code) in [
+            ("Bonjour, ça va ?", "fr"),
+            ("Hello", "en"),
+            ("Bonjour tout le monde, il fait beau aujourd'hui.", "fr"),
+        ] {
+            let result = session.detect(&text).unwrap();
+            assert_eq!(result.predictions.len(), 1);
+            assert_eq!(result.predictions[0].language_code, language_code);
+        }
+    }
+}
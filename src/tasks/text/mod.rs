@@ -1,3 +1,5 @@
+mod language_detection;
 mod text_classification;
 
+pub use language_detection::{LanguageDetector, LanguageDetectorBuilder, LanguageDetectorSession};
 pub use text_classification::{TextClassifier, TextClassifierBuilder, TextClassifierSession};
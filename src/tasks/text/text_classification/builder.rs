@@ -34,6 +34,8 @@ impl TextClassifierBuilder {
 
     base_task_options_impl!();
 
+    model_metadata_override_impl!();
+
     classification_options_impl!();
 
     /// Use the build options to create a new task instance.
@@ -42,11 +44,15 @@ impl TextClassifierBuilder {
         classification_options_check!(self, classification_options);
         let buf = base_task_options_check_and_get_buf!(self);
 
-        // change the lifetime to 'static, because the buf will move to graph and will not be released.
+        // change the lifetime to 'static, because the graph keeps the buf until the task is dropped.
         let model_resource_ref = crate::model::parse_model(buf.as_ref())?;
         let model_resource = unsafe {
             std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
         };
+        let model_resource = self
+            .base_task_options
+            .model_metadata_override
+            .apply(model_resource)?;
 
         // check model
         model_base_check_impl!(model_resource, 1);
@@ -69,11 +75,9 @@ impl TextClassifierBuilder {
             }
         }
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.execution_target,
-        )
-        .build_from_shared_slices([buf])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.model_backend(), buf)?;
 
         return Ok(TextClassifier {
             build_options: self,
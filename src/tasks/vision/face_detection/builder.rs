@@ -1,7 +1,6 @@
-use super::FaceDetector;
+use super::{FaceDetectionModelType, FaceDetector};
 use crate::model::ModelResourceTrait;
-use crate::postprocess::SsdAnchorsBuilder;
-use crate::tasks::common::BaseTaskOptions;
+use crate::tasks::common::{BaseTaskOptions, SsdAnchorsOptions};
 
 /// Configure the build options of a new **Face Detection** task instance.
 ///
@@ -14,6 +13,10 @@ pub struct FaceDetectorBuilder {
     pub(super) min_detection_confidence: f32,
     /// The minimum non-maximum-suppression threshold for face detection to be considered overlapped.
     pub(super) min_suppression_threshold: f32,
+    /// The anchors options to override the model metadata and defaults.
+    pub(super) ssd_anchors_options: SsdAnchorsOptions,
+    /// The face detection model type, `None` means detecting it from the model.
+    pub(super) model_type: Option<FaceDetectionModelType>,
 }
 
 impl Default for FaceDetectorBuilder {
@@ -32,11 +35,17 @@ impl FaceDetectorBuilder {
             num_faces: -1,
             min_detection_confidence: 0.5,
             min_suppression_threshold: 0.3,
+            ssd_anchors_options: Default::default(),
+            model_type: None,
         }
     }
 
     base_task_options_impl!();
 
+    model_metadata_override_impl!();
+
+    ssd_anchors_options_impl!();
+
     /// Set the maximum number of faces can be detected by the HandDetector.
     /// Default is -1, (no limits)
     #[inline(always)]
@@ -61,6 +70,14 @@ impl FaceDetectorBuilder {
         self
     }
 
+    /// Set the face detection model type, such as the short-range or the full-range model.
+    /// Default is detected from the number of boxes output by the model.
+    #[inline(always)]
+    pub fn model_type(mut self, model_type: FaceDetectionModelType) -> Self {
+        self.model_type = Some(model_type);
+        self
+    }
+
     /// Use the build options to create a new task instance.
     #[inline]
     pub fn finalize(mut self) -> Result<FaceDetector, crate::Error> {
@@ -71,36 +88,48 @@ impl FaceDetectorBuilder {
         }
         let buf = base_task_options_check_and_get_buf!(self);
 
-        // change the lifetime to 'static, because the buf will move to graph and will not be released.
+        // change the lifetime to 'static, because the graph keeps the buf until the task is dropped.
         let model_resource_ref = crate::model::parse_model(buf.as_ref())?;
         let model_resource = unsafe {
             std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
         };
+        let model_resource = self
+            .base_task_options
+            .model_metadata_override
+            .apply(model_resource)?;
 
         // check model
         model_base_check_impl!(model_resource, 1, 2);
         let img_info =
             model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_image()?;
 
-        // generate anchors
-        // todo: read info from metadata
-        let num_box = 896;
+        // generate anchors and decoding options
         let width = img_info.width();
         let height = img_info.height();
-        let anchors = SsdAnchorsBuilder::new(width, height, 0.1484375, 0.75, 4)
-            .interpolated_scale_aspect_ratio(1.0)
-            .anchor_offset_x(0.5)
-            .anchor_offset_y(0.5)
-            .strides(vec![8, 16, 16, 16])
-            .aspect_ratios(vec![1.0])
-            .fixed_anchor_size(true)
-            .generate();
-
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.execution_target,
-        )
-        .build_from_shared_slices([buf])?;
+        let model_type = match self.model_type {
+            Some(t) => t,
+            None => {
+                let location_shape =
+                    model_resource_check_and_get_impl!(model_resource, output_tensor_shape, 0);
+                let num_boxes = location_shape
+                    .iter()
+                    .product::<usize>()
+                    .checked_div(location_shape.last().cloned().unwrap_or(0))
+                    .unwrap_or(0);
+                FaceDetectionModelType::detect(width, height, num_boxes)
+            }
+        };
+        let (anchors, decoding_options) = self.ssd_anchors_options.anchors_and_decoding_options(
+            model_resource.as_ref(),
+            0,
+            1,
+            model_type.default_anchors(width, height),
+            model_type.default_decoding_options(width, height),
+        )?;
+
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.model_backend(), buf)?;
 
         let input_tensor_type =
             model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
@@ -112,8 +141,93 @@ impl FaceDetectorBuilder {
             anchors,
             location_buf_index: 0,
             score_buf_index: 1,
-            num_box,
+            decoding_options,
+            model_type,
             input_tensor_type,
         });
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::metadata_writer::{
+        test::bare_model_with_types, DetectorMetadata, MetadataWriter,
+    };
+
+    fn build(model: Vec<u8>, builder: FaceDetectorBuilder) -> Result<FaceDetector, crate::Error> {
+        let backend = std::sync::Arc::new(crate::backend::ReplayBackend::new("replay"));
+        builder
+            .model_asset_buffer(model)
+            .inference_backend(backend)
+            .image_normalization(vec![127.5], vec![127.5])
+            .finalize()
+    }
+
+    fn model(num_boxes: i32) -> Vec<u8> {
+        bare_model_with_types(
+            &[(&[1, 16, 16, 3], crate::TensorType::F32)],
+            &[
+                (&[1, num_boxes, 16], crate::TensorType::F32),
+                (&[1, num_boxes, 1], crate::TensorType::F32),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_builder_ssd_anchors() {
+        // default anchors: 2 * 2 * 2 for stride 8, 1 * 1 * 6 for stride 16
+        let detector = build(model(14), FaceDetectorBuilder::new()).unwrap();
+        assert_eq!(detector.anchors.len(), 14);
+        assert_eq!(detector.model_type(), FaceDetectionModelType::ShortRange);
+        assert_eq!(detector.decoding_options.x_scale, 16.);
+        assert!(detector.new_session().is_ok());
+        assert!(build(model(8), FaceDetectorBuilder::new()).is_err());
+
+        // full-range model: 4 * 4 * 1 for stride 4
+        let detector = build(model(16), FaceDetectorBuilder::new()).unwrap();
+        assert_eq!(detector.model_type(), FaceDetectionModelType::FullRange);
+        assert_eq!(detector.anchors.len(), 16);
+        assert_eq!(detector.anchors[0].x_center, 0.125);
+        assert!(detector.new_session().is_ok());
+        assert!(build(
+            model(16),
+            FaceDetectorBuilder::new().model_type(FaceDetectionModelType::ShortRange)
+        )
+        .is_err());
+
+        // builder overrides
+        let detector = build(
+            model(8),
+            FaceDetectorBuilder::new().anchors_strides(vec![8]),
+        )
+        .unwrap();
+        assert_eq!(detector.anchors.len(), 8);
+        assert!(build(
+            model(8),
+            FaceDetectorBuilder::new()
+                .anchors_strides(vec![8])
+                .anchors_num_layers(2)
+        )
+        .is_err());
+
+        // model metadata
+        let buf = MetadataWriter::new(model(2))
+            .detector_metadata(
+                DetectorMetadata::new()
+                    .fixed_anchors([[0.25, 0.5, 1., 1.], [0.75, 0.5, 1., 1.]])
+                    .tensors_decoding(1, 2, 16)
+                    .key_points(4, 6, 2)
+                    .sigmoid_score(true),
+            )
+            .finalize()
+            .unwrap();
+        let detector = build(buf.clone(), FaceDetectorBuilder::new()).unwrap();
+        assert_eq!(detector.anchors[1].x_center, 0.75);
+        assert_eq!(detector.decoding_options.num_boxes, 2);
+        assert_eq!(detector.decoding_options.w_scale, 16.);
+        assert!(detector.new_session().is_ok());
+        // builder overrides have higher priority
+        assert!(build(buf, FaceDetectorBuilder::new().anchors_strides(vec![8])).is_err());
+    }
+}
@@ -4,11 +4,81 @@ pub use builder::FaceDetectorBuilder;
 use crate::model::ModelResourceTrait;
 use crate::postprocess::{
     Anchor, CategoriesFilter, DetectionBoxFormat, DetectionResult, NonMaxSuppressionAlgorithm,
-    NonMaxSuppressionOverlapType, TensorsToDetection,
+    NonMaxSuppressionOverlapType, SsdAnchorsBuilder, SsdDecodingOptions, TensorsToDetection,
 };
 use crate::preprocess::vision::ImageToTensorInfo;
 use crate::{Error, Graph, GraphExecutionContext, TensorType};
 
+/// The MediaPipe face detection model types, which have different anchors and decoding options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaceDetectionModelType {
+    /// Short-range BlazeFace model (128x128 input), for faces within 2 meters from the camera.
+    ShortRange,
+    /// Full-range sparse model (192x192 input), for faces within 5 meters from the camera.
+    FullRange,
+}
+
+impl FaceDetectionModelType {
+    const ALL: [Self; 2] = [Self::ShortRange, Self::FullRange];
+
+    /// The default anchors options.
+    /// reference: https://github.com/google/mediapipe/tree/master/mediapipe/modules/face_detection
+    fn default_anchors(&self, width: u32, height: u32) -> SsdAnchorsBuilder {
+        match self {
+            Self::ShortRange => SsdAnchorsBuilder::new(width, height, 0.1484375, 0.75, 4)
+                .interpolated_scale_aspect_ratio(1.0)
+                .anchor_offset_x(0.5)
+                .anchor_offset_y(0.5)
+                .strides(vec![8, 16, 16, 16])
+                .aspect_ratios(vec![1.0])
+                .fixed_anchor_size(true),
+            Self::FullRange => SsdAnchorsBuilder::new(width, height, 0.1484375, 0.75, 1)
+                .interpolated_scale_aspect_ratio(0.0)
+                .anchor_offset_x(0.5)
+                .anchor_offset_y(0.5)
+                .strides(vec![4])
+                .aspect_ratios(vec![1.0])
+                .fixed_anchor_size(true),
+        }
+    }
+
+    /// The default decoding options. Both models output 16 values per box: bbox and 6 key points.
+    fn default_decoding_options(&self, width: u32, height: u32) -> SsdDecodingOptions {
+        SsdDecodingOptions {
+            num_coords: 16,
+            keypoint_coord_offset: 4,
+            num_key_points: 6,
+            num_values_per_key_point: 2,
+            x_scale: width as f32,
+            y_scale: height as f32,
+            w_scale: width as f32,
+            h_scale: height as f32,
+            sigmoid_score: true,
+            ..Default::default()
+        }
+    }
+
+    /// The box format of the model location outputs.
+    #[inline(always)]
+    fn box_format(&self) -> DetectionBoxFormat {
+        match self {
+            Self::ShortRange | Self::FullRange => DetectionBoxFormat::XYWH,
+        }
+    }
+
+    /// Select the model type whose default anchors match the number of boxes output by the model.
+    /// Return ```ShortRange``` if no model type matches.
+    fn detect(width: u32, height: u32, num_boxes: usize) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|t| {
+                let anchors = t.default_anchors(width, height);
+                anchors.check().is_ok() && anchors.generate().len() == num_boxes
+            })
+            .unwrap_or(Self::ShortRange)
+    }
+}
+
 /// Performs face detection on images and video frames.
 pub struct FaceDetector {
     build_options: FaceDetectorBuilder,
@@ -18,7 +88,8 @@ pub struct FaceDetector {
     anchors: Vec<Anchor>,
     location_buf_index: usize,
     score_buf_index: usize,
-    num_box: usize,
+    decoding_options: SsdDecodingOptions,
+    model_type: FaceDetectionModelType,
 
     // only one input and one output
     input_tensor_type: TensorType,
@@ -49,6 +120,12 @@ impl FaceDetector {
         self.build_options.min_suppression_threshold
     }
 
+    /// Get the face detection model type, which is set in builder or detected from the model.
+    #[inline(always)]
+    pub fn model_type(&self) -> FaceDetectionModelType {
+        self.model_type
+    }
+
     /// Create a new task session that contains processing buffers and can do inference.
     #[inline(always)]
     pub fn new_session(&self) -> Result<FaceDetectorSession, Error> {
@@ -70,17 +147,14 @@ impl FaceDetector {
         );
 
         // config options
-        tensors_to_detection.set_anchors_scales(128.0, 128.0, 128.0, 128.0);
-        tensors_to_detection.set_num_coords(16);
-        tensors_to_detection.set_key_points(6, 2, 4);
-        tensors_to_detection.set_sigmoid_score(true);
+        tensors_to_detection.set_decoding_options(&self.decoding_options);
         tensors_to_detection.set_score_clipping_thresh(100.);
-        tensors_to_detection.set_box_format(DetectionBoxFormat::XYWH);
+        tensors_to_detection.set_box_format(self.model_type.box_format());
         tensors_to_detection.set_nms_min_suppression_threshold(self.min_suppression_threshold());
         tensors_to_detection
             .set_nms_overlap_type(NonMaxSuppressionOverlapType::IntersectionOverUnion);
         tensors_to_detection.set_nms_algorithm(NonMaxSuppressionAlgorithm::WEIGHTED);
-        tensors_to_detection.realloc(self.num_box);
+        tensors_to_detection.realloc(self.decoding_options.num_boxes);
 
         let execution_ctx = self.graph.init_execution_context()?;
         Ok(FaceDetectorSession {
@@ -128,7 +202,9 @@ impl<'model> FaceDetectorSession<'model> {
         )?;
 
         // generate result
-        Ok(self.tensors_to_detection.result(self.detector.num_box))
+        Ok(self
+            .tensors_to_detection
+            .result(self.detector.decoding_options.num_boxes))
     }
 
     detector_session_impl!(DetectionResult);
@@ -1,10 +1,12 @@
 use super::*;
-use crate::model::{ModelResourceTrait, ZipFiles};
+use crate::model::{ModelMetadataOverride, ModelResourceTrait, ZipFiles};
 use crate::tasks::common::{BaseTaskOptions, ClassificationOptions, HandLandmarkOptions};
 
 /// Configure the build options of a new **Gesture Recognition** task instance.
 ///
 /// Methods can be chained on it in order to configure it.
+/// The image options which override the model metadata apply to the hand landmark subtask,
+/// the labels override the labels of the canned gesture classifier.
 pub struct GestureRecognizerBuilder {
     pub(super) base_task_options: BaseTaskOptions,
     pub(super) classification_options: ClassificationOptions,
@@ -14,16 +16,12 @@ pub struct GestureRecognizerBuilder {
 
 macro_rules! build_graph_and_extra_model_resource {
     ( $file_buf:ident, $self:ident ) => {{
-        // change the lifetime to 'static, because the buf will move to graph and will not be released.
+        // change the lifetime to 'static, because the graph keeps the buf until the task is dropped.
         let model_resource_ref = crate::model::parse_model($file_buf.as_ref())?;
         let model_resource = unsafe {
             std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
         };
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            $self.base_task_options.execution_target,
-        )
-        .build_from_shared_slices([$file_buf])?;
+        let graph = $self.base_task_options.build_graph(model_resource.model_backend(), $file_buf)?;
         (model_resource, graph)
     }};
 }
@@ -42,6 +40,8 @@ impl GestureRecognizerBuilder {
 
     hand_landmark_options_impl!();
 
+    model_metadata_override_impl!();
+
     /// **Set options for custom classification model! (if custom model exists)**
     ///
     /// Set the locale to use for display names specified through the TFLite Model Metadata, if any.
@@ -153,6 +153,12 @@ impl GestureRecognizerBuilder {
                 model_asset_buffer: Some(landmark_task_file),
                 model_asset_path: None,
                 execution_target: self.base_task_options.execution_target,
+                inference_backend: self.base_task_options.inference_backend.clone(),
+                // the labels are the gesture labels of the canned gesture classifier
+                model_metadata_override: self
+                    .base_task_options
+                    .model_metadata_override
+                    .input_override(),
             },
             hand_landmark_options: Default::default(),
         }
@@ -200,6 +206,15 @@ impl GestureRecognizerBuilder {
         );
         let (canned_classify_model_resources, canned_classify_graph) =
             build_graph_and_extra_model_resource!(canned_file, self);
+        let canned_classify_model_resources = ModelMetadataOverride {
+            labels: self
+                .base_task_options
+                .model_metadata_override
+                .labels
+                .clone(),
+            ..Default::default()
+        }
+        .apply(canned_classify_model_resources)?;
         model_base_check_impl!(canned_classify_model_resources, 1, 1);
         check_tensor_type!(
             canned_classify_model_resources,
@@ -224,13 +239,16 @@ impl GestureRecognizerBuilder {
             let mut search_result = None;
             for name in Self::GESTURE_CUSTOM_GESTURE_CLASSIFIER_CANDIDATE_NAMES {
                 if let Some(r) = zip_file.get_file_offset(*name) {
-                    search_result = Some(r);
+                    let len = r.end - r.start;
+                    search_result = Some(hand_gesture_bundle_file.subslice(r.start, len).unwrap());
+                    break;
+                }
+                if let Some(c) = zip_file.get_file(name)? {
+                    search_result = Some(crate::SharedSlice::from(c.to_vec()));
                     break;
                 }
             }
-            if let Some(r) = search_result {
-                let len = r.end - r.start;
-                let custom_file = hand_gesture_bundle_file.subslice(r.start, len).unwrap();
+            if let Some(custom_file) = search_result {
                 let (r, g) = build_graph_and_extra_model_resource!(custom_file, self);
                 model_base_check_impl!(r, 1, 1);
                 check_tensor_type!(r, 0, input_tensor_type, TensorType::F32);
@@ -6,6 +6,7 @@ use crate::tasks::vision::results::HandLandmarkResult;
 
 /// The gesture recognition result from GestureRecognizer
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GestureRecognizerResult {
     /// Recognized hand gestures with sorted order such that the winning label is the first item in the list.
     pub gestures: ClassificationResult,
@@ -15,6 +16,7 @@ pub struct GestureRecognizerResult {
 
 /// The gesture recognition result list from GestureRecognizer
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GestureRecognizerResults(pub Vec<GestureRecognizerResult>);
 
 impl Deref for GestureRecognizerResults {
@@ -90,3 +92,55 @@ impl Display for GestureRecognizerResult {
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+    use crate::postprocess::{Category, Classifications, Landmark, Landmarks};
+
+    #[test]
+    fn test_serde() {
+        let category = |name: &str| Category {
+            index: 0,
+            score: 0.5,
+            category_name: Some(name.into()),
+            display_name: None,
+        };
+        let landmark = Landmark {
+            x: 0.5,
+            y: 0.25,
+            z: -0.125,
+            visibility: None,
+            presence: Some(1.),
+            name: None,
+        };
+        let results = GestureRecognizerResults(vec![GestureRecognizerResult {
+            gestures: ClassificationResult {
+                classifications: vec![Classifications {
+                    head_index: 0,
+                    head_name: None,
+                    categories: vec![category("Victory")],
+                }],
+                timestamp_ms: Some(100),
+            },
+            hand_landmark: HandLandmarkResult {
+                handedness: category("Left"),
+                hand_landmarks: Landmarks(vec![landmark]),
+                hand_world_landmarks: Landmarks(vec![]),
+            },
+        }]);
+        let json = serde_json::to_string(&results).unwrap();
+        let r: GestureRecognizerResults = serde_json::from_str(&json).unwrap();
+        assert_eq!(r.len(), 1);
+        assert_eq!(r[0].gestures.timestamp_ms, Some(100));
+        assert_eq!(
+            r[0].gestures.classifications[0].categories[0]
+                .category_name
+                .as_deref(),
+            Some("Victory")
+        );
+        assert_eq!(r[0].hand_landmark.hand_landmarks[0].z, -0.125);
+        assert!(r[0].hand_landmark.hand_world_landmarks.is_empty());
+        assert_eq!(serde_json::to_string(&r).unwrap(), json);
+    }
+}
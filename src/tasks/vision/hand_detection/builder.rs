@@ -1,18 +1,20 @@
 use super::HandDetector;
 use crate::model::ModelResourceTrait;
-use crate::postprocess::SsdAnchorsBuilder;
-use crate::tasks::common::BaseTaskOptions;
+use crate::postprocess::{SsdAnchorsBuilder, SsdDecodingOptions};
+use crate::tasks::common::{BaseTaskOptions, SsdAnchorsOptions};
 
 /// Configure the build options of a new **Hand Detection** task instance.
 ///
 /// Methods can be chained on it in order to configure it.
 pub struct HandDetectorBuilder {
-    pub(super) base_task_options: BaseTaskOptions,
+    pub(in super::super) base_task_options: BaseTaskOptions,
     /// The maximum number of hands output by the detector.
     pub(super) num_hands: i32,
     /// Minimum confidence value ([0.0, 1.0]) for confidence score to be considered
     /// successfully detecting a hand in the image.
     pub(super) min_detection_confidence: f32,
+    /// The anchors options to override the model metadata and defaults.
+    pub(super) ssd_anchors_options: SsdAnchorsOptions,
 }
 
 impl Default for HandDetectorBuilder {
@@ -30,11 +32,16 @@ impl HandDetectorBuilder {
             base_task_options: Default::default(),
             num_hands: -1,
             min_detection_confidence: 0.5,
+            ssd_anchors_options: Default::default(),
         }
     }
 
     base_task_options_impl!();
 
+    model_metadata_override_impl!();
+
+    ssd_anchors_options_impl!();
+
     /// Set the maximum number of hands can be detected by the HandDetector.
     /// Default is -1, (no limits)
     #[inline(always)]
@@ -61,35 +68,53 @@ impl HandDetectorBuilder {
         }
         let buf = base_task_options_check_and_get_buf!(self);
 
-        // change the lifetime to 'static, because the buf will move to graph and will not be released.
+        // change the lifetime to 'static, because the graph keeps the buf until the task is dropped.
         let model_resource_ref = crate::model::parse_model(buf.as_ref())?;
         let model_resource = unsafe {
             std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
         };
+        let model_resource = self
+            .base_task_options
+            .model_metadata_override
+            .apply(model_resource)?;
 
         // check model
         model_base_check_impl!(model_resource, 1, 2);
         let img_info =
             model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_image()?;
 
-        // generate anchors
-        // todo: read info from metadata
-        let num_box = 2016;
+        // generate anchors and decoding options
         let width = img_info.width();
         let height = img_info.height();
-        let anchors = SsdAnchorsBuilder::new(width, height, 0.1484375, 0.75, 4)
+        let default_anchors = SsdAnchorsBuilder::new(width, height, 0.1484375, 0.75, 4)
             .anchor_offset_x(0.5)
             .anchor_offset_y(0.5)
             .strides(vec![8, 16, 16, 16])
             .aspect_ratios(vec![1.0])
-            .fixed_anchor_size(true)
-            .generate();
+            .fixed_anchor_size(true);
+        let default_decoding_options = SsdDecodingOptions {
+            num_coords: 18,
+            keypoint_coord_offset: 4,
+            num_key_points: 7,
+            num_values_per_key_point: 2,
+            x_scale: width as f32,
+            y_scale: height as f32,
+            w_scale: width as f32,
+            h_scale: height as f32,
+            sigmoid_score: true,
+            ..Default::default()
+        };
+        let (anchors, decoding_options) = self.ssd_anchors_options.anchors_and_decoding_options(
+            model_resource.as_ref(),
+            0,
+            1,
+            default_anchors,
+            default_decoding_options,
+        )?;
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.execution_target,
-        )
-        .build_from_shared_slices([buf])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.model_backend(), buf)?;
 
         let input_tensor_type =
             model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
@@ -101,7 +126,7 @@ impl HandDetectorBuilder {
             anchors,
             location_buf_index: 0,
             score_buf_index: 1,
-            num_box,
+            decoding_options,
             input_tensor_type,
         });
     }
@@ -6,6 +6,8 @@ use crate::tasks::common::{BaseTaskOptions, HandLandmarkOptions};
 /// Configure the build options of a new **Hand Landmark** task instance.
 ///
 /// Methods can be chained on it in order to configure it.
+/// The image options which override the model metadata apply to both the hand detection model
+/// and the hand landmark model, the labels override the handedness labels.
 pub struct HandLandmarkerBuilder {
     pub(in super::super) base_task_options: BaseTaskOptions,
     pub(in super::super) hand_landmark_options: HandLandmarkOptions,
@@ -35,6 +37,8 @@ impl HandLandmarkerBuilder {
 
     hand_landmark_options_impl!();
 
+    model_metadata_override_impl!();
+
     pub const HAND_DETECTOR_CANDIDATE_NAMES: &'static [&'static str] = &["hand_detector.tflite"];
     pub const HAND_LANDMARKS_CANDIDATE_NAMES: &'static [&'static str] =
         &["hand_landmarks_detector.tflite"];
@@ -59,18 +63,28 @@ impl HandLandmarkerBuilder {
             "HandDetection"
         );
 
-        let subtask = HandDetectorBuilder::new()
+        let mut subtask_builder = HandDetectorBuilder::new()
             .model_asset_slice(hand_detection_file)
             .execution_target(self.base_task_options.execution_target)
+            .inference_backend(self.base_task_options.inference_backend.clone())
             .num_hands(self.hand_landmark_options.num_hands)
-            .min_detection_confidence(self.hand_landmark_options.min_hand_detection_confidence)
-            .finalize()?;
+            .min_detection_confidence(self.hand_landmark_options.min_hand_detection_confidence);
+        // the labels are the handedness labels of the landmark model
+        subtask_builder.base_task_options.model_metadata_override = self
+            .base_task_options
+            .model_metadata_override
+            .input_override();
+        let subtask = subtask_builder.finalize()?;
 
-        // change the lifetime to 'static, because the buf will move to graph and will not be released.
+        // change the lifetime to 'static, because the graph keeps the buf until the task is dropped.
         let model_resource_ref = crate::model::parse_model(landmark_file.as_ref())?;
         let model_resource = unsafe {
             std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
         };
+        let model_resource = self
+            .base_task_options
+            .model_metadata_override
+            .apply(model_resource)?;
 
         // check model
         model_base_check_impl!(model_resource, 1, 4);
@@ -97,11 +111,9 @@ impl HandLandmarkerBuilder {
             TensorType::F32
         );
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.execution_target,
-        )
-        .build_from_shared_slices([landmark_file])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.model_backend(), landmark_file)?;
 
         Ok(HandLandmarker {
             build_options: self,
@@ -6,6 +6,7 @@ use std::ops::{Deref, DerefMut};
 
 /// A single hand landmark detection result.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HandLandmarkResult {
     /// Classification of handedness.
     pub handedness: Category,
@@ -17,6 +18,7 @@ pub struct HandLandmarkResult {
 
 /// The hand landmarks detection result from HandLandmark
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HandLandmarkResults(pub Vec<HandLandmarkResult>);
 
 impl Deref for HandLandmarkResults {
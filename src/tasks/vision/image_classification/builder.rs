@@ -32,6 +32,8 @@ impl ImageClassifierBuilder {
 
     base_task_options_impl!();
 
+    model_metadata_override_impl!();
+
     classification_options_impl!();
 
     /// Use the build options to create a new task instance.
@@ -40,11 +42,15 @@ impl ImageClassifierBuilder {
         classification_options_check!(self, classification_options);
         let buf = base_task_options_check_and_get_buf!(self);
 
-        // change the lifetime to 'static, because the buf will move to graph and will not be released.
+        // change the lifetime to 'static, because the graph keeps the buf until the task is dropped.
         let model_resource_ref = crate::model::parse_model(buf.as_ref())?;
         let model_resource = unsafe {
             std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
         };
+        let model_resource = self
+            .base_task_options
+            .model_metadata_override
+            .apply(model_resource)?;
 
         // check model
         model_base_check_impl!(model_resource, 1, 1);
@@ -52,11 +58,9 @@ impl ImageClassifierBuilder {
         let input_tensor_type =
             model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.execution_target,
-        )
-        .build_from_shared_slices([buf])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.model_backend(), buf)?;
 
         return Ok(ImageClassifier {
             build_options: self,
@@ -85,4 +89,35 @@ mod test {
             .finalize()
             .is_err());
     }
+
+    #[test]
+    fn test_builder_model_metadata_override() {
+        let model = crate::model::metadata_writer::test::bare_model_with_types(
+            &[(&[1, 4, 4, 3], crate::TensorType::F32)],
+            &[(&[1, 2], crate::TensorType::F32)],
+        );
+        let backend = std::sync::Arc::new(crate::backend::ReplayBackend::new("replay"));
+        // the model has no image information
+        assert!(ImageClassifierBuilder::new()
+            .model_asset_buffer(model.clone())
+            .inference_backend(backend.clone())
+            .finalize()
+            .is_err());
+        let classifier = ImageClassifierBuilder::new()
+            .model_asset_buffer(model.clone())
+            .inference_backend(backend.clone())
+            .image_normalization(vec![127.5], vec![127.5])
+            .labels(vec!["cat".into(), "dog".into()])
+            .finalize()
+            .unwrap();
+        assert!(classifier.new_session().is_ok());
+        // the model has no labels
+        let classifier = ImageClassifierBuilder::new()
+            .model_asset_buffer(model)
+            .inference_backend(backend)
+            .image_normalization(vec![127.5], vec![127.5])
+            .finalize()
+            .unwrap();
+        assert!(classifier.new_session().is_err());
+    }
 }
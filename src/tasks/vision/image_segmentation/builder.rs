@@ -49,6 +49,8 @@ impl ImageSegmenterBuilder {
 
     base_task_options_impl!();
 
+    model_metadata_override_impl!();
+
     /// The locale to use for display names specified through the TFLite Model
     /// Metadata, if any. Defaults to English.
     #[inline(always)]
@@ -85,11 +87,15 @@ impl ImageSegmenterBuilder {
 
         let buf = base_task_options_check_and_get_buf!(self);
 
-        // change the lifetime to 'static, because the buf will move to graph and will not be released.
+        // change the lifetime to 'static, because the graph keeps the buf until the task is dropped.
         let model_resource_ref = crate::model::parse_model(buf.as_ref())?;
         let model_resource = unsafe {
             std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
         };
+        let model_resource = self
+            .base_task_options
+            .model_metadata_override
+            .apply(model_resource)?;
 
         // check model
         model_base_check_impl!(model_resource, 1, 1);
@@ -97,11 +103,9 @@ impl ImageSegmenterBuilder {
         let input_tensor_type =
             model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
 
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.execution_target,
-        )
-        .build_from_shared_slices([buf])?;
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.model_backend(), buf)?;
 
         let (label, label_locale) =
             model_resource.output_tensor_labels_locale(0, self.display_names_locale.as_str())?;
@@ -2,12 +2,65 @@ use crate::postprocess::{ImageCategoryMask, ImageConfidenceMask};
 
 /// The output result of Image Segmentation tasks.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageSegmentationResult {
     /// Multiple masks of float image in VEC32F1 format where, for each mask, each
     /// pixel represents the prediction confidence, usually in the [0, 1] range.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::postprocess::mask_serde::confidence_masks")
+    )]
     pub confidence_masks: Option<Vec<ImageConfidenceMask>>,
 
     /// A category mask of uint8 image in GRAY8 format where each pixel represents
     /// the class which the pixel in the original image was predicted to belong to.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::postprocess::mask_serde::category_mask")
+    )]
     pub category_mask: Option<ImageCategoryMask>,
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let result = ImageSegmentationResult {
+            confidence_masks: Some(vec![
+                ImageConfidenceMask::from_raw(2, 1, vec![0.25, 1.]).unwrap()
+            ]),
+            category_mask: Some(ImageCategoryMask::from_raw(1, 2, vec![3, 4]).unwrap()),
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            json,
+            r#"{"confidence_masks":[{"width":2,"height":1,"data":[0.25,1.0]}],"category_mask":{"width":1,"height":2,"data":[3,4]}}"#
+        );
+        let r: ImageSegmentationResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(r.confidence_masks, result.confidence_masks);
+        assert_eq!(r.category_mask, result.category_mask);
+
+        let r: ImageSegmentationResult =
+            serde_json::from_str(r#"{"confidence_masks":null,"category_mask":null}"#).unwrap();
+        assert!(r.confidence_masks.is_none() && r.category_mask.is_none());
+
+        // omitted fields are `None`
+        let result = ImageSegmentationResult {
+            confidence_masks: None,
+            category_mask: Some(ImageCategoryMask::from_raw(1, 2, vec![3, 4]).unwrap()),
+        };
+        let r: ImageSegmentationResult =
+            serde_json::from_str(r#"{"category_mask":{"width":1,"height":2,"data":[3,4]}}"#)
+                .unwrap();
+        assert_eq!(r.confidence_masks, result.confidence_masks);
+        assert_eq!(r.category_mask, result.category_mask);
+        let r: ImageSegmentationResult = serde_json::from_str("{}").unwrap();
+        assert!(r.confidence_masks.is_none() && r.category_mask.is_none());
+        assert!(serde_json::from_str::<ImageSegmentationResult>(
+            r#"{"confidence_masks":null,"category_mask":{"width":2,"height":2,"data":[3,4]}}"#
+        )
+        .is_err());
+    }
+}
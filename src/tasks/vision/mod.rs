@@ -7,7 +7,9 @@ mod image_embedding;
 mod image_segmentation;
 mod object_detection;
 
-pub use face_detection::{FaceDetector, FaceDetectorBuilder, FaceDetectorSession};
+pub use face_detection::{
+    FaceDetectionModelType, FaceDetector, FaceDetectorBuilder, FaceDetectorSession,
+};
 pub use gesture_recognition::{
     GestureRecognizer, GestureRecognizerBuilder, GestureRecognizerSession,
 };
@@ -1,5 +1,9 @@
 use super::ObjectDetector;
-use crate::model::ModelResourceTrait;
+use crate::model::{MemoryTextFile, ModelResourceTrait};
+use crate::postprocess::{
+    NonMaxSuppressionAlgorithm, NonMaxSuppressionOverlapType, SoftNmsDecay, YoloDecodingOptions,
+    YoloOutputLayout,
+};
 use crate::tasks::common::{BaseTaskOptions, ClassificationOptions};
 
 /// Configure the build options of a new **Object Detection** task instance.
@@ -8,15 +12,23 @@ use crate::tasks::common::{BaseTaskOptions, ClassificationOptions};
 pub struct ObjectDetectorBuilder {
     pub(super) base_task_options: BaseTaskOptions,
     pub(super) classification_options: ClassificationOptions,
+    /// The non-maximum-suppression algorithm.
+    pub(super) nms_algorithm: NonMaxSuppressionAlgorithm,
+    /// The overlap similarity used by non-maximum-suppression, `None` means the default of model type.
+    pub(super) nms_overlap_type: Option<NonMaxSuppressionOverlapType>,
+    /// Whether only suppress the boxes which have the same category.
+    pub(super) nms_per_class: bool,
+    /// The minimum non-maximum-suppression threshold for boxes to be considered overlapped,
+    /// `None` means the default of model type.
+    pub(super) min_suppression_threshold: Option<f32>,
+    /// Options to decode YOLO-family models, `None` means detecting from the model output shape.
+    pub(super) yolo_decoding_options: Option<YoloDecodingOptions>,
 }
 
 impl Default for ObjectDetectorBuilder {
     #[inline(always)]
     fn default() -> Self {
-        Self {
-            base_task_options: Default::default(),
-            classification_options: Default::default(),
-        }
+        Self::new()
     }
 }
 
@@ -27,37 +39,149 @@ impl ObjectDetectorBuilder {
         Self {
             base_task_options: Default::default(),
             classification_options: Default::default(),
+            nms_algorithm: NonMaxSuppressionAlgorithm::DEFAULT,
+            nms_overlap_type: None,
+            nms_per_class: false,
+            min_suppression_threshold: None,
+            yolo_decoding_options: None,
         }
     }
 
     base_task_options_impl!();
 
+    model_metadata_override_impl!();
+
     classification_options_impl!();
 
+    /// Set the non-maximum-suppression algorithm, such as ```WEIGHTED``` or Soft-NMS.
+    /// For Soft-NMS, boxes whose decayed score is below the score threshold are removed.
+    /// Default is ```DEFAULT```
+    #[inline(always)]
+    pub fn nms_algorithm(mut self, nms_algorithm: NonMaxSuppressionAlgorithm) -> Self {
+        self.nms_algorithm = nms_algorithm;
+        self
+    }
+
+    /// Set the overlap similarity used by non-maximum-suppression, such as IoU or DIoU.
+    /// Default is ```Jaccard```, and ```IntersectionOverUnion``` for YOLO-family models.
+    #[inline(always)]
+    pub fn nms_overlap_type(mut self, nms_overlap_type: NonMaxSuppressionOverlapType) -> Self {
+        self.nms_overlap_type = Some(nms_overlap_type);
+        self
+    }
+
+    /// Set whether only suppress the boxes which have the same category.
+    /// Default is false (class-agnostic)
+    #[inline(always)]
+    pub fn nms_per_class(mut self, nms_per_class: bool) -> Self {
+        self.nms_per_class = nms_per_class;
+        self
+    }
+
+    /// Set the minimum non-maximum-suppression threshold for boxes to be considered overlapped.
+    /// Default is 1.0, which means no boxes will be suppressed, because the models usually have done it.
+    /// For YOLO-family models, default is 0.45.
+    #[inline(always)]
+    pub fn min_suppression_threshold(mut self, min_suppression_threshold: f32) -> Self {
+        self.min_suppression_threshold = Some(min_suppression_threshold);
+        self
+    }
+
+    /// Decode the model output as YOLO-family single tensor, e.g. ```[1, num_boxes, 4 + num_classes]``` or transposed.
+    /// The fields that are ```None``` in options are detected from the model.
+    /// Default, models with a single output tensor shaped as above are detected as YOLO-family models.
+    ///
+    /// For YOLO-family models, the default score threshold is 0.25.
+    #[inline(always)]
+    pub fn yolo_decoding(mut self, yolo_decoding_options: YoloDecodingOptions) -> Self {
+        self.yolo_decoding_options = Some(yolo_decoding_options);
+        self
+    }
+
     /// Use the build options to create a new task instance.
     #[inline]
     pub fn finalize(mut self) -> Result<ObjectDetector, crate::Error> {
         classification_options_check!(self, classification_options);
+        if let NonMaxSuppressionAlgorithm::SOFT(SoftNmsDecay::Gaussian { sigma }) =
+            self.nms_algorithm
+        {
+            if sigma.is_nan() || sigma <= 0. {
+                return Err(crate::Error::ArgumentError(format!(
+                    "The sigma of Soft-NMS must be greater than 0, but got `{}`",
+                    sigma
+                )));
+            }
+        }
         let buf = base_task_options_check_and_get_buf!(self);
 
-        // change the lifetime to 'static, because the buf will move to graph and will not be released.
+        // change the lifetime to 'static, because the graph keeps the buf until the task is dropped.
         let model_resource_ref = crate::model::parse_model(buf.as_ref())?;
         let model_resource = unsafe {
             std::mem::transmute::<_, Box<dyn ModelResourceTrait + 'static>>(model_resource_ref)
         };
+        let model_resource = self
+            .base_task_options
+            .model_metadata_override
+            .apply(model_resource)?;
 
         // check model
-        model_base_check_impl!(model_resource, 1, 4);
         model_resource_check_and_get_impl!(model_resource, to_tensor_info, 0).try_to_image()?;
-
-        let graph = crate::GraphBuilder::new(
-            model_resource.model_backend(),
-            self.base_task_options.execution_target,
-        )
-        .build_from_shared_slices([buf])?;
-
         let input_tensor_type =
             model_resource_check_and_get_impl!(model_resource, input_tensor_type, 0);
+
+        // YOLO-family models have only one output tensor
+        let is_yolo = self.yolo_decoding_options.is_some()
+            || (model_resource.output_tensor_count() == 1
+                && model_resource
+                    .output_tensor_shape(0)
+                    .is_some_and(YoloOutputLayout::is_yolo_shape));
+        if is_yolo {
+            model_base_check_impl!(model_resource, 1, 1);
+            let shape = model_resource_check_and_get_impl!(model_resource, output_tensor_shape, 0);
+            // raw YOLO exports have no labels, the number of classes is detected from the shape.
+            let mut num_labels = 0;
+            match model_resource.output_tensor_labels_locale(
+                0,
+                self.classification_options.display_names_locale.as_str(),
+            ) {
+                Ok((labels, _)) => {
+                    let mut labels_file = MemoryTextFile::new(labels);
+                    while labels_file.next_line().is_some() {
+                        num_labels += 1;
+                    }
+                }
+                Err(crate::Error::ModelInconsistentError(_)) => {}
+                Err(e) => return Err(e),
+            }
+            let yolo_layout = YoloOutputLayout::new(
+                shape,
+                num_labels,
+                model_resource.model_backend(),
+                &self.yolo_decoding_options.unwrap_or_default(),
+            )?;
+
+            let graph = self
+                .base_task_options
+                .build_graph(model_resource.model_backend(), buf)?;
+            return Ok(ObjectDetector {
+                build_options: self,
+                model_resource,
+                graph,
+                bound_box_properties: [0, 1, 2, 3],
+                location_buf_index: 0,
+                categories_buf_index: 0,
+                score_buf_index: 0,
+                num_box_buf_index: 0,
+                yolo_layout: Some(yolo_layout),
+                input_tensor_type,
+            });
+        }
+
+        model_base_check_impl!(model_resource, 1, 4);
+        let graph = self
+            .base_task_options
+            .build_graph(model_resource.model_backend(), buf)?;
+
         let location_buf_index = model_resource_check_and_get_impl!(
             model_resource,
             output_tensor_name_to_index,
@@ -110,7 +234,85 @@ impl ObjectDetectorBuilder {
             categories_buf_index,
             score_buf_index,
             num_box_buf_index,
+            yolo_layout: None,
             input_tensor_type,
         });
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_builder_yolo() {
+        let backend = std::sync::Arc::new(crate::backend::ReplayBackend::new("replay"));
+        let build = |output_shape: &[i32], builder: ObjectDetectorBuilder| {
+            let model = crate::model::metadata_writer::test::bare_model_with_types(
+                &[(&[1, 32, 32, 3], crate::TensorType::F32)],
+                &[(output_shape, crate::TensorType::F32)],
+            );
+            builder
+                .model_asset_buffer(model)
+                .inference_backend(backend.clone())
+                .image_normalization(vec![0.], vec![255.])
+                .labels(vec!["cat".into(), "dog".into()])
+                .finalize()
+        };
+
+        // yolov8 layout
+        let detector = build(&[1, 6, 21], ObjectDetectorBuilder::new()).unwrap();
+        assert!(detector.is_yolo());
+        assert_eq!(detector.yolo_layout.unwrap().num_boxes, 21);
+        assert!(!detector.yolo_layout.unwrap().objectness);
+        assert_eq!(
+            detector.nms_overlap_type(),
+            NonMaxSuppressionOverlapType::IntersectionOverUnion
+        );
+        assert_eq!(detector.min_suppression_threshold(), 0.45);
+        assert!(detector.new_session().is_ok());
+
+        // yolov5 layout
+        let detector = build(&[1, 21, 7], ObjectDetectorBuilder::new()).unwrap();
+        assert!(detector.yolo_layout.unwrap().objectness);
+
+        // options
+        let detector = build(
+            &[1, 7, 6],
+            ObjectDetectorBuilder::new()
+                .yolo_decoding(YoloDecodingOptions {
+                    transposed: Some(true),
+                    ..Default::default()
+                })
+                .min_suppression_threshold(0.6),
+        )
+        .unwrap();
+        assert_eq!(detector.yolo_layout.unwrap().num_boxes, 6);
+        assert_eq!(detector.min_suppression_threshold(), 0.6);
+        assert!(build(
+            &[1, 2],
+            ObjectDetectorBuilder::new().yolo_decoding(Default::default())
+        )
+        .is_err());
+
+        // not a yolo model, and do not have 4 outputs
+        assert!(build(&[1, 2], ObjectDetectorBuilder::new()).is_err());
+
+        // raw yolo exports without labels
+        let model = crate::model::metadata_writer::test::bare_model_with_types(
+            &[(&[1, 32, 32, 3], crate::TensorType::F32)],
+            &[(&[1, 84, 8400], crate::TensorType::F32)],
+        );
+        let detector = ObjectDetectorBuilder::new()
+            .model_asset_buffer(model)
+            .inference_backend(backend.clone())
+            .image_normalization(vec![0.], vec![255.])
+            .finalize()
+            .unwrap();
+        assert_eq!(detector.yolo_layout.unwrap().num_classes, 80);
+        assert!(!detector.yolo_layout.unwrap().objectness);
+        // the coordinates space is decided when building
+        assert!(detector.yolo_layout.unwrap().normalized);
+        assert!(detector.new_session().is_ok());
+    }
+}
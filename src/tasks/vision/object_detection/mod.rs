@@ -2,7 +2,10 @@ mod builder;
 pub use builder::ObjectDetectorBuilder;
 
 use crate::model::ModelResourceTrait;
-use crate::postprocess::{CategoriesFilter, DetectionResult, TensorsToDetection};
+use crate::postprocess::{
+    CategoriesFilter, DetectionResult, NonMaxSuppression, NonMaxSuppressionAlgorithm,
+    NonMaxSuppressionOverlapType, TensorsToDetection, YoloOutputLayout, YoloToDetection,
+};
 use crate::preprocess::vision::ImageToTensorInfo;
 use crate::{Error, Graph, GraphExecutionContext, TensorType};
 
@@ -17,6 +20,8 @@ pub struct ObjectDetector {
     categories_buf_index: usize,
     score_buf_index: usize,
     num_box_buf_index: usize,
+    /// Output layout of YOLO-family models, which have only one output tensor.
+    yolo_layout: Option<YoloOutputLayout>,
     // only one input and one output
     input_tensor_type: TensorType,
 }
@@ -26,6 +31,56 @@ impl ObjectDetector {
 
     detector_impl!(ObjectDetectorSession, DetectionResult);
 
+    /// Get the non-maximum-suppression algorithm.
+    #[inline(always)]
+    pub fn nms_algorithm(&self) -> NonMaxSuppressionAlgorithm {
+        self.build_options.nms_algorithm
+    }
+
+    /// Get the overlap similarity used by non-maximum-suppression.
+    #[inline(always)]
+    pub fn nms_overlap_type(&self) -> NonMaxSuppressionOverlapType {
+        match self.build_options.nms_overlap_type {
+            Some(t) => t,
+            None if self.is_yolo() => NonMaxSuppressionOverlapType::IntersectionOverUnion,
+            None => NonMaxSuppressionOverlapType::Jaccard,
+        }
+    }
+
+    /// Get whether only suppress the boxes which have the same category.
+    #[inline(always)]
+    pub fn nms_per_class(&self) -> bool {
+        self.build_options.nms_per_class
+    }
+
+    /// Get the minimum non-maximum-suppression threshold for boxes to be considered overlapped.
+    #[inline(always)]
+    pub fn min_suppression_threshold(&self) -> f32 {
+        match self.build_options.min_suppression_threshold {
+            Some(t) => t,
+            None if self.is_yolo() => Self::YOLO_MIN_SUPPRESSION_THRESHOLD,
+            None => 1.0,
+        }
+    }
+
+    /// Return true if the model is decoded as a YOLO-family model.
+    #[inline(always)]
+    pub fn is_yolo(&self) -> bool {
+        self.yolo_layout.is_some()
+    }
+
+    const YOLO_MIN_SUPPRESSION_THRESHOLD: f32 = 0.45;
+    const YOLO_SCORE_THRESHOLD: f32 = 0.25;
+
+    #[inline(always)]
+    fn config_nms(&self, nms: &mut NonMaxSuppression, score_threshold: f32) {
+        nms.set_algorithm(self.nms_algorithm());
+        nms.set_overlap_type(self.nms_overlap_type());
+        nms.set_per_class(self.nms_per_class());
+        nms.set_min_suppression_threshold(self.min_suppression_threshold());
+        nms.set_min_score_threshold(score_threshold.max(0.));
+    }
+
     /// Create a new task session that contains processing buffers and can do inference.
     #[inline(always)]
     pub fn new_session(&self) -> Result<ObjectDetectorSession, Error> {
@@ -40,27 +95,62 @@ impl ObjectDetector {
                 .classification_options
                 .display_names_locale
                 .as_ref(),
-        )?;
-
-        let categories_filter = CategoriesFilter::new(
-            &self.build_options.classification_options,
-            labels.0,
-            labels.1,
         );
-        let mut tensors_to_detection = TensorsToDetection::new(
-            categories_filter,
-            self.build_options.classification_options.max_results,
-            get_type_and_quantization!(self.model_resource, self.location_buf_index),
-            get_type_and_quantization!(self.model_resource, self.categories_buf_index),
-            get_type_and_quantization!(self.model_resource, self.score_buf_index),
-        );
-        tensors_to_detection.set_box_indices(&self.bound_box_properties);
+
+        let mut categories_filter = match (labels, self.yolo_layout) {
+            (Ok(labels), _) => CategoriesFilter::new(
+                &self.build_options.classification_options,
+                labels.0,
+                labels.1,
+            ),
+            // YOLO models without labels, the categories only have indices.
+            (Err(Error::ModelInconsistentError(_)), Some(yolo_layout)) => {
+                CategoriesFilter::new_without_labels(
+                    &self.build_options.classification_options,
+                    yolo_layout.num_classes,
+                )
+            }
+            (Err(e), _) => return Err(e),
+        };
+        let mut score_threshold = self.build_options.classification_options.score_threshold;
+        let decoder = if let Some(yolo_layout) = self.yolo_layout {
+            if score_threshold < 0. {
+                score_threshold = Self::YOLO_SCORE_THRESHOLD;
+                categories_filter.set_score_threshold(score_threshold);
+            }
+            let mut yolo_to_detection = YoloToDetection::new(
+                categories_filter,
+                self.build_options.classification_options.max_results,
+                get_type_and_quantization!(self.model_resource, 0),
+                yolo_layout,
+                image_to_tensor_info.width(),
+                image_to_tensor_info.height(),
+            );
+            self.config_nms(yolo_to_detection.nms_mut(), score_threshold);
+            DetectionDecoder::Yolo(yolo_to_detection)
+        } else {
+            let mut tensors_to_detection = TensorsToDetection::new(
+                categories_filter,
+                self.build_options.classification_options.max_results,
+                get_type_and_quantization!(self.model_resource, self.location_buf_index),
+                get_type_and_quantization!(self.model_resource, self.categories_buf_index),
+                get_type_and_quantization!(self.model_resource, self.score_buf_index),
+            );
+            tensors_to_detection.set_box_indices(&self.bound_box_properties);
+            tensors_to_detection.set_nms_algorithm(self.nms_algorithm());
+            tensors_to_detection.set_nms_overlap_type(self.nms_overlap_type());
+            tensors_to_detection.set_nms_per_class(self.nms_per_class());
+            tensors_to_detection
+                .set_nms_min_suppression_threshold(self.min_suppression_threshold());
+            tensors_to_detection.set_nms_min_score_threshold(score_threshold.max(0.));
+            DetectionDecoder::Tensors(Box::new(tensors_to_detection))
+        };
 
         let execution_ctx = self.graph.init_execution_context()?;
         Ok(ObjectDetectorSession {
             detector: self,
             execution_ctx,
-            tensors_to_detection,
+            decoder,
             num_box_buf: [0f32],
             image_to_tensor_info,
             input_tensor_shape,
@@ -69,6 +159,14 @@ impl ObjectDetector {
     }
 }
 
+/// Convert model outputs to detection results.
+enum DetectionDecoder<'model> {
+    /// MediaPipe object detection models: location, category, score and number of boxes.
+    Tensors(Box<TensorsToDetection<'model>>),
+    /// YOLO-family models: single output tensor.
+    Yolo(YoloToDetection<'model>),
+}
+
 /// Session to run inference.
 /// If process multiple images or videos, reuse it can get better performance.
 ///
@@ -84,7 +182,7 @@ impl ObjectDetector {
 pub struct ObjectDetectorSession<'model> {
     detector: &'model ObjectDetector,
     execution_ctx: GraphExecutionContext<'model>,
-    tensors_to_detection: TensorsToDetection<'model>,
+    decoder: DetectionDecoder<'model>,
 
     image_to_tensor_info: &'model ImageToTensorInfo,
     num_box_buf: [f32; 1],
@@ -105,6 +203,15 @@ impl<'model> ObjectDetectorSession<'model> {
         )?;
         self.execution_ctx.compute()?;
 
+        let tensors_to_detection = match self.decoder {
+            DetectionDecoder::Tensors(ref mut t) => t,
+            DetectionDecoder::Yolo(ref mut yolo_to_detection) => {
+                self.execution_ctx
+                    .get_output(0, yolo_to_detection.output_buf())?;
+                return Ok(yolo_to_detection.result());
+            }
+        };
+
         // get num box
         let output_size = self
             .execution_ctx
@@ -118,24 +225,24 @@ impl<'model> ObjectDetectorSession<'model> {
         let num_box = self.num_box_buf[0].round() as usize;
 
         // realloc
-        self.tensors_to_detection.realloc(num_box);
+        tensors_to_detection.realloc(num_box);
 
         // get other buffers
         self.execution_ctx.get_output(
             self.detector.location_buf_index,
-            self.tensors_to_detection.location_buf(),
+            tensors_to_detection.location_buf(),
         )?;
         self.execution_ctx.get_output(
             self.detector.categories_buf_index,
-            self.tensors_to_detection.categories_buf().unwrap(),
+            tensors_to_detection.categories_buf().unwrap(),
         )?;
         self.execution_ctx.get_output(
             self.detector.score_buf_index,
-            self.tensors_to_detection.score_buf(),
+            tensors_to_detection.score_buf(),
         )?;
 
         // generate result
-        Ok(self.tensors_to_detection.result(num_box))
+        Ok(tensors_to_detection.result(num_box))
     }
 
     detector_session_impl!(DetectionResult);
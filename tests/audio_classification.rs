@@ -1,3 +1,5 @@
+mod common;
+
 use mediapipe_rs::preprocess::audio::{AudioData, SymphoniaAudioData};
 use mediapipe_rs::tasks::audio::AudioClassifierBuilder;
 use symphonia::core::io::MediaSourceStream;
@@ -25,7 +27,7 @@ fn test_audio_classification() {
         .unwrap();
     let input = SymphoniaAudioData::new(probed.format, decoder);
 
-    audio_classification_task_run(MODEL_1, input);
+    audio_classification_task_run("audio_classification_symphonia", MODEL_1, input);
 }
 
 #[cfg(feature = "ffmpeg")]
@@ -38,11 +40,12 @@ fn test_ffmpeg() {
     )
     .unwrap();
 
-    audio_classification_task_run(MODEL_1, input);
+    audio_classification_task_run("audio_classification_ffmpeg", MODEL_1, input);
 }
 
-fn audio_classification_task_run(model_asset_path: &str, input: impl AudioData) {
+fn audio_classification_task_run(test_name: &str, model_asset_path: &str, input: impl AudioData) {
     let classification_list = AudioClassifierBuilder::new()
+        .inference_backend(common::inference_backend(test_name))
         .model_asset_path(model_asset_path)
         .cpu()
         .max_results(1)
@@ -0,0 +1,24 @@
+use mediapipe_rs::backend::{InferenceBackend, ReplayKey};
+use std::sync::Arc;
+
+/// The model outputs recorded by running the tests in WasmEdge.
+pub const RECORDINGS_DIR: &str = "assets/testdata/recordings";
+
+/// Get the inference backend of a test, every test uses its own recordings directory.
+///
+/// In WasmEdge (```wasm32```), the models run by WASI-NN and the outputs are recorded. Otherwise, the recorded outputs
+/// are replayed, so the tests can run without WasmEdge. The recordings are found by the order of inferences instead of
+/// input hashes, because the floating point inputs may be slightly different on different platforms.
+pub fn inference_backend(test_name: &str) -> Arc<dyn InferenceBackend> {
+    let dir = std::path::Path::new(RECORDINGS_DIR).join(test_name);
+    #[cfg(target_arch = "wasm32")]
+    return Arc::new(
+        mediapipe_rs::backend::RecordingBackend::new(
+            Arc::new(mediapipe_rs::backend::WasiNNBackend),
+            dir,
+        )
+        .key(ReplayKey::CallOrder),
+    );
+    #[cfg(not(target_arch = "wasm32"))]
+    return Arc::new(mediapipe_rs::backend::ReplayBackend::new(dir).key(ReplayKey::CallOrder));
+}
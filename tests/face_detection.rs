@@ -1,6 +1,10 @@
-use mediapipe_rs::tasks::vision::FaceDetectorBuilder;
+mod common;
+
+use mediapipe_rs::tasks::vision::{FaceDetectionModelType, FaceDetectorBuilder};
 
 const MODEL_1: &'static str = "assets/models/face_detection/face_detection_short_range.tflite";
+const MODEL_2: &'static str =
+    "assets/models/face_detection/face_detection_full_range_sparse.tflite";
 
 const FACE_IMG_1: &'static str = "assets/testdata/img/face.jpg";
 
@@ -9,14 +13,25 @@ fn test_face_detection_model_1() {
     face_detection_task_run(MODEL_1)
 }
 
+#[test]
+fn test_face_detection_model_2() {
+    face_detection_task_run(MODEL_2)
+}
+
 fn face_detection_task_run(model_path: &str) {
     let img = image::open(FACE_IMG_1).unwrap();
-    let face_detection_result = FaceDetectorBuilder::new()
+    let face_detector = FaceDetectorBuilder::new()
+        .inference_backend(common::inference_backend("face_detection"))
         .model_asset_path(model_path)
         .finalize()
-        .unwrap()
-        .detect(&img)
         .unwrap();
+    let expect_model_type = if model_path == MODEL_2 {
+        FaceDetectionModelType::FullRange
+    } else {
+        FaceDetectionModelType::ShortRange
+    };
+    assert_eq!(face_detector.model_type(), expect_model_type);
+    let face_detection_result = face_detector.detect(&img).unwrap();
     eprintln!("{}", face_detection_result);
 
     let draw = false;
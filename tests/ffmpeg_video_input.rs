@@ -1,9 +1,16 @@
+#[cfg(feature = "ffmpeg")]
+mod common;
+
 #[cfg(feature = "ffmpeg")]
 mod ffmpeg {
-    use mediapipe_rs::preprocess::vision::FFMpegVideoData;
+    use mediapipe_rs::postprocess::utils::{
+        annotate_video, draw_detection, FFMpegVideoWriter, FFMpegVideoWriterOptions, VideoCodec,
+    };
+    use mediapipe_rs::preprocess::vision::{FFMpegVideoData, ImageToTensor, VideoData};
     use mediapipe_rs::tasks::vision::{
         ImageClassifierBuilder, ImageProcessingOptions, ObjectDetectorBuilder,
     };
+    use mediapipe_rs::Error;
 
     const IMAGE_CLASSIFICATION_MODEL: &'static str =
         "assets/models/image_classification/efficientnet_lite0_fp32.tflite";
@@ -12,12 +19,73 @@ mod ffmpeg {
 
     const VIDEO_1: &'static str = "assets/testdata/video/banana_clock_tabby.mp4";
 
+    // the timestamps (ms) of the output frames
+    fn output_timestamps(mut input: FFMpegVideoData) -> Vec<Option<u64>> {
+        let mut timestamps = Vec::new();
+        while let Some(frame) = input.next_frame().unwrap() {
+            assert_eq!(frame.image_size(), (640, 640));
+            timestamps.push(frame.timestamp_ms());
+        }
+        timestamps
+    }
+
+    fn video_1() -> FFMpegVideoData {
+        FFMpegVideoData::new(ffmpeg_next::format::input(&VIDEO_1).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_frame_sampling() {
+        ffmpeg_next::init().unwrap();
+        // the video has 3 frames, 10 ms per frame
+        assert_eq!(
+            output_timestamps(video_1()),
+            vec![Some(0), Some(10), Some(20)]
+        );
+
+        assert_eq!(
+            output_timestamps(video_1().every_nth_frame(2)),
+            vec![Some(0), Some(20)]
+        );
+        assert_eq!(
+            output_timestamps(video_1().target_fps(50.)),
+            vec![Some(0), Some(20)]
+        );
+        assert_eq!(
+            output_timestamps(video_1().target_fps(200.)),
+            vec![Some(0), Some(10), Some(20)]
+        );
+        assert_eq!(
+            output_timestamps(video_1().start_ms(10)),
+            vec![Some(10), Some(20)]
+        );
+        assert_eq!(
+            output_timestamps(video_1().end_ms(10)),
+            vec![Some(0), Some(10)]
+        );
+        assert_eq!(
+            output_timestamps(video_1().start_ms(10).end_ms(10)),
+            vec![Some(10)]
+        );
+
+        let mut input = video_1().max_frames(2);
+        assert_eq!(input.next_frame().unwrap().unwrap().timestamp_ms(), Some(0));
+        assert_eq!(
+            input.next_frame().unwrap().unwrap().timestamp_ms(),
+            Some(10)
+        );
+        assert!(input.next_frame().unwrap().is_none());
+        assert_eq!(input.num_output_frames(), 2);
+    }
+
     #[test]
     fn test_image_classification() {
         ffmpeg_next::init().unwrap();
         let input = FFMpegVideoData::new(ffmpeg_next::format::input(&VIDEO_1).unwrap()).unwrap();
 
         let classification_results = ImageClassifierBuilder::new()
+            .inference_backend(crate::common::inference_backend(
+                "ffmpeg_image_classification",
+            ))
             .model_asset_path(IMAGE_CLASSIFICATION_MODEL)
             .max_results(1)
             .finalize()
@@ -45,6 +113,7 @@ mod ffmpeg {
         let input = FFMpegVideoData::new(ffmpeg_next::format::input(&VIDEO_1).unwrap()).unwrap();
 
         let detection_results = ObjectDetectorBuilder::new()
+            .inference_backend(crate::common::inference_backend("ffmpeg_object_detection"))
             .model_asset_path(OBJECT_DETECTION_MODEL)
             .max_results(1)
             .finalize()
@@ -76,6 +145,7 @@ mod ffmpeg {
         let input_2 = FFMpegVideoData::new(ffmpeg_next::format::input(&VIDEO_1).unwrap()).unwrap();
 
         let classifier = ImageClassifierBuilder::new()
+            .inference_backend(crate::common::inference_backend("ffmpeg_results_iter"))
             .model_asset_path(IMAGE_CLASSIFICATION_MODEL)
             .max_results(1)
             .finalize()
@@ -101,4 +171,129 @@ mod ffmpeg {
             img_options = img_options.rotation_degrees(num_frame * 90).unwrap();
         }
     }
+
+    // write frames with the codec, then read the video and check the frames
+    fn check_video_writer(path: &str, codec: VideoCodec) {
+        ffmpeg_next::init().unwrap();
+        const WIDTH: u32 = 64;
+        const HEIGHT: u32 = 48;
+        let timestamps = [0, 40, 80, 120, 200];
+
+        let mut writer = FFMpegVideoWriter::with_options(
+            path,
+            WIDTH,
+            HEIGHT,
+            FFMpegVideoWriterOptions::new().codec(codec),
+        )
+        .unwrap();
+        for (i, t) in timestamps.iter().enumerate() {
+            let img = image::RgbImage::from_pixel(WIDTH, HEIGHT, image::Rgb([i as u8 * 50, 0, 0]));
+            writer.write_frame(&img, *t).unwrap();
+        }
+        assert_eq!(writer.num_frames(), timestamps.len());
+        assert_eq!(writer.size(), (WIDTH, HEIGHT));
+        writer.finish().unwrap();
+
+        let mut input = FFMpegVideoData::new(ffmpeg_next::format::input(&path).unwrap()).unwrap();
+        let mut output_timestamps = Vec::new();
+        while let Some(frame) = input.next_frame().unwrap() {
+            assert_eq!(frame.image_size(), (WIDTH, HEIGHT));
+            output_timestamps.push(frame.timestamp_ms().unwrap());
+        }
+        assert_eq!(output_timestamps, timestamps, "{:?}", codec);
+    }
+
+    #[test]
+    fn test_video_writer_h264() {
+        assert_eq!(VideoCodec::from_path("a.mp4"), VideoCodec::H264);
+        check_video_writer("./target/video_writer_test.mp4", VideoCodec::H264);
+    }
+
+    #[test]
+    fn test_video_writer_vp9() {
+        assert_eq!(VideoCodec::from_path("a.webm"), VideoCodec::VP9);
+        check_video_writer("./target/video_writer_test.webm", VideoCodec::VP9);
+    }
+
+    #[test]
+    fn test_video_writer_mjpeg() {
+        assert_eq!(VideoCodec::from_path("a.AVI"), VideoCodec::MJPEG);
+        check_video_writer("./target/video_writer_test.avi", VideoCodec::MJPEG);
+    }
+
+    #[test]
+    fn test_video_writer_errors() {
+        ffmpeg_next::init().unwrap();
+        // yuv420p needs even size
+        assert!(matches!(
+            FFMpegVideoWriter::new("./target/video_writer_odd.mp4", 63, 48),
+            Err(Error::ArgumentError(_))
+        ));
+        assert!(matches!(
+            FFMpegVideoWriter::with_options(
+                "./target/video_writer_odd.webm",
+                64,
+                47,
+                FFMpegVideoWriterOptions::new().codec(VideoCodec::VP9)
+            ),
+            Err(Error::ArgumentError(_))
+        ));
+
+        let mut writer =
+            FFMpegVideoWriter::new("./target/video_writer_errors.mp4", 64, 48).unwrap();
+        let img = image::RgbImage::new(64, 48);
+        writer.write_frame(&img, 40).unwrap();
+        // timestamps must be strictly increasing
+        assert!(matches!(
+            writer.write_frame(&img, 40),
+            Err(Error::ArgumentError(_))
+        ));
+        assert!(matches!(
+            writer.write_frame(&img, 20),
+            Err(Error::ArgumentError(_))
+        ));
+        // frame size must be the video size
+        assert!(matches!(
+            writer.write_frame(&image::RgbImage::new(32, 48), 80),
+            Err(Error::ArgumentError(_))
+        ));
+        writer.write_frame(&img, 80).unwrap();
+        assert_eq!(writer.num_frames(), 2);
+        writer.finish().unwrap();
+        assert!(matches!(
+            writer.write_frame(&img, 120),
+            Err(Error::ArgumentError(_))
+        ));
+    }
+
+    #[test]
+    fn test_annotate_video() {
+        ffmpeg_next::init().unwrap();
+        const OUTPUT: &str = "./target/annotate_video_test.mp4";
+
+        let detector = ObjectDetectorBuilder::new()
+            .inference_backend(crate::common::inference_backend("ffmpeg_annotate_video"))
+            .model_asset_path(OBJECT_DETECTION_MODEL)
+            .max_results(1)
+            .finalize()
+            .unwrap();
+        let mut session = detector.new_session().unwrap();
+        let mut num_drawn = 0;
+        let num_frames = annotate_video(
+            &mut session,
+            video_1(),
+            OUTPUT,
+            FFMpegVideoWriterOptions::new(),
+            |img, result| {
+                num_drawn += 1;
+                draw_detection(img, result);
+            },
+        )
+        .unwrap();
+        assert_eq!(num_frames, 3);
+        assert_eq!(num_drawn, 3);
+
+        let output = FFMpegVideoData::new(ffmpeg_next::format::input(&OUTPUT).unwrap()).unwrap();
+        assert_eq!(output_timestamps(output), output_timestamps(video_1()));
+    }
 }
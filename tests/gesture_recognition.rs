@@ -1,3 +1,5 @@
+mod common;
+
 use mediapipe_rs::tasks::vision::GestureRecognizerBuilder;
 
 const MODEL_ASSET: &'static str = "assets/models/gesture_recognition/gesture_recognizer.task";
@@ -14,6 +16,7 @@ const POINTING_UP_IMG: &'static str =
 #[test]
 fn test_gesture_recognition() {
     let gesture_recognizer = GestureRecognizerBuilder::new()
+        .inference_backend(common::inference_backend("gesture_recognition"))
         .num_hands(1)
         .max_results(1)
         .model_asset_path(MODEL_ASSET)
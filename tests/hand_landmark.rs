@@ -1,3 +1,5 @@
+mod common;
+
 use mediapipe_rs::tasks::vision::HandLandmarkerBuilder;
 
 const MODEL_PATH: &'static str = "assets/models/hand_landmark_detection/hand_landmarker.task";
@@ -6,6 +8,7 @@ const HANDS_1: &'static str = "assets/testdata/img/google_sample_woman_hands.jpg
 #[test]
 fn test_hand_detection() {
     let hand_landmarker = HandLandmarkerBuilder::new()
+        .inference_backend(common::inference_backend("hand_detection"))
         .model_asset_path(MODEL_PATH)
         .num_hands(5)
         .finalize()
@@ -21,6 +24,7 @@ fn test_hand_detection() {
 fn test_hand_landmark() {
     let img = image::open(HANDS_1).unwrap();
     let hand_landmark_results = HandLandmarkerBuilder::new()
+        .inference_backend(common::inference_backend("hand_landmark"))
         .model_asset_path(MODEL_PATH)
         .cpu()
         .num_hands(10)
@@ -1,3 +1,5 @@
+mod common;
+
 use mediapipe_rs::tasks::vision::{ImageClassifierBuilder, ImageProcessingOptions};
 
 const MODEL_1: &'static str = "assets/models/image_classification/efficientnet_lite0_fp32.tflite";
@@ -29,6 +31,7 @@ fn test_image_classification_model_4() {
 
 fn image_classification_task_run(model_asset_path: &str) {
     let image_classifier = ImageClassifierBuilder::new()
+        .inference_backend(common::inference_backend("image_classification"))
         .model_asset_path(model_asset_path)
         .cpu()
         .max_results(2)
@@ -58,6 +61,7 @@ fn test_bird_from_tf_hub() {
     const IMAGE: &'static str = "assets/testdata/img/bird.jpg";
 
     let res = ImageClassifierBuilder::new()
+        .inference_backend(common::inference_backend("image_classification_bird"))
         .model_asset_path(MODEL)
         .max_results(2)
         .finalize()
@@ -86,6 +90,9 @@ fn test_bird_from_tf_hub() {
 #[test]
 fn test_classify_with_options() {
     let classifier = ImageClassifierBuilder::new()
+        .inference_backend(common::inference_backend(
+            "image_classification_with_options",
+        ))
         .max_results(1)
         .model_asset_path(MODEL_1)
         .finalize()
@@ -1,3 +1,5 @@
+mod common;
+
 use mediapipe_rs::tasks::vision::ImageEmbedderBuilder;
 
 const MODEL_1: &'static str =
@@ -20,6 +22,7 @@ fn test_image_embedding_model_2() {
 
 fn image_embedding_tasks_run(model_asset: &str) {
     let image_embedder = ImageEmbedderBuilder::new()
+        .inference_backend(common::inference_backend("image_embedding"))
         .model_asset_path(model_asset)
         .l2_normalize(true)
         .quantize(true)
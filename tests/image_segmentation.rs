@@ -1,3 +1,5 @@
+mod common;
+
 use mediapipe_rs::postprocess::ImageCategoryMask;
 use mediapipe_rs::tasks::vision::ImageSegmenterBuilder;
 
@@ -19,6 +21,7 @@ fn test_image_segmentation_model_2() {
 fn test_image_segmentation_tasks(model_asset: &str) {
     let img = image::open(IMG_1).unwrap();
     let segmentation_res = ImageSegmenterBuilder::new()
+        .inference_backend(common::inference_backend("image_segmentation"))
         .model_asset_path(model_asset)
         .output_confidence_masks(false)
         .output_category_mask(true)
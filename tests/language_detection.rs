@@ -0,0 +1,49 @@
+mod common;
+
+use mediapipe_rs::tasks::text::LanguageDetectorBuilder;
+
+const MODEL_1: &'static str = "assets/models/language_detection/language_detector.tflite";
+
+#[test]
+fn test_language_detection_model_1() {
+    let language_detector = LanguageDetectorBuilder::new()
+        .inference_backend(common::inference_backend("language_detection"))
+        .model_asset_path(MODEL_1)
+        .max_results(1)
+        .finalize()
+        .unwrap();
+    let mut session = language_detector.new_session().unwrap();
+
+    let inputs = [
+        ("To be, or not to be, that is the question", "en"),
+        (
+            "Il y a beaucoup de bouches qui parlent et fort peu de têtes qui pensent.",
+            "fr",
+        ),
+        ("分久必合合久必分", "zh"),
+    ];
+    for (text, language_code) in inputs {
+        let result = session.detect(&text).unwrap();
+        eprintln!("`{}` -- {}", text, result);
+        assert_eq!(result.predictions.len(), 1);
+        assert_eq!(result.predictions[0].language_code, language_code);
+    }
+}
+
+#[test]
+fn test_language_detection_score_threshold() {
+    let result = LanguageDetectorBuilder::new()
+        .inference_backend(common::inference_backend(
+            "language_detection_score_threshold",
+        ))
+        .model_asset_path(MODEL_1)
+        .score_threshold(0.3)
+        .finalize()
+        .unwrap()
+        .detect(&"It is a beautiful day")
+        .unwrap();
+    assert!(!result.predictions.is_empty());
+    for p in result.predictions {
+        assert!(p.probability >= 0.3);
+    }
+}
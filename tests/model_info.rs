@@ -0,0 +1,95 @@
+use mediapipe_rs::model::ModelInfo;
+use mediapipe_rs::TensorType;
+
+const IMAGE_CLASSIFICATION_MODEL: &'static str =
+    "assets/models/image_classification/efficientnet_lite0_uint8.tflite";
+const TEXT_CLASSIFICATION_MODEL: &'static str =
+    "assets/models/text_classification/bert_text_classifier.tflite";
+const HAND_LANDMARK_MODEL: &'static str =
+    "assets/models/hand_landmark_detection/hand_landmarker.task";
+
+#[test]
+fn test_image_model_info() {
+    let model_info = ModelInfo::from_path(IMAGE_CLASSIFICATION_MODEL).unwrap();
+    eprintln!("{}", model_info);
+
+    assert_eq!(model_info.input_tensor_count(), 1);
+    assert_eq!(model_info.output_tensor_count(), 1);
+    assert_eq!(model_info.input_tensor_type(0), Some(TensorType::U8));
+    assert_eq!(
+        model_info.input_tensor_shape(0),
+        Some([1, 224, 224, 3].as_ref())
+    );
+    assert_eq!(model_info.output_tensor_shape(0), Some([1, 1000].as_ref()));
+    assert!(model_info
+        .input_tensor_quantization_parameters(0)
+        .is_some());
+    assert!(model_info
+        .output_tensor_quantization_parameters(0)
+        .is_some());
+
+    let labels = model_info.output_tensor_labels(0).unwrap();
+    assert_eq!(labels.len(), 1000);
+
+    let img_info = model_info
+        .to_tensor_info(0)
+        .unwrap()
+        .try_to_image()
+        .unwrap();
+    assert_eq!(img_info.normalization_options.0.len(), 1);
+    let files = model_info.associated_files();
+    assert!(!files.is_empty());
+    for name in files {
+        assert!(model_info.associated_file(name).unwrap().is_some());
+    }
+    assert!(model_info.output_tensor_labels(1).is_none());
+}
+
+#[test]
+fn test_text_model_info() {
+    let buf = std::fs::read(TEXT_CLASSIFICATION_MODEL).unwrap();
+    let model_info = ModelInfo::from_buffer(buf).unwrap();
+    eprintln!("{}", model_info);
+
+    assert_eq!(model_info.input_tensor_count(), 3);
+    for i in 0..3 {
+        assert_eq!(model_info.input_tensor_type(i), Some(TensorType::I32));
+    }
+    assert!(model_info
+        .input_tensor_quantization_parameters(0)
+        .is_none());
+    assert!(model_info.to_tensor_info(0).unwrap().try_to_text().is_ok());
+    assert!(model_info
+        .to_tensor_info(0)
+        .unwrap()
+        .try_to_image()
+        .is_err());
+}
+
+#[test]
+fn test_bundle_model_info() {
+    let model_info = ModelInfo::from_path(HAND_LANDMARK_MODEL).unwrap();
+    eprintln!("{}", model_info);
+
+    assert!(model_info.is_model_asset_bundle());
+    assert_eq!(model_info.input_tensor_count(), 0);
+    assert_eq!(
+        model_info.bundle_file_names(),
+        vec!["hand_detector.tflite", "hand_landmarks_detector.tflite"]
+    );
+    let models = model_info.bundle_model_infos().unwrap();
+    assert_eq!(models.len(), 2);
+    for (name, info) in models {
+        assert!(!info.is_model_asset_bundle(), "{}", name);
+        assert_eq!(info.input_tensor_count(), 1);
+        assert!(info.to_tensor_info(0).unwrap().try_to_image().is_ok());
+    }
+    assert!(model_info.bundle_model_info("not_exists.tflite").is_err());
+}
+
+#[test]
+fn test_invalid_model_info() {
+    assert!(ModelInfo::from_buffer(vec![0; 4]).is_err());
+    // a zip magic without zip records
+    assert!(ModelInfo::from_buffer(vec![0x50, 0x4b, 0x03, 0x04, 0, 0, 0, 0]).is_err());
+}
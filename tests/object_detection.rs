@@ -1,3 +1,5 @@
+mod common;
+
 use mediapipe_rs::tasks::vision::ObjectDetectorBuilder;
 
 const MODEL_1: &'static str = "assets/models/object_detection/efficientdet_lite0_fp32.tflite";
@@ -40,6 +42,7 @@ fn test_object_detection_model_6() {
 
 fn object_detection_task_run(model_asset_path: &str) {
     let object_detector = ObjectDetectorBuilder::new()
+        .inference_backend(common::inference_backend("object_detection"))
         .model_asset_path(model_asset_path)
         .cpu()
         .max_results(5)
@@ -55,6 +58,7 @@ fn object_detection_task_run(model_asset_path: &str) {
 #[test]
 fn test_allow_deny_list() {
     let res = ObjectDetectorBuilder::new()
+        .inference_backend(common::inference_backend("object_detection_deny_list"))
         .model_asset_path(MODEL_1)
         .cpu()
         .max_results(1)
@@ -73,6 +77,7 @@ fn test_allow_deny_list() {
     );
 
     let res = ObjectDetectorBuilder::new()
+        .inference_backend(common::inference_backend("object_detection_allow_list"))
         .model_asset_path(MODEL_1)
         .cpu()
         .max_results(1)
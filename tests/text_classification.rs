@@ -1,3 +1,5 @@
+mod common;
+
 use mediapipe_rs::tasks::text::TextClassifierBuilder;
 
 const MODEL_1: &'static str = "assets/models/text_classification/average_word_embedding.tflite";
@@ -17,6 +19,7 @@ fn test_model_2() {
 
 fn text_classification_task_run(model_asset_path: &str, positive_name: &str, negative_name: &str) {
     let classification_result = TextClassifierBuilder::new()
+        .inference_backend(common::inference_backend("text_classification"))
         .model_asset_path(model_asset_path)
         .finalize()
         .unwrap()
@@ -34,6 +37,7 @@ fn text_classification_task_run(model_asset_path: &str, positive_name: &str, neg
 #[test]
 fn test_bert() {
     let classifier = TextClassifierBuilder::new()
+        .inference_backend(common::inference_backend("text_classification_bert"))
         .model_asset_path(MODEL_2)
         .max_results(1)
         .finalize()